@@ -1,13 +1,22 @@
+use crate::context::grid::SplitDirection;
 use crate::event::{ClickState, EventPayload, EventProxy, RioEvent, RioEventType};
 use crate::ime::Preedit;
 use crate::renderer::utils::update_colors_based_on_theme;
-use crate::router::{routes::RoutePath, Router};
+use crate::router::{
+    routes::{palette::PaletteCommand, RoutePath},
+    Router,
+};
 use crate::scheduler::{Scheduler, TimerId, Topic};
 use crate::screen::touch::on_touch;
-use crate::watcher::configuration_file_updates;
+use crate::watcher::{configuration_file_updates, imports_to_watch, ConfigWatcher};
 use raw_window_handle::HasDisplayHandle;
 use rio_backend::clipboard::{Clipboard, ClipboardType};
 use rio_backend::config::colors::ColorRgb;
+use rio_backend::config::overrides::ConfigOverrides;
+use rio_backend::config::{ConfigError, ConfigFile};
+use rio_backend::crosswords::export::ExportFormat;
+use rio_backend::error::{ConfigFallback, RioError};
+use rio_backend::performer::player::Recording;
 use rio_window::application::ApplicationHandler;
 use rio_window::event::{
     ElementState, Hook, Ime, MouseButton, MouseScrollDelta, StartCause, TouchPhase,
@@ -22,20 +31,44 @@ use rio_window::platform::macos::ActiveEventLoopExtMacOS;
 use rio_window::platform::macos::WindowExtMacOS;
 use rio_window::window::WindowId;
 use rio_window::window::{CursorIcon, Fullscreen};
+use std::collections::HashMap;
 use std::error::Error;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 pub struct Application<'a> {
     config: rio_backend::config::Config,
+    /// File of the last valid config, decoded again with the overrides of
+    /// each window. `None` when there is no config file.
+    config_file: Option<ConfigFile>,
+    /// Overrides passed with `-o`, applied again on every reload.
+    config_overrides: ConfigOverrides,
+    /// Overrides of the windows opened through IPC.
+    window_overrides: HashMap<WindowId, ConfigOverrides>,
+    /// Theme picked in the command palette and the `theme` of the config
+    /// when it was picked, kept over reloads until the config changes it.
+    palette_theme: Option<(String, String)>,
     event_proxy: EventProxy,
     router: Router<'a>,
     scheduler: Scheduler,
+    config_watcher: Option<ConfigWatcher>,
+    startup_layout: Option<String>,
+    startup_record: Option<PathBuf>,
+    startup_playback: Option<Recording>,
+    #[cfg(unix)]
+    ipc_socket: Option<PathBuf>,
 }
 
 impl Application<'_> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new<'app>(
         config: rio_backend::config::Config,
         config_error: Option<rio_backend::config::ConfigError>,
+        config_file: Option<ConfigFile>,
+        config_overrides: ConfigOverrides,
+        startup_layout: Option<String>,
+        startup_record: Option<PathBuf>,
+        startup_playback: Option<Recording>,
         event_loop: &EventLoop<EventPayload>,
     ) -> Application<'app> {
         // SAFETY: Since this takes a pointer to the winit event loop, it MUST be dropped first,
@@ -44,16 +77,25 @@ impl Application<'_> {
             unsafe { Clipboard::new(event_loop.display_handle().unwrap().as_raw()) };
 
         let mut router = Router::new(config.fonts.to_owned(), clipboard);
+        let imported = imports_to_watch(&config.imported, config_error.as_ref());
         if let Some(error) = config_error {
             router.propagate_error_to_next_route(error.into());
+        } else if !config.diagnostics.is_empty() {
+            router.propagate_error_to_next_route(RioError::configuration_warnings(
+                config.diagnostics.clone(),
+            ));
         }
 
         let proxy = event_loop.create_proxy();
         let event_proxy = EventProxy::new(proxy.clone());
-        let _ = configuration_file_updates(
+        let config_watcher = configuration_file_updates(
             rio_backend::config::config_dir_path(),
+            &imported,
             event_proxy.clone(),
-        );
+        )
+        .ok();
+        #[cfg(unix)]
+        let ipc_socket = crate::ipc::spawn_ipc_socket(event_proxy.clone());
         let scheduler = Scheduler::new(proxy);
         event_loop.listen_device_events(DeviceEvents::Never);
 
@@ -62,9 +104,32 @@ impl Application<'_> {
 
         Application {
             config,
+            config_file,
+            config_overrides,
+            window_overrides: HashMap::new(),
+            palette_theme: None,
             event_proxy,
             router,
             scheduler,
+            config_watcher,
+            startup_layout,
+            startup_record,
+            startup_playback,
+            #[cfg(unix)]
+            ipc_socket,
+        }
+    }
+
+    /// Config of a window opened with its own overrides, which go over the
+    /// ones of the command line. Uses the config of the other windows when
+    /// the overrides of the window are invalid.
+    fn window_config(&self, overrides: &ConfigOverrides) -> rio_backend::config::Config {
+        let overrides = self.config_overrides.merged(overrides);
+        match &self.config_file {
+            Some(config_file) => config_file
+                .load(&overrides)
+                .unwrap_or_else(|_| self.config.clone()),
+            None => rio_backend::config::Config::default_with_overrides(&overrides),
         }
     }
 
@@ -128,13 +193,36 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
 
         update_colors_based_on_theme(&mut self.config, event_loop.system_theme());
 
+        // Layout from `--layout` only applies to the first window
         self.router.create_window(
             event_loop,
             self.event_proxy.clone(),
             &self.config,
             None,
+            self.startup_layout.take(),
         );
 
+        // Playback from `--play` replaces the shell of the first window
+        if let Some(recording) = self.startup_playback.take() {
+            for route in self.router.routes.values_mut() {
+                route
+                    .window
+                    .screen
+                    .context_manager
+                    .start_playback(recording.clone());
+            }
+        }
+
+        // Recording from `--record` only applies to the first window as well
+        if let Some(path) = self.startup_record.take() {
+            for route in self.router.routes.values_mut() {
+                let context_manager = &mut route.window.screen.context_manager;
+                if context_manager.start_recording(path.clone()) {
+                    context_manager.update_titles();
+                }
+            }
+        }
+
         tracing::info!("Initialisation complete");
     }
 
@@ -152,6 +240,24 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                     route.request_redraw();
                 }
             }
+            RioEventType::Rio(RioEvent::Output(route_id)) => {
+                if let Some(route) = self.router.routes.get_mut(&window_id) {
+                    let is_focused = route.window.is_focused;
+                    let context_manager = &mut route.window.screen.context_manager;
+                    if let Some(interval) =
+                        context_manager.monitor_output(route_id, is_focused)
+                    {
+                        // Restarted on every output, fires once the route stays quiet
+                        let timer_id = TimerId::new(Topic::MonitorSilence, route_id);
+                        let event = EventPayload::new(
+                            RioEventType::Rio(RioEvent::MonitorSilence(route_id)),
+                            window_id,
+                        );
+                        self.scheduler.unschedule(timer_id);
+                        self.scheduler.schedule(event, interval, false, timer_id);
+                    }
+                }
+            }
             RioEventType::Rio(RioEvent::RenderRoute(route_id)) => {
                 if self.config.renderer.strategy.is_event_based() {
                     if let Some(route) = self.router.routes.get_mut(&window_id) {
@@ -226,12 +332,48 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                 }
             }
             RioEventType::Rio(RioEvent::UpdateConfig) => {
-                let (config, config_error) = match rio_backend::config::Config::try_load()
-                {
-                    Ok(config) => (config, None),
-                    Err(error) => (rio_backend::config::Config::default(), Some(error)),
+                let loaded = ConfigFile::read().and_then(|config_file| {
+                    let config = config_file.load(&self.config_overrides)?;
+                    Ok((config_file, config))
+                });
+                let (mut config, config_error) = match loaded {
+                    Ok((config_file, config)) => {
+                        self.config_file = Some(config_file);
+                        (config, None)
+                    }
+                    Err(error @ ConfigError::PathNotFound) => {
+                        self.config_file = None;
+                        (
+                            rio_backend::config::Config::default_with_overrides(
+                                &self.config_overrides,
+                            ),
+                            Some(error),
+                        )
+                    }
+                    // Keeps the last valid config while the file has errors
+                    Err(error) => (self.config.clone(), Some(error)),
                 };
 
+                if let Some((theme, config_theme)) = self.palette_theme.take() {
+                    // The last valid config already has the picked theme
+                    if config.theme == config_theme || config.theme == theme {
+                        if let Err(err) = config.set_theme(&theme) {
+                            tracing::warn!("failed to load theme {theme}: {err}");
+                        }
+                        self.palette_theme = Some((theme, config_theme));
+                    }
+                }
+
+                // Imports may have been added or removed, keeps watching the
+                // previous ones while the config has errors along with the
+                // ones it failed to import
+                if let Some(config_watcher) = &mut self.config_watcher {
+                    config_watcher.watch_imported(&imports_to_watch(
+                        &config.imported,
+                        config_error.as_ref(),
+                    ));
+                }
+
                 let has_font_updates = self.config.fonts != config.fonts;
 
                 let font_library_errors = if has_font_updates {
@@ -245,7 +387,18 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                 };
 
                 self.config = config;
-                for (_id, route) in self.router.routes.iter_mut() {
+
+                let routes = &self.router.routes;
+                self.window_overrides
+                    .retain(|id, _| routes.contains_key(id));
+                let window_configs: HashMap<WindowId, rio_backend::config::Config> = self
+                    .window_overrides
+                    .iter()
+                    .map(|(id, overrides)| (*id, self.window_config(overrides)))
+                    .collect();
+
+                for (id, route) in self.router.routes.iter_mut() {
+                    let config = window_configs.get(id).unwrap_or(&self.config);
                     if has_font_updates {
                         if let Some(ref err) = font_library_errors {
                             route
@@ -259,14 +412,21 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                     }
 
                     route.update_config(
-                        &self.config,
+                        config,
                         &self.router.font_library,
                         has_font_updates,
                     );
-                    route.window.configure_window(&self.config);
+                    route.window.configure_window(config);
 
                     if let Some(error) = &config_error {
-                        route.report_error(&error.to_owned().into());
+                        route.report_error(&RioError::configuration_error(
+                            error.to_owned(),
+                            ConfigFallback::LastValid,
+                        ));
+                    } else if !self.config.diagnostics.is_empty() {
+                        route.report_error(&RioError::configuration_warnings(
+                            self.config.diagnostics.clone(),
+                        ));
                     } else {
                         route.clear_errors();
                     }
@@ -472,13 +632,83 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                         .send_bytes(format(rgb).into_bytes());
                 }
             }
+            RioEventType::Rio(RioEvent::SaveScreen(path, format, scrollback)) => {
+                let route = self
+                    .router
+                    .routes
+                    .values()
+                    .find(|route| route.window.is_focused)
+                    .or_else(|| self.router.routes.values().next());
+
+                if let Some(route) = route {
+                    let format = format
+                        .or_else(|| ExportFormat::from_path(&path))
+                        .unwrap_or(self.config.export.format);
+                    let context_manager = &route.window.screen.context_manager;
+                    if let Err(err) = context_manager.export(&path, format, scrollback) {
+                        tracing::error!("unable to save {path:?}: {err}");
+                    }
+                }
+            }
+            RioEventType::Rio(RioEvent::MonitorSilence(route_id)) => {
+                if let Some(route) = self.router.routes.get_mut(&window_id) {
+                    let is_focused = route.window.is_focused;
+                    route
+                        .window
+                        .screen
+                        .context_manager
+                        .monitor_silence(route_id, is_focused);
+                }
+            }
+            RioEventType::Rio(RioEvent::Trigger(route_id, action)) => {
+                if let Some(route) = self.router.routes.get(&window_id) {
+                    route
+                        .window
+                        .screen
+                        .context_manager
+                        .run_trigger(route_id, action);
+                }
+            }
+            RioEventType::Rio(RioEvent::Bell) => {
+                if let Some(route) = self.router.routes.get_mut(&window_id) {
+                    let route_id = route.window.screen.ctx().current_route();
+                    route.window.screen.context_manager.ring_bell(route_id);
+                    route.signal_bell(&self.config.bell);
+                }
+            }
+            RioEventType::Rio(RioEvent::BellOnRoute(route_id)) => {
+                if let Some(route) = self.router.routes.get_mut(&window_id) {
+                    let focused = route.window.is_focused;
+                    if route.window.screen.context_manager.bell(route_id, focused) {
+                        route.signal_bell(&self.config.bell);
+                    }
+                }
+            }
+            RioEventType::Rio(RioEvent::CommandPalette) => {
+                if let Some(route) = self.router.routes.get_mut(&window_id) {
+                    route.open_command_palette();
+                    route.request_redraw();
+                }
+            }
             RioEventType::Rio(RioEvent::CreateWindow) => {
                 self.router.create_window(
                     event_loop,
                     self.event_proxy.clone(),
                     &self.config,
                     None,
+                    None,
+                );
+            }
+            RioEventType::Rio(RioEvent::CreateWindowWithOverrides(overrides)) => {
+                let config = self.window_config(&overrides);
+                let id = self.router.create_window(
+                    event_loop,
+                    self.event_proxy.clone(),
+                    &config,
+                    None,
+                    None,
                 );
+                self.window_overrides.insert(id, overrides);
             }
             #[cfg(target_os = "macos")]
             RioEventType::Rio(RioEvent::CreateNativeTab(working_dir_overwrite)) => {
@@ -589,6 +819,7 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                     self.event_proxy.clone(),
                     config,
                     Some(url),
+                    None,
                 );
             }
             return;
@@ -703,6 +934,13 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
 
                 match state {
                     ElementState::Pressed => {
+                        // Dragging a border between splits
+                        if button == MouseButton::Left
+                            && route.window.screen.start_split_border_drag()
+                        {
+                            return;
+                        }
+
                         // In case need to switch grid current
                         route.window.screen.select_current_based_on_mouse();
 
@@ -730,8 +968,6 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                                 .window
                                 .screen
                                 .mouse_report(code, ElementState::Pressed);
-
-                            route.window.screen.process_mouse_bindings(button);
                         } else {
                             // Calculate time since the last click to handle double/triple clicks.
                             let now = Instant::now();
@@ -758,6 +994,12 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                                 _ => ClickState::Click,
                             };
 
+                            // Bindings replace the default behavior of the click
+                            if route.window.screen.process_mouse_bindings(button) {
+                                route.request_redraw();
+                                return;
+                            }
+
                             // Load mouse point, treating message bar and padding as the closest square.
                             let display_offset = route.window.screen.display_offset();
 
@@ -769,9 +1011,14 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
 
                             route.request_redraw();
                         }
-                        route.window.screen.process_mouse_bindings(button);
                     }
                     ElementState::Released => {
+                        if button == MouseButton::Left
+                            && route.window.screen.stop_split_border_drag()
+                        {
+                            return;
+                        }
+
                         if !route.window.screen.modifiers.state().shift_key()
                             && route.window.screen.mouse_mode()
                         {
@@ -850,6 +1097,30 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                 route.window.screen.mouse.x = x;
                 route.window.screen.mouse.y = y;
 
+                if route.window.screen.update_split_border_drag() {
+                    return;
+                }
+
+                if !lmb_pressed && !rmb_pressed {
+                    if let Some(edge) = route.window.screen.split_border_under_mouse() {
+                        let cursor_icon = match edge {
+                            SplitDirection::Left | SplitDirection::Right => {
+                                CursorIcon::ColResize
+                            }
+                            SplitDirection::Up | SplitDirection::Down => {
+                                CursorIcon::RowResize
+                            }
+                        };
+                        route.window.winit_window.set_cursor(cursor_icon);
+                        route.window.screen.mouse.over_split_border = true;
+                        return;
+                    }
+                }
+
+                // Leaving a border resets the resize icon, even within the cell
+                let left_split_border =
+                    std::mem::take(&mut route.window.screen.mouse.over_split_border);
+
                 let point = route.window.screen.mouse_position(display_offset);
 
                 let square_changed = old_point != point;
@@ -859,6 +1130,7 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
 
                 // If the mouse hasn't changed cells, do nothing.
                 if !square_changed
+                    && !left_split_border
                     && route.window.screen.mouse.square_side == square_side
                     && route.window.screen.mouse.inside_text_area == inside_text_area
                 {
@@ -974,6 +1246,39 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                 event: key_event,
                 ..
             } => {
+                if route.path == RoutePath::CommandPalette {
+                    match route.palette_key(&key_event) {
+                        Some(PaletteCommand::Action(action)) => {
+                            route.window.screen.run_action(&action);
+                        }
+                        Some(PaletteCommand::Theme(theme)) => {
+                            let mut config = self.config.clone();
+                            if let Err(err) = config.set_theme(&theme) {
+                                tracing::warn!("failed to load theme {theme}: {err}");
+                            } else {
+                                let config_theme = match self.palette_theme.take() {
+                                    Some((_, config_theme)) => config_theme,
+                                    None => self.config.theme.clone(),
+                                };
+                                self.palette_theme = Some((theme, config_theme));
+                                self.config = config;
+                                for (_id, route) in self.router.routes.iter_mut() {
+                                    route.update_config(
+                                        &self.config,
+                                        &self.router.font_library,
+                                        false,
+                                    );
+                                    route.request_redraw();
+                                }
+                            }
+                            return;
+                        }
+                        None => {}
+                    }
+                    route.request_redraw();
+                    return;
+                }
+
                 if route.has_key_wait(&key_event) {
                     if route.path != RoutePath::Terminal
                         && key_event.state == ElementState::Released
@@ -1002,6 +1307,14 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                     return;
                 }
 
+                if route.path == RoutePath::CommandPalette {
+                    if let Ime::Commit(text) = ime {
+                        route.palette.push(&text);
+                        route.request_redraw();
+                    }
+                    return;
+                }
+
                 match ime {
                     Ime::Commit(text) => {
                         // Don't use bracketed paste for single char input.
@@ -1119,8 +1432,52 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                     RoutePath::Welcome => {
                         route.window.screen.render_welcome();
                     }
+                    RoutePath::CommandPalette => {
+                        route.window.screen.render_palette(&route.palette);
+                    }
                     RoutePath::Terminal => {
                         route.window.screen.render();
+
+                        // Frames of the visual bell fading out
+                        if route.window.screen.context_manager.has_visual_bell() {
+                            let timer_id = TimerId::new(
+                                Topic::VisualBell,
+                                route.window.screen.ctx().current_route(),
+                            );
+                            if !self.scheduler.scheduled(timer_id) {
+                                let event = EventPayload::new(
+                                    RioEventType::Rio(RioEvent::Render),
+                                    window_id,
+                                );
+                                self.scheduler.schedule(
+                                    event,
+                                    Duration::from_millis(16),
+                                    false,
+                                    timer_id,
+                                );
+                            }
+                        }
+
+                        // Hides the key table indicator once it times out
+                        if let Some(deadline) = route.window.screen.key_tables_deadline()
+                        {
+                            let timer_id = TimerId::new(
+                                Topic::KeyTables,
+                                route.window.screen.ctx().current_route(),
+                            );
+                            if !self.scheduler.scheduled(timer_id) {
+                                let event = EventPayload::new(
+                                    RioEventType::Rio(RioEvent::Render),
+                                    window_id,
+                                );
+                                self.scheduler.schedule(
+                                    event,
+                                    deadline.saturating_duration_since(Instant::now()),
+                                    false,
+                                    timer_id,
+                                );
+                            }
+                        }
                     }
                     RoutePath::ConfirmQuit => {
                         route
@@ -1221,6 +1578,11 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
         // Renderer and contexts ran.
         self.router.routes.clear();
 
+        #[cfg(unix)]
+        if let Some(socket) = &self.ipc_socket {
+            let _ = std::fs::remove_file(socket);
+        }
+
         // SAFETY: The clipboard must be dropped before the event loop, so use the nop clipboard
         // as a safe placeholder.
         std::mem::swap(
@@ -0,0 +1,154 @@
+// Conditions of the `when` field of bindings on what's running in the
+// terminal, like `~process=nvim` to leave `control+h` to an editor with
+// its own split navigation. Every condition must hold, like modes.
+
+use crate::crosswords::Mode;
+use std::cell::OnceCell;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    /// The foreground process has any of the names.
+    Process(Vec<String>),
+    AltScreen,
+    /// The application requests mouse reports.
+    MouseReporting,
+    /// Index of the focused split in the tab.
+    Pane(usize),
+    Not(Box<Condition>),
+}
+
+/// Parses conditions like `~process=nvim,vim | altscreen`.
+pub fn parse(when: &str) -> Result<Vec<Condition>, String> {
+    let mut conditions = Vec::new();
+    for item in when
+        .split('|')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+    {
+        let (negated, item) = match item.strip_prefix('~') {
+            Some(item) => (true, item.trim()),
+            None => (false, item),
+        };
+
+        let condition = match item.split_once('=') {
+            Some((name, value)) if name.trim().eq_ignore_ascii_case("process") => {
+                Condition::Process(
+                    value
+                        .split(',')
+                        .map(|name| name.trim().to_string())
+                        .filter(|name| !name.is_empty())
+                        .collect(),
+                )
+            }
+            Some((name, value)) if name.trim().eq_ignore_ascii_case("pane") => {
+                let index = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Invalid pane index '{}'", value.trim()))?;
+                Condition::Pane(index)
+            }
+            _ => match item.to_lowercase().as_str() {
+                "altscreen" => Condition::AltScreen,
+                "mouse" => Condition::MouseReporting,
+                _ => return Err(format!("Unable to find condition '{item}'")),
+            },
+        };
+
+        conditions.push(if negated {
+            Condition::Not(Box::new(condition))
+        } else {
+            condition
+        });
+    }
+    Ok(conditions)
+}
+
+/// Terminal state the conditions are checked against, the foreground
+/// process is only looked up once a condition needs it.
+pub struct ConditionState {
+    mode: Mode,
+    pane: usize,
+    process: OnceCell<String>,
+    lookup: Box<dyn Fn() -> String>,
+}
+
+impl ConditionState {
+    pub fn new(mode: Mode, pane: usize, lookup: Box<dyn Fn() -> String>) -> Self {
+        ConditionState {
+            mode,
+            pane,
+            process: OnceCell::new(),
+            lookup,
+        }
+    }
+
+    #[inline]
+    pub fn matches(&self, conditions: &[Condition]) -> bool {
+        conditions.iter().all(|condition| self.check(condition))
+    }
+
+    fn check(&self, condition: &Condition) -> bool {
+        match condition {
+            Condition::Process(names) => {
+                let process = self.process.get_or_init(|| (self.lookup)());
+                names.iter().any(|name| name == process)
+            }
+            Condition::AltScreen => self.mode.contains(Mode::ALT_SCREEN),
+            Condition::MouseReporting => self.mode.intersects(Mode::MOUSE_MODE),
+            Condition::Pane(index) => self.pane == *index,
+            Condition::Not(condition) => !self.check(condition),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn parse_conditions() {
+        assert_eq!(parse("").unwrap(), vec![]);
+        assert_eq!(
+            parse("~process=nvim, vim | AltScreen | ~mouse | pane=1").unwrap(),
+            vec![
+                Condition::Not(Box::new(Condition::Process(vec![
+                    String::from("nvim"),
+                    String::from("vim")
+                ]))),
+                Condition::AltScreen,
+                Condition::Not(Box::new(Condition::MouseReporting)),
+                Condition::Pane(1),
+            ]
+        );
+        assert!(parse("pane=first").is_err());
+        assert!(parse("vi").is_err());
+    }
+
+    #[test]
+    fn check_conditions() {
+        let lookups = Rc::new(Cell::new(0));
+        let counter = lookups.clone();
+        let state = ConditionState::new(
+            Mode::ALT_SCREEN | Mode::MOUSE_REPORT_CLICK,
+            1,
+            Box::new(move || {
+                counter.set(counter.get() + 1);
+                String::from("nvim")
+            }),
+        );
+
+        assert!(state.matches(&[]));
+        assert!(state.matches(&parse("altscreen | mouse | pane=1").unwrap()));
+        assert_eq!(lookups.get(), 0);
+        assert!(!state.matches(&parse("~process=nvim").unwrap()));
+        assert!(state.matches(&parse("process=vim,nvim").unwrap()));
+        assert!(!state.matches(&parse("pane=0").unwrap()));
+        assert_eq!(lookups.get(), 1);
+
+        let state = ConditionState::new(Mode::empty(), 0, Box::new(String::new));
+        assert!(state.matches(&parse("~altscreen | ~mouse | pane=0").unwrap()));
+        assert!(!state.matches(&parse("process=nvim").unwrap()));
+    }
+}
@@ -0,0 +1,175 @@
+// Conversions between the key events of the window and the keys encoded by
+// rio_backend::keyboard, which doesn't depend on the window system.
+
+use rio_backend::keyboard::{
+    Key as InputKey, KeyInput, KeyLocation as InputKeyLocation, KeyModifiers, KeyState,
+    NamedKey as InputNamedKey,
+};
+use rio_window::event::{ElementState, KeyEvent};
+use rio_window::keyboard::{Key, KeyLocation, ModifiersState, NamedKey};
+use rio_window::platform::modifier_supplement::KeyEventExtModifierSupplement;
+
+macro_rules! named_keys {
+    ($($name:ident),* $(,)?) => {
+        fn input_named_key(key: NamedKey) -> Option<InputNamedKey> {
+            match key {
+                $(NamedKey::$name => Some(InputNamedKey::$name),)*
+                _ => None,
+            }
+        }
+
+        /// Named key of the window for a key encoded by the backend.
+        pub fn window_named_key(key: InputNamedKey) -> NamedKey {
+            match key {
+                $(InputNamedKey::$name => NamedKey::$name,)*
+            }
+        }
+    };
+}
+
+named_keys!(
+    Enter,
+    Tab,
+    Space,
+    Backspace,
+    Escape,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    End,
+    Home,
+    PageDown,
+    PageUp,
+    Insert,
+    Delete,
+    Alt,
+    CapsLock,
+    Control,
+    Hyper,
+    Meta,
+    NumLock,
+    ScrollLock,
+    Shift,
+    Super,
+    ContextMenu,
+    Pause,
+    PrintScreen,
+    MediaFastForward,
+    MediaPause,
+    MediaPlay,
+    MediaPlayPause,
+    MediaRecord,
+    MediaRewind,
+    MediaStop,
+    MediaTrackNext,
+    MediaTrackPrevious,
+    AudioVolumeDown,
+    AudioVolumeUp,
+    AudioVolumeMute,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+    F25,
+    F26,
+    F27,
+    F28,
+    F29,
+    F30,
+    F31,
+    F32,
+    F33,
+    F34,
+    F35,
+);
+
+fn input_key(key: &Key) -> InputKey {
+    match key {
+        Key::Named(named) => match input_named_key(*named) {
+            Some(named) => InputKey::Named(named),
+            None => InputKey::Unidentified,
+        },
+        Key::Character(character) => InputKey::Character(character.to_string()),
+        _ => InputKey::Unidentified,
+    }
+}
+
+/// Parts of the key event encoded by the backend.
+pub fn key_input(key: &KeyEvent) -> KeyInput<'_> {
+    KeyInput {
+        logical_key: input_key(&key.logical_key),
+        key_without_modifiers: input_key(&key.key_without_modifiers()),
+        location: match key.location {
+            KeyLocation::Standard => InputKeyLocation::Standard,
+            KeyLocation::Left => InputKeyLocation::Left,
+            KeyLocation::Right => InputKeyLocation::Right,
+            KeyLocation::Numpad => InputKeyLocation::Numpad,
+        },
+        state: match key.state {
+            ElementState::Pressed => KeyState::Pressed,
+            ElementState::Released => KeyState::Released,
+        },
+        repeat: key.repeat,
+        text: key.text_with_all_modifiers(),
+    }
+}
+
+pub fn key_modifiers(mods: ModifiersState) -> KeyModifiers {
+    let mut modifiers = KeyModifiers::empty();
+    modifiers.set(KeyModifiers::SHIFT, mods.shift_key());
+    modifiers.set(KeyModifiers::CONTROL, mods.control_key());
+    modifiers.set(KeyModifiers::ALT, mods.alt_key());
+    modifiers.set(KeyModifiers::SUPER, mods.super_key());
+    modifiers
+}
+
+pub fn modifiers_state(mods: KeyModifiers) -> ModifiersState {
+    let mut modifiers = ModifiersState::empty();
+    modifiers.set(ModifiersState::SHIFT, mods.contains(KeyModifiers::SHIFT));
+    modifiers.set(
+        ModifiersState::CONTROL,
+        mods.contains(KeyModifiers::CONTROL),
+    );
+    modifiers.set(ModifiersState::ALT, mods.contains(KeyModifiers::ALT));
+    modifiers.set(ModifiersState::SUPER, mods.contains(KeyModifiers::SUPER));
+    modifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named_keys_round_trip() {
+        for sequence in rio_backend::keyboard::DEFAULT_SEQUENCES {
+            let named = window_named_key(sequence.key);
+            assert_eq!(input_named_key(named), Some(sequence.key));
+            assert_eq!(key_modifiers(modifiers_state(sequence.mods)), sequence.mods);
+        }
+        assert_eq!(
+            input_key(&Key::Named(NamedKey::Copy)),
+            InputKey::Unidentified
+        );
+    }
+}
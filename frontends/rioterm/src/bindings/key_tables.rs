@@ -0,0 +1,390 @@
+// Multi-key sequences, like `control+a |` with `control+a` as leader key,
+// and key tables activated by `ActivateKeyTable:name`. Both are left after
+// `[bindings] timeout` without key presses, key tables also with Escape.
+
+use crate::bindings::conditions::ConditionState;
+use crate::bindings::{chord_label, BindingKey, BindingMode, KeyBinding};
+use rio_window::keyboard::{Key, ModifiersState, NamedKey};
+use std::time::{Duration, Instant};
+
+/// Modifiers and key pressed at once.
+pub type Chord = (ModifiersState, BindingKey);
+
+/// Key press matched against the sequences of the bindings.
+#[derive(Debug, PartialEq)]
+pub enum SequenceMatch {
+    /// Last chord of the sequence of the binding at the index.
+    Complete(usize),
+    /// Continues one or more sequences with the given chord.
+    Partial(Chord),
+    /// Modifier key pressed alone, like Shift before `|`, which leaves the
+    /// pending sequence as it is.
+    Modifier,
+    None,
+}
+
+/// Whether the key is a modifier key, which winit also reports as pressed
+/// on its own.
+#[inline]
+fn is_modifier(key: &BindingKey) -> bool {
+    matches!(
+        key,
+        BindingKey::Keycode {
+            key: Key::Named(
+                NamedKey::Shift
+                    | NamedKey::Control
+                    | NamedKey::Alt
+                    | NamedKey::AltGraph
+                    | NamedKey::Super
+                    | NamedKey::Meta
+                    | NamedKey::Hyper
+                    | NamedKey::Fn
+            ),
+            ..
+        }
+    )
+}
+
+#[derive(Debug, Default)]
+pub struct KeyTables {
+    /// Chords typed so far of a sequence.
+    pending: Vec<Chord>,
+    /// Activated key tables, the last one is the active one.
+    tables: Vec<String>,
+    timeout: Option<Duration>,
+    deadline: Option<Instant>,
+}
+
+impl KeyTables {
+    pub fn new(timeout: u64) -> KeyTables {
+        let mut key_tables = KeyTables::default();
+        key_tables.set_timeout(timeout);
+        key_tables
+    }
+
+    /// Sets the timeout in milliseconds, `0` to wait forever.
+    #[inline]
+    pub fn set_timeout(&mut self, timeout: u64) {
+        self.timeout = (timeout > 0).then(|| Duration::from_millis(timeout));
+    }
+
+    #[inline]
+    pub fn active(&self) -> Option<&str> {
+        self.tables.last().map(String::as_str)
+    }
+
+    #[inline]
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Activates the table over the others, moving it to the top if it
+    /// was already active.
+    pub fn activate(&mut self, table: String) {
+        self.tables.retain(|active| *active != table);
+        self.tables.push(table);
+        self.touch();
+    }
+
+    #[inline]
+    pub fn pop(&mut self) -> Option<String> {
+        self.tables.pop()
+    }
+
+    #[inline]
+    pub fn push_chord(&mut self, chord: Chord) {
+        self.pending.push(chord);
+        self.touch();
+    }
+
+    #[inline]
+    pub fn clear_pending(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Restarts the timeout, called on every key press handled by a
+    /// sequence or a key table.
+    #[inline]
+    pub fn touch(&mut self) {
+        self.deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+    }
+
+    /// When the pending sequence and the key tables are left, if any.
+    pub fn deadline(&self) -> Option<Instant> {
+        if self.pending.is_empty() && self.tables.is_empty() {
+            return None;
+        }
+        self.deadline
+    }
+
+    /// Leaves the pending sequence and the key tables once timed out.
+    pub fn expire(&mut self, now: Instant) {
+        if self.deadline.is_some_and(|deadline| deadline <= now) {
+            self.pending.clear();
+            self.tables.clear();
+            self.deadline = None;
+        }
+    }
+
+    /// Shown while a sequence is typed (its chords so far) or a key table
+    /// is active (its name).
+    pub fn indicator(&self) -> Option<String> {
+        if !self.pending.is_empty() {
+            let chords: Vec<String> = self
+                .pending
+                .iter()
+                .map(|(mods, key)| chord_label(*mods, key))
+                .collect();
+            return Some(chords.join(" "));
+        }
+        self.active().map(str::to_string)
+    }
+
+    /// Matches the key press, any of the `inputs` chords, against the
+    /// next chord of the sequences starting with the pending ones.
+    pub fn matches(
+        &self,
+        bindings: &[KeyBinding],
+        inputs: &[Chord],
+        mode: &BindingMode,
+        conditions: &ConditionState,
+    ) -> SequenceMatch {
+        if inputs.iter().all(|(_, key)| is_modifier(key)) {
+            return SequenceMatch::Modifier;
+        }
+
+        let position = self.pending.len();
+        let mut partial = None;
+
+        for (index, binding) in bindings.iter().enumerate() {
+            if binding.prefix.len() < position
+                || binding.prefix[..position] != self.pending[..]
+                || !binding.is_active(mode, self.active())
+            {
+                continue;
+            }
+
+            let (mods, key) = match binding.prefix.get(position) {
+                Some((mods, key)) => (*mods, key),
+                None => (binding.mods, &binding.trigger),
+            };
+            if !inputs
+                .iter()
+                .any(|input| input.0 == mods && input.1 == *key)
+            {
+                continue;
+            }
+            if !conditions.matches(&binding.when) {
+                continue;
+            }
+
+            if position == binding.prefix.len() {
+                return SequenceMatch::Complete(index);
+            }
+            partial.get_or_insert((mods, key.clone()));
+        }
+
+        match partial {
+            Some(chord) => SequenceMatch::Partial(chord),
+            None => SequenceMatch::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bindings::{config_key_bindings, Action};
+    use crate::crosswords::Mode;
+    use rio_backend::config::bindings::KeyBinding as ConfigKeyBinding;
+    use rio_window::keyboard::KeyLocation;
+
+    fn config_binding(keys: &str, table: &str, action: &str) -> ConfigKeyBinding {
+        ConfigKeyBinding {
+            key: String::new(),
+            keys: keys.to_string(),
+            with: String::new(),
+            action: action.to_string(),
+            text: String::new(),
+            bytes: vec![],
+            mode: String::new(),
+            table: table.to_string(),
+            when: String::new(),
+        }
+    }
+
+    fn chord(mods: ModifiersState, key: &str) -> Chord {
+        (
+            mods,
+            BindingKey::Keycode {
+                key: Key::Character(key.into()),
+                location: KeyLocation::Standard,
+            },
+        )
+    }
+
+    fn conditions() -> ConditionState {
+        ConditionState::new(Mode::empty(), 0, Box::new(String::new))
+    }
+
+    #[test]
+    fn key_sequences() {
+        let bindings = config_key_bindings(
+            vec![
+                config_binding("control+a |", "", "splitright"),
+                config_binding("ctrl+a shift+-", "", "splitdown"),
+                config_binding("control+a r", "", "activatekeytable:resize"),
+            ],
+            vec![],
+        );
+        assert_eq!(bindings.len(), 3);
+        assert_eq!(bindings[0].label(), "Control+A |");
+        assert_eq!(bindings[1].label(), "Control+A Shift+-");
+        let mode = BindingMode::empty();
+        let conditions = conditions();
+        let leader = chord(ModifiersState::CONTROL, "a");
+
+        let mut key_tables = KeyTables::new(1000);
+        assert_eq!(
+            key_tables.matches(
+                &bindings,
+                &[chord(ModifiersState::empty(), "|")],
+                &mode,
+                &conditions
+            ),
+            SequenceMatch::None
+        );
+        assert_eq!(
+            key_tables.matches(
+                &bindings,
+                std::slice::from_ref(&leader),
+                &mode,
+                &conditions
+            ),
+            SequenceMatch::Partial(leader.clone())
+        );
+
+        key_tables.push_chord(leader);
+        assert!(key_tables.is_pending());
+        assert_eq!(key_tables.indicator().as_deref(), Some("Control+A"));
+        // Shift pressed to type `|` and Control pressed again
+        for (mods, key) in [
+            (ModifiersState::SHIFT, NamedKey::Shift),
+            (ModifiersState::CONTROL, NamedKey::Control),
+        ] {
+            let modifier = BindingKey::Keycode {
+                key: Key::Named(key),
+                location: KeyLocation::Left,
+            };
+            assert_eq!(
+                key_tables.matches(&bindings, &[(mods, modifier)], &mode, &conditions),
+                SequenceMatch::Modifier
+            );
+        }
+        assert_eq!(
+            key_tables.matches(
+                &bindings,
+                &[chord(ModifiersState::empty(), "|")],
+                &mode,
+                &conditions
+            ),
+            SequenceMatch::Complete(0)
+        );
+        assert_eq!(
+            key_tables.matches(
+                &bindings,
+                &[chord(ModifiersState::SHIFT, "-")],
+                &mode,
+                &conditions
+            ),
+            SequenceMatch::Complete(1)
+        );
+        assert_eq!(
+            key_tables.matches(
+                &bindings,
+                &[chord(ModifiersState::empty(), "x")],
+                &mode,
+                &conditions
+            ),
+            SequenceMatch::None
+        );
+        assert_eq!(
+            bindings[2].action,
+            Action::ActivateKeyTable(String::from("resize"))
+        );
+
+        let in_editor =
+            ConditionState::new(Mode::empty(), 0, Box::new(|| String::from("nvim")));
+        let mut binding = config_binding("control+a |", "", "splitright");
+        binding.when = String::from("~process=nvim");
+        let bindings = config_key_bindings(vec![binding], vec![]);
+        assert_eq!(
+            key_tables.matches(
+                &bindings,
+                &[chord(ModifiersState::empty(), "|")],
+                &mode,
+                &in_editor
+            ),
+            SequenceMatch::None
+        );
+        assert_eq!(
+            key_tables.matches(
+                &bindings,
+                &[chord(ModifiersState::empty(), "|")],
+                &mode,
+                &conditions
+            ),
+            SequenceMatch::Complete(0)
+        );
+    }
+
+    #[test]
+    fn key_tables_activation_and_timeout() {
+        let bindings = config_key_bindings(
+            vec![config_binding("control+w h", "resize", "resizesplitleft")],
+            vec![],
+        );
+        assert_eq!(bindings[0].table.as_deref(), Some("resize"));
+        let mode = BindingMode::empty();
+        let conditions = conditions();
+        let leader = chord(ModifiersState::CONTROL, "w");
+
+        let mut key_tables = KeyTables::new(1000);
+        assert_eq!(key_tables.deadline(), None);
+        assert_eq!(
+            key_tables.matches(
+                &bindings,
+                std::slice::from_ref(&leader),
+                &mode,
+                &conditions
+            ),
+            SequenceMatch::None
+        );
+
+        key_tables.activate(String::from("resize"));
+        key_tables.activate(String::from("copy"));
+        key_tables.activate(String::from("resize"));
+        assert_eq!(key_tables.active(), Some("resize"));
+        assert_eq!(key_tables.indicator().as_deref(), Some("resize"));
+        assert_eq!(
+            key_tables.matches(&bindings, &[leader], &mode, &conditions),
+            SequenceMatch::Partial(chord(ModifiersState::CONTROL, "w"))
+        );
+
+        let deadline = key_tables.deadline().unwrap();
+        key_tables.expire(deadline - Duration::from_millis(1));
+        assert_eq!(key_tables.active(), Some("resize"));
+        key_tables.expire(deadline);
+        assert_eq!(key_tables.active(), None);
+        assert_eq!(key_tables.deadline(), None);
+
+        key_tables.activate(String::from("copy"));
+        key_tables.activate(String::from("resize"));
+        assert_eq!(key_tables.pop().as_deref(), Some("resize"));
+        assert_eq!(key_tables.active(), Some("copy"));
+
+        let mut without_timeout = KeyTables::new(0);
+        without_timeout.activate(String::from("resize"));
+        assert_eq!(without_timeout.deadline(), None);
+    }
+}
@@ -2,13 +2,21 @@
 // was originally taken from https://github.com/alacritty/alacritty/blob/e35e5ad14fce8456afdd89f2b392b9924bb27471/alacritty/src/config/bindings.rs
 // which is licensed under Apache 2.0 license.
 
-pub mod kitty_keyboard;
-
+pub mod conditions;
+pub mod key_input;
+pub mod key_tables;
+
+use crate::bindings::conditions::Condition;
+use crate::context::grid::{SplitDirection, SplitSize};
+use crate::context::BroadcastScope;
+use crate::crosswords::export::ExportFormat;
 use crate::crosswords::vi_mode::ViMotion;
 use crate::crosswords::Mode;
 use bitflags::bitflags;
 use rio_backend::config::bindings::KeyBinding as ConfigKeyBinding;
+use rio_backend::config::bindings::MouseBinding as ConfigMouseBinding;
 use rio_backend::config::keyboard::Keyboard as ConfigKeyboard;
+use rio_backend::keyboard::DEFAULT_SEQUENCES;
 use rio_window::event::MouseButton;
 use rio_window::keyboard::Key::*;
 use rio_window::keyboard::NamedKey::*;
@@ -82,6 +90,17 @@ pub struct Binding<T> {
     ///
     /// For example, this might be a key like "G", or a mouse button.
     pub trigger: T,
+
+    /// Chords pressed before the trigger, like `Control+A` in `control+a |`.
+    pub prefix: Vec<(ModifiersState, T)>,
+
+    /// Key table the binding belongs to, it's only active while the table
+    /// is the last one activated.
+    pub table: Option<String>,
+
+    /// Conditions on the terminal state that must all hold, like the
+    /// foreground process not being `nvim`.
+    pub when: Vec<Condition>,
 }
 
 impl<T: Eq> Binding<T> {
@@ -101,6 +120,15 @@ impl<T: Eq> Binding<T> {
             && !mode.intersects(self.notmode.clone())
     }
 
+    /// Whether the binding can be triggered in `mode` while `table` is the
+    /// active key table.
+    #[inline]
+    pub fn is_active(&self, mode: &BindingMode, table: Option<&str>) -> bool {
+        (self.table.is_none() || self.table.as_deref() == table)
+            && mode.contains(self.mode.clone())
+            && !mode.intersects(self.notmode.clone())
+    }
+
     #[inline]
     pub fn triggers_match(&self, binding: &Binding<T>) -> bool {
         // Check the binding's key and modifiers.
@@ -108,6 +136,10 @@ impl<T: Eq> Binding<T> {
             return false;
         }
 
+        if self.prefix != binding.prefix || self.table != binding.table {
+            return false;
+        }
+
         let selfmode = if self.mode.is_empty() {
             BindingMode::all()
         } else {
@@ -148,8 +180,78 @@ pub enum BindingKey {
 pub type KeyBinding = Binding<BindingKey>;
 pub type KeyBindings = Vec<KeyBinding>;
 
+impl KeyBinding {
+    /// Human readable trigger of the binding, like `Super+Shift+P` or
+    /// `Control+A |` for sequences.
+    pub fn label(&self) -> String {
+        let mut chords: Vec<String> = self
+            .prefix
+            .iter()
+            .map(|(mods, key)| chord_label(*mods, key))
+            .collect();
+        chords.push(chord_label(self.mods, &self.trigger));
+        chords.join(" ")
+    }
+}
+
+/// Human readable chord, like `Super+Shift+P`.
+pub fn chord_label(mods: ModifiersState, key: &BindingKey) -> String {
+    let mut label = String::new();
+    for (modifier, name) in [
+        (ModifiersState::CONTROL, "Control"),
+        (ModifiersState::SUPER, "Super"),
+        (ModifiersState::ALT, "Alt"),
+        (ModifiersState::SHIFT, "Shift"),
+    ] {
+        if mods.contains(modifier) {
+            label.push_str(name);
+            label.push('+');
+        }
+    }
+
+    match key {
+        BindingKey::Keycode {
+            key: Character(character),
+            ..
+        } => label.push_str(&character.to_uppercase()),
+        BindingKey::Keycode {
+            key: Named(named), ..
+        } => label.push_str(&format!("{named:?}")),
+        BindingKey::Keycode { key, .. } => label.push_str(&format!("{key:?}")),
+        BindingKey::Scancode(code) => label.push_str(&format!("{code:?}")),
+    }
+    label
+}
+
+/// Mouse button pressed, with the number of consecutive clicks.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MouseTrigger {
+    pub button: MouseButton,
+    /// Any number of clicks when unset.
+    pub clicks: Option<u8>,
+}
+
 /// Bindings that are triggered by a mouse button.
-pub type MouseBinding = Binding<MouseButton>;
+pub type MouseBinding = Binding<MouseTrigger>;
+
+impl MouseBinding {
+    #[inline]
+    pub fn is_triggered_by_click(
+        &self,
+        mode: &BindingMode,
+        mods: ModifiersState,
+        button: MouseButton,
+        clicks: u8,
+    ) -> bool {
+        self.trigger.button == button
+            && self
+                .trigger
+                .clicks
+                .map_or(true, |expected| expected == clicks)
+            && self.mods == mods
+            && self.is_active(mode, None)
+    }
+}
 
 bitflags! {
     /// Modes available for key bindings.
@@ -162,11 +264,12 @@ bitflags! {
         const SEARCH              = 0b0001_0000;
         const DISAMBIGUATE_KEYS   = 0b0010_0000;
         const ALL_KEYS_AS_ESC     = 0b0100_0000;
+        const PLAYBACK            = 0b1000_0000;
     }
 }
 
 impl BindingMode {
-    pub fn new(mode: &Mode, search: bool) -> BindingMode {
+    pub fn new(mode: &Mode, search: bool, playback: bool) -> BindingMode {
         let mut binding_mode = BindingMode::empty();
         binding_mode.set(BindingMode::APP_CURSOR, mode.contains(Mode::APP_CURSOR));
         binding_mode.set(BindingMode::APP_KEYPAD, mode.contains(Mode::APP_KEYPAD));
@@ -181,6 +284,7 @@ impl BindingMode {
             mode.contains(Mode::REPORT_ALL_KEYS_AS_ESC),
         );
         binding_mode.set(BindingMode::VI, mode.contains(Mode::VI));
+        binding_mode.set(BindingMode::PLAYBACK, playback);
         binding_mode
     }
 }
@@ -208,60 +312,190 @@ impl Program {
     }
 }
 
+/// Name of an action without arguments and its constructor.
+pub type NamedAction = (&'static str, fn() -> Action);
+
+/// Actions without arguments by name, in the order the command palette
+/// lists them. `From<String>` matches the names case insensitively.
+pub const NAMED_ACTIONS: &[NamedAction] = &[
+    ("Paste", || Action::Paste),
+    ("Copy", || Action::Copy),
+    ("OpenConfigEditor", || Action::ConfigEditor),
+    ("ResetFontSize", || Action::ResetFontSize),
+    ("IncreaseFontSize", || Action::IncreaseFontSize),
+    ("DecreaseFontSize", || Action::DecreaseFontSize),
+    ("CreateWindow", || Action::WindowCreateNew),
+    ("CreateTab", || Action::TabCreateNew),
+    ("CloseTab", || Action::TabCloseCurrent),
+    ("CloseCurrentTabOrSplit", || Action::CloseCurrentSplitOrTab),
+    ("CloseUnfocusedTabs", || Action::TabCloseUnfocused),
+    ("SelectPrevTab", || Action::SelectPrevTab),
+    ("SelectNextTab", || Action::SelectNextTab),
+    ("SelectLastTab", || Action::SelectLastTab),
+    ("MoveCurrentTabToPrev", || Action::MoveCurrentTabToPrev),
+    ("MoveCurrentTabToNext", || Action::MoveCurrentTabToNext),
+    ("SplitRight", || Action::SplitRight),
+    ("SplitDown", || Action::SplitDown),
+    ("SelectNextSplit", || Action::SelectNextSplit),
+    ("SelectPrevSplit", || Action::SelectPrevSplit),
+    ("SelectSplitLeft", || {
+        Action::SelectSplit(SplitDirection::Left)
+    }),
+    ("SelectSplitRight", || {
+        Action::SelectSplit(SplitDirection::Right)
+    }),
+    ("SelectSplitUp", || Action::SelectSplit(SplitDirection::Up)),
+    ("SelectSplitDown", || {
+        Action::SelectSplit(SplitDirection::Down)
+    }),
+    ("ResizeSplitLeft", || {
+        Action::ResizeSplit(SplitDirection::Left, SplitSize::Cells(1))
+    }),
+    ("ResizeSplitRight", || {
+        Action::ResizeSplit(SplitDirection::Right, SplitSize::Cells(1))
+    }),
+    ("ResizeSplitUp", || {
+        Action::ResizeSplit(SplitDirection::Up, SplitSize::Cells(1))
+    }),
+    ("ResizeSplitDown", || {
+        Action::ResizeSplit(SplitDirection::Down, SplitSize::Cells(1))
+    }),
+    ("EqualizeSplits", || Action::EqualizeSplits),
+    ("ToggleSplitZoom", || Action::ToggleSplitZoom),
+    ("SwapSplitNext", || Action::SwapSplitNext),
+    ("SwapSplitPrev", || Action::SwapSplitPrev),
+    ("MoveSplitToNewTab", || Action::MoveSplitToNewTab),
+    ("ToggleBroadcast", || {
+        Action::ToggleBroadcast(BroadcastScope::Tab)
+    }),
+    ("ToggleBroadcast(all)", || {
+        Action::ToggleBroadcast(BroadcastScope::AllTabs)
+    }),
+    ("ToggleBroadcast(marked)", || {
+        Action::ToggleBroadcast(BroadcastScope::Marked)
+    }),
+    ("MarkSplitForBroadcast", || Action::MarkSplitForBroadcast),
+    ("ScrollHalfPageUp", || Action::ScrollHalfPageUp),
+    ("ScrollHalfPageDown", || Action::ScrollHalfPageDown),
+    ("ScrollToTop", || Action::ScrollToTop),
+    ("ScrollToBottom", || Action::ScrollToBottom),
+    ("ClearHistory", || Action::ClearHistory),
+    ("SearchForward", || Action::SearchForward),
+    ("SearchBackward", || Action::SearchBackward),
+    ("ToggleVIMode", || Action::ToggleViMode),
+    ("ToggleFullscreen", || Action::ToggleFullscreen),
+    ("ToggleRecording", || Action::ToggleRecording),
+    ("SaveScreen", || Action::SaveScreen(None)),
+    ("SaveScrollback", || Action::SaveScrollback(None)),
+    ("OpenScrollbackInEditor", || Action::OpenScrollbackInEditor),
+    ("OpenHyperlink", || Action::OpenHyperlink),
+    ("ExpandSelection", || {
+        Action::Mouse(MouseAction::ExpandSelection)
+    }),
+    ("PopKeyTable", || Action::PopKeyTable),
+    ("ToggleHighlight", || Action::ToggleHighlight),
+    ("ToggleTimestamps", || Action::ToggleTimestamps),
+    ("ToggleMonitorActivity", || Action::ToggleMonitorActivity),
+    ("ToggleMonitorSilence", || Action::ToggleMonitorSilence),
+    ("PlaybackTogglePause", || Action::PlaybackTogglePause),
+    ("PlaybackStep", || Action::PlaybackStep),
+    ("PlaybackFaster", || Action::PlaybackFaster),
+    ("PlaybackSlower", || Action::PlaybackSlower),
+    ("Quit", || Action::Quit),
+];
+
+/// Actions without arguments that the command palette doesn't list, as
+/// they are only useful from a binding.
+const UNLISTED_ACTIONS: &[NamedAction] = &[
+    ("OpenCommandPalette", || Action::OpenCommandPalette),
+    ("SearchConfirm", || {
+        Action::Search(SearchAction::SearchConfirm)
+    }),
+    ("SearchCancel", || {
+        Action::Search(SearchAction::SearchCancel)
+    }),
+    ("SearchClear", || Action::Search(SearchAction::SearchClear)),
+    ("SearchFocusNext", || {
+        Action::Search(SearchAction::SearchFocusNext)
+    }),
+    ("SearchFocusPrevious", || {
+        Action::Search(SearchAction::SearchFocusPrevious)
+    }),
+    ("SearchDeleteWord", || {
+        Action::Search(SearchAction::SearchDeleteWord)
+    }),
+    ("SearchHistoryNext", || {
+        Action::Search(SearchAction::SearchHistoryNext)
+    }),
+    ("SearchHistoryPrevious", || {
+        Action::Search(SearchAction::SearchHistoryPrevious)
+    }),
+    ("ReceiveChar", || Action::ReceiveChar),
+    ("None", || Action::None),
+];
+
 impl From<String> for Action {
     fn from(action: String) -> Action {
+        // Layout and key table names are case sensitive so they're parsed
+        // before lowercase
+        if let Some((action_name, name)) = action.split_once(':') {
+            if action_name.eq_ignore_ascii_case("createtab") && !name.is_empty() {
+                return Action::TabCreateNewWithLayout(name.to_string());
+            }
+            if action_name.eq_ignore_ascii_case("activatekeytable") && !name.is_empty() {
+                return Action::ActivateKeyTable(name.to_string());
+            }
+        }
+
+        if let Some((_, named)) = NAMED_ACTIONS
+            .iter()
+            .chain(UNLISTED_ACTIONS)
+            .find(|(name, _)| name.eq_ignore_ascii_case(&action))
+        {
+            return named();
+        }
+
         let action = action.to_lowercase();
 
-        let action_from_string = match action.as_str() {
-            "paste" => Some(Action::Paste),
-            "quit" => Some(Action::Quit),
-            "copy" => Some(Action::Copy),
-            "searchforward" => Some(Action::SearchForward),
-            "searchbackward" => Some(Action::SearchBackward),
-            "searchconfirm" => Some(Action::Search(SearchAction::SearchConfirm)),
-            "searchcancel" => Some(Action::Search(SearchAction::SearchCancel)),
-            "searchclear" => Some(Action::Search(SearchAction::SearchClear)),
-            "searchfocusnext" => Some(Action::Search(SearchAction::SearchFocusNext)),
-            "searchfocusprevious" => {
-                Some(Action::Search(SearchAction::SearchFocusPrevious))
+        let re = regex::Regex::new(r"movesplittotab\(([^()]+)\)").unwrap();
+        for capture in re.captures_iter(&action) {
+            if let Some(matched) = capture.get(1) {
+                let matched_string = matched.as_str().to_string();
+                let parsed_matched_string: usize = matched_string.parse().unwrap_or(0);
+                return Action::MoveSplitToTab(parsed_matched_string);
+            }
+        }
+
+        let re = regex::Regex::new(r"togglebroadcast\((tab|all|marked)\)").unwrap();
+        for capture in re.captures_iter(&action) {
+            if let Some(matched) = capture.get(1) {
+                let scope = match matched.as_str() {
+                    "all" => BroadcastScope::AllTabs,
+                    "marked" => BroadcastScope::Marked,
+                    _ => BroadcastScope::Tab,
+                };
+                return Action::ToggleBroadcast(scope);
             }
-            "searchdeleteword" => Some(Action::Search(SearchAction::SearchDeleteWord)),
-            "searchhistorynext" => Some(Action::Search(SearchAction::SearchHistoryNext)),
-            "searchhistoryprevious" => {
-                Some(Action::Search(SearchAction::SearchHistoryPrevious))
+        }
+
+        let re = regex::Regex::new(r"save(screen|scrollback)\((text|ansi|html|svg)\)")
+            .unwrap();
+        for capture in re.captures_iter(&action) {
+            if let (Some(range), Some(format)) = (capture.get(1), capture.get(2)) {
+                let format = format.as_str().parse().ok();
+                return match range.as_str() {
+                    "scrollback" => Action::SaveScrollback(format),
+                    _ => Action::SaveScreen(format),
+                };
             }
-            "clearhistory" => Some(Action::ClearHistory),
-            "resetfontsize" => Some(Action::ResetFontSize),
-            "increasefontsize" => Some(Action::IncreaseFontSize),
-            "decreasefontsize" => Some(Action::DecreaseFontSize),
-            "createwindow" => Some(Action::WindowCreateNew),
-            "createtab" => Some(Action::TabCreateNew),
-            "movecurrenttabtoprev" => Some(Action::MoveCurrentTabToPrev),
-            "movecurrenttabtonext" => Some(Action::MoveCurrentTabToNext),
-            "closetab" => Some(Action::TabCloseCurrent),
-            "closecurrenttaborsplit" => Some(Action::CloseCurrentSplitOrTab),
-            "closeunfocusedtabs" => Some(Action::TabCloseUnfocused),
-            "openconfigeditor" => Some(Action::ConfigEditor),
-            "selectprevtab" => Some(Action::SelectPrevTab),
-            "selectnexttab" => Some(Action::SelectNextTab),
-            "selectlasttab" => Some(Action::SelectLastTab),
-            "receivechar" => Some(Action::ReceiveChar),
-            "scrollhalfpageup" => Some(Action::ScrollHalfPageUp),
-            "scrollhalfpagedown" => Some(Action::ScrollHalfPageDown),
-            "scrolltotop" => Some(Action::ScrollToTop),
-            "scrolltobottom" => Some(Action::ScrollToBottom),
-            "splitright" => Some(Action::SplitRight),
-            "splitdown" => Some(Action::SplitDown),
-            "selectnextsplit" => Some(Action::SelectNextSplit),
-            "selectprevsplit" => Some(Action::SelectPrevSplit),
-            "togglevimode" => Some(Action::ToggleViMode),
-            "togglefullscreen" => Some(Action::ToggleFullscreen),
-            "none" => Some(Action::None),
-            _ => None,
-        };
+        }
 
-        if action_from_string.is_some() {
-            return action_from_string.unwrap_or(Action::None);
+        let re = regex::Regex::new(r"playbackseek\((-?[0-9]+)\)").unwrap();
+        for capture in re.captures_iter(&action) {
+            if let Some(matched) = capture.get(1) {
+                let seconds: i32 = matched.as_str().parse().unwrap_or(0);
+                return Action::PlaybackSeek(seconds);
+            }
         }
 
         let re = regex::Regex::new(r"selecttab\(([^()]+)\)").unwrap();
@@ -297,6 +531,28 @@ impl From<String> for Action {
             }
         }
 
+        let re = regex::Regex::new(r"resizesplit(left|right|up|down)\(([0-9]+)(%?)\)")
+            .unwrap();
+        for capture in re.captures_iter(&action) {
+            if let (Some(direction), Some(amount)) = (capture.get(1), capture.get(2)) {
+                let direction = match direction.as_str() {
+                    "left" => SplitDirection::Left,
+                    "right" => SplitDirection::Right,
+                    "up" => SplitDirection::Up,
+                    _ => SplitDirection::Down,
+                };
+                let amount: usize = amount.as_str().parse().unwrap_or(1);
+                let is_percentage =
+                    capture.get(3).is_some_and(|m| !m.as_str().is_empty());
+                let size = if is_percentage {
+                    SplitSize::Percentage(amount.min(100) as u8)
+                } else {
+                    SplitSize::Cells(amount)
+                };
+                return Action::ResizeSplit(direction, size);
+            }
+        }
+
         let re = regex::Regex::new(r"scroll\(([^()]+)\)").unwrap();
         for capture in re.captures_iter(&action) {
             if let Some(matched) = capture.get(1) {
@@ -332,6 +588,9 @@ pub enum Action {
     /// Perform mouse binding exclusive action.
     Mouse(MouseAction),
 
+    /// Open the hyperlink (or the URL) under the mouse cursor.
+    OpenHyperlink,
+
     /// Paste contents of system clipboard.
     Paste,
 
@@ -409,6 +668,9 @@ pub enum Action {
     /// Create a new Rio tab.
     TabCreateNew,
 
+    /// Create a new tab from a layout declared in the configuration.
+    TabCreateNewWithLayout(String),
+
     /// Move current tab to previous slot.
     MoveCurrentTabToPrev,
 
@@ -468,6 +730,81 @@ pub enum Action {
     SelectNextSplit,
     SelectPrevSplit,
 
+    /// Select the split placed in the direction of the current one.
+    SelectSplit(SplitDirection),
+
+    /// Move the border of the current split towards the direction.
+    ResizeSplit(SplitDirection, SplitSize),
+
+    /// Give the same size to every split of the current tab.
+    EqualizeSplits,
+
+    /// Make the current split take the whole tab (or restore it).
+    ToggleSplitZoom,
+
+    /// Swap the current split with the next/previous one.
+    SwapSplitNext,
+    SwapSplitPrev,
+
+    /// Move the current split to a new tab.
+    MoveSplitToNewTab,
+
+    /// Move the current split to the tab in the given index.
+    MoveSplitToTab(usize),
+
+    /// Duplicate keyboard input and pastes to the splits in the scope.
+    ToggleBroadcast(BroadcastScope),
+
+    /// Mark (or unmark) the current split to receive broadcast input.
+    MarkSplitForBroadcast,
+
+    /// Start (or stop) recording the current split in asciicast v2 format.
+    ToggleRecording,
+
+    /// Enable (or disable) the `[highlight]` rules.
+    ToggleHighlight,
+
+    /// Show (or hide) the time each line was written in a gutter.
+    ToggleTimestamps,
+
+    /// Watch (or stop watching) the current tab for new output while it is
+    /// in background.
+    ToggleMonitorActivity,
+
+    /// Watch (or stop watching) the current tab for silence while it is in
+    /// background.
+    ToggleMonitorSilence,
+
+    /// Open the command palette to search and run any action.
+    OpenCommandPalette,
+
+    /// Save the visible screen (or the whole scrollback) of the current
+    /// split in the export directory.
+    SaveScreen(Option<ExportFormat>),
+    SaveScrollback(Option<ExportFormat>),
+
+    /// Open the scrollback of the current split with the scrollback editor.
+    OpenScrollbackInEditor,
+
+    /// Pause or resume the playback of a recording (`rio --play`).
+    PlaybackTogglePause,
+
+    /// Move the playback forward (or backward) by seconds.
+    PlaybackSeek(i32),
+
+    /// Pause the playback and play the next frame.
+    PlaybackStep,
+
+    /// Double (or halve) the playback speed.
+    PlaybackFaster,
+    PlaybackSlower,
+
+    /// Activate the key table with the given name over the active ones.
+    ActivateKeyTable(String),
+
+    /// Leave the last activated key table.
+    PopKeyTable,
+
     /// Allow receiving char input.
     ReceiveChar,
 
@@ -537,6 +874,9 @@ macro_rules! bindings {
                 mode: _mode,
                 notmode: _notmode,
                 action: $action.into(),
+                prefix: Vec::new(),
+                table: None,
+                when: Vec::new(),
             });
         )*
 
@@ -563,18 +903,28 @@ macro_rules! trigger {
             location: KeyLocation::Standard,
         }
     }};
+    (MouseBinding, $button:expr,) => {{
+        MouseTrigger {
+            button: $button,
+            clicks: None,
+        }
+    }};
     ($ty:ident, $key:expr,) => {{
         $key
     }};
 }
 
-pub fn default_mouse_bindings() -> Vec<MouseBinding> {
-    bindings!(
+pub fn default_mouse_bindings(
+    config_mouse_bindings: Vec<ConfigMouseBinding>,
+) -> Vec<MouseBinding> {
+    let bindings = bindings!(
         MouseBinding;
         MouseButton::Right;                            MouseAction::ExpandSelection;
         MouseButton::Right,   ModifiersState::CONTROL; MouseAction::ExpandSelection;
         MouseButton::Middle, ~BindingMode::VI;         Action::PasteSelection;
-    )
+    );
+
+    mouse_bindings(config_mouse_bindings, bindings)
 }
 
 pub fn default_key_bindings(
@@ -590,23 +940,18 @@ pub fn default_key_bindings(
         Key::Named(Paste), ~BindingMode::VI; Action::Paste;
         Key::Character("l".into()), ModifiersState::CONTROL; Action::ClearLogNotice;
         "l",  ModifiersState::CONTROL, ~BindingMode::VI; Action::Esc("\x0c".into());
-        Key::Named(Tab),  ModifiersState::SHIFT, ~BindingMode::VI; Action::Esc("\x1b[Z".into());
         Key::Named(Home),     ModifiersState::SHIFT, ~BindingMode::ALT_SCREEN; Action::ScrollToTop;
         Key::Named(End),      ModifiersState::SHIFT, ~BindingMode::ALT_SCREEN; Action::ScrollToBottom;
         Key::Named(PageUp),   ModifiersState::SHIFT, ~BindingMode::ALT_SCREEN; Action::ScrollPageUp;
         Key::Named(PageDown), ModifiersState::SHIFT, ~BindingMode::ALT_SCREEN; Action::ScrollPageDown;
-        Key::Named(Home),  +BindingMode::APP_CURSOR, ~BindingMode::VI;
-            Action::Esc("\x1bOH".into());
-        Key::Named(End),   +BindingMode::APP_CURSOR, ~BindingMode::VI;
-            Action::Esc("\x1bOF".into());
-        Key::Named(ArrowUp),    +BindingMode::APP_CURSOR, ~BindingMode::VI;
-            Action::Esc("\x1bOA".into());
-        Key::Named(ArrowDown),  +BindingMode::APP_CURSOR, ~BindingMode::VI;
-            Action::Esc("\x1bOB".into());
-        Key::Named(ArrowRight), +BindingMode::APP_CURSOR, ~BindingMode::VI;
-            Action::Esc("\x1bOC".into());
-        Key::Named(ArrowLeft),  +BindingMode::APP_CURSOR, ~BindingMode::VI;
-            Action::Esc("\x1bOD".into());
+
+        // Playback
+        Key::Named(Space), +BindingMode::PLAYBACK, ~BindingMode::VI; Action::PlaybackTogglePause;
+        ".", +BindingMode::PLAYBACK, ~BindingMode::VI; Action::PlaybackStep;
+        Key::Named(ArrowLeft), +BindingMode::PLAYBACK, ~BindingMode::VI; Action::PlaybackSeek(-5);
+        Key::Named(ArrowRight), +BindingMode::PLAYBACK, ~BindingMode::VI; Action::PlaybackSeek(5);
+        "=", +BindingMode::PLAYBACK, ~BindingMode::VI; Action::PlaybackFaster;
+        "-", +BindingMode::PLAYBACK, ~BindingMode::VI; Action::PlaybackSlower;
 
         // VI Mode
         Key::Named(Space), ModifiersState::ALT | ModifiersState::SHIFT; Action::ToggleViMode;
@@ -676,26 +1021,22 @@ pub fn default_key_bindings(
             ViMotion::Bracket;
     );
 
-    bindings.extend(bindings!(
-        KeyBinding;
-        Key::Named(ArrowUp), ~BindingMode::APP_CURSOR, ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC; Action::Esc("\x1b[A".into());
-        Key::Named(ArrowDown), ~BindingMode::APP_CURSOR, ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC; Action::Esc("\x1b[B".into());
-        Key::Named(ArrowRight), ~BindingMode::APP_CURSOR, ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC; Action::Esc("\x1b[C".into());
-        Key::Named(ArrowLeft),  ~BindingMode::APP_CURSOR, ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC; Action::Esc("\x1b[D".into());
-        Key::Named(Insert),     ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC, ~BindingMode::DISAMBIGUATE_KEYS; Action::Esc("\x1b[2~".into());
-        Key::Named(Delete),     ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC, ~BindingMode::DISAMBIGUATE_KEYS; Action::Esc("\x1b[3~".into());
-        Key::Named(PageUp),     ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC, ~BindingMode::DISAMBIGUATE_KEYS; Action::Esc("\x1b[5~".into());
-        Key::Named(PageDown),   ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC, ~BindingMode::DISAMBIGUATE_KEYS; Action::Esc("\x1b[6~".into());
-        Key::Named(Backspace),  ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC; Action::Esc("\x7f".into());
-        Key::Named(Backspace), ModifiersState::ALT,     ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC; Action::Esc("\x1b\x7f".into());
-        Key::Named(Backspace), ModifiersState::SHIFT,   ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC; Action::Esc("\x7f".into());
-        Key::Named(F1), ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC, ~BindingMode::DISAMBIGUATE_KEYS; Action::Esc("\x1bOP".into());
-        Key::Named(F2), ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC, ~BindingMode::DISAMBIGUATE_KEYS; Action::Esc("\x1bOQ".into());
-        Key::Named(F3), ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC, ~BindingMode::DISAMBIGUATE_KEYS; Action::Esc("\x1bOR".into());
-        Key::Named(F4), ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC, ~BindingMode::DISAMBIGUATE_KEYS; Action::Esc("\x1bOS".into());
-        Key::Named(Tab), ModifiersState::SHIFT, ~BindingMode::VI, ~BindingMode::SEARCH,   ~BindingMode::ALL_KEYS_AS_ESC; Action::Esc("\x1b[Z".into());
-        Key::Named(Tab), ModifiersState::SHIFT | ModifiersState::ALT, ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::ALL_KEYS_AS_ESC; Action::Esc("\x1b\x1b[Z".into());
-    ));
+    // Sequences shared with the headless terminal.
+    bindings.extend(DEFAULT_SEQUENCES.iter().map(|sequence| KeyBinding {
+        trigger: BindingKey::Keycode {
+            key: Named(key_input::window_named_key(sequence.key)),
+            location: KeyLocation::Standard,
+        },
+        mods: key_input::modifiers_state(sequence.mods),
+        mode: BindingMode::new(&sequence.mode, false, false),
+        notmode: BindingMode::new(&sequence.notmode, false, false)
+            | BindingMode::VI
+            | BindingMode::SEARCH,
+        action: Action::Esc(sequence.sequence.into()),
+        prefix: Vec::new(),
+        table: None,
+        when: Vec::new(),
+    }));
 
     bindings.extend(platform_key_bindings(
         use_navigation_key_bindings,
@@ -712,15 +1053,44 @@ pub struct ModeWrapper {
     pub not_mode: BindingMode,
 }
 
-#[inline]
-fn convert(config_key_binding: ConfigKeyBinding) -> Result<KeyBinding, String> {
-    let (key, location) = if config_key_binding.key.chars().count() == 1 {
+/// Parses modes like `vi | ~alt`, `~` requires the mode to be inactive.
+fn modes_from_names(modes: &str) -> ModeWrapper {
+    let mut res_mode = ModeWrapper {
+        mode: BindingMode::empty(),
+        not_mode: BindingMode::empty(),
+    };
+
+    for modifier in modes.split('|') {
+        match modifier.trim().to_lowercase().as_str() {
+            "appcursor" => res_mode.mode |= BindingMode::APP_CURSOR,
+            "~appcursor" => res_mode.not_mode |= BindingMode::APP_CURSOR,
+            "appkeypad" => res_mode.mode |= BindingMode::APP_KEYPAD,
+            "~appkeypad" => res_mode.not_mode |= BindingMode::APP_KEYPAD,
+            "alt" => res_mode.mode |= BindingMode::ALT_SCREEN,
+            "~alt" => res_mode.not_mode |= BindingMode::ALT_SCREEN,
+            "vi" => res_mode.mode |= BindingMode::VI,
+            "~vi" => res_mode.not_mode |= BindingMode::VI,
+            "playback" => res_mode.mode |= BindingMode::PLAYBACK,
+            "~playback" => res_mode.not_mode |= BindingMode::PLAYBACK,
+            _ => {
+                res_mode.not_mode |= BindingMode::empty();
+                res_mode.mode |= BindingMode::empty();
+            }
+        }
+    }
+
+    res_mode
+}
+
+/// Key with the name used in the config, like `a`, `pageup` or `numpad1`.
+fn key_from_name(name: &str) -> Result<BindingKey, String> {
+    let (key, location) = if name.chars().count() == 1 {
         (
-            Key::Character(config_key_binding.key.to_lowercase().into()),
+            Key::Character(name.to_lowercase().into()),
             KeyLocation::Standard,
         )
     } else {
-        match config_key_binding.key.to_lowercase().as_str() {
+        match name.to_lowercase().as_str() {
             "home" => (Key::Named(Home), KeyLocation::Standard),
             "space" => (Key::Named(Space), KeyLocation::Standard),
             "delete" => (Key::Named(Delete), KeyLocation::Standard),
@@ -787,19 +1157,58 @@ fn convert(config_key_binding: ConfigKeyBinding) -> Result<KeyBinding, String> {
         }
     };
 
-    let trigger = BindingKey::Keycode { key, location };
+    Ok(BindingKey::Keycode { key, location })
+}
 
-    let mut res = ModifiersState::empty();
-    for modifier in config_key_binding.with.split('|') {
-        match modifier.trim().to_lowercase().as_str() {
-            "command" | "super" => res.insert(ModifiersState::SUPER),
-            "shift" => res.insert(ModifiersState::SHIFT),
-            "alt" | "option" => res.insert(ModifiersState::ALT),
-            "control" => res.insert(ModifiersState::CONTROL),
-            "none" => (),
-            _ => (),
-        }
+#[inline]
+fn modifier_from_name(name: &str) -> Option<ModifiersState> {
+    match name.trim().to_lowercase().as_str() {
+        "command" | "cmd" | "super" => Some(ModifiersState::SUPER),
+        "shift" => Some(ModifiersState::SHIFT),
+        "alt" | "option" => Some(ModifiersState::ALT),
+        "control" | "ctrl" => Some(ModifiersState::CONTROL),
+        "none" => Some(ModifiersState::empty()),
+        _ => None,
+    }
+}
+
+/// Parses a chord like `control+shift+t` (or `control++` for the `+` key).
+fn chord_from_name(chord: &str) -> Result<(ModifiersState, BindingKey), String> {
+    let (modifiers, key) = match chord.strip_suffix("++") {
+        Some(modifiers) => (modifiers, "+"),
+        None => match chord.rsplit_once('+') {
+            Some((modifiers, key)) if !key.is_empty() => (modifiers, key),
+            _ => ("", chord),
+        },
+    };
+
+    let mut mods = ModifiersState::empty();
+    for modifier in modifiers.split('+').filter(|name| !name.is_empty()) {
+        mods |= modifier_from_name(modifier)
+            .ok_or_else(|| format!("Unable to find modifier '{modifier}'"))?;
     }
+    Ok((mods, key_from_name(key)?))
+}
+
+#[inline]
+fn convert(config_key_binding: ConfigKeyBinding) -> Result<KeyBinding, String> {
+    let (res, trigger, prefix) = if config_key_binding.keys.trim().is_empty() {
+        let mut res = ModifiersState::empty();
+        for modifier in config_key_binding.with.split('|') {
+            res |= modifier_from_name(modifier).unwrap_or_default();
+        }
+        (res, key_from_name(&config_key_binding.key)?, Vec::new())
+    } else {
+        let mut chords = config_key_binding
+            .keys
+            .split_whitespace()
+            .map(chord_from_name)
+            .collect::<Result<Vec<_>, String>>()?;
+        let (res, trigger) = chords
+            .pop()
+            .ok_or_else(|| "Unable to find defined 'keycode'".to_string())?;
+        (res, trigger, chords)
+    };
 
     let mut action: Action = config_key_binding.action.into();
     if !config_key_binding.text.is_empty() {
@@ -812,27 +1221,10 @@ fn convert(config_key_binding: ConfigKeyBinding) -> Result<KeyBinding, String> {
         }
     }
 
-    let mut res_mode = ModeWrapper {
-        mode: BindingMode::empty(),
-        not_mode: BindingMode::empty(),
-    };
+    let res_mode = modes_from_names(&config_key_binding.mode);
 
-    for modifier in config_key_binding.mode.split('|') {
-        match modifier.trim().to_lowercase().as_str() {
-            "appcursor" => res_mode.mode |= BindingMode::APP_CURSOR,
-            "~appcursor" => res_mode.not_mode |= BindingMode::APP_CURSOR,
-            "appkeypad" => res_mode.mode |= BindingMode::APP_KEYPAD,
-            "~appkeypad" => res_mode.not_mode |= BindingMode::APP_KEYPAD,
-            "alt" => res_mode.mode |= BindingMode::ALT_SCREEN,
-            "~alt" => res_mode.not_mode |= BindingMode::ALT_SCREEN,
-            "vi" => res_mode.mode |= BindingMode::VI,
-            "~vi" => res_mode.not_mode |= BindingMode::VI,
-            _ => {
-                res_mode.not_mode |= BindingMode::empty();
-                res_mode.mode |= BindingMode::empty();
-            }
-        }
-    }
+    let table = Some(config_key_binding.table.trim().to_string())
+        .filter(|table| !table.is_empty());
 
     Ok(KeyBinding {
         trigger,
@@ -840,9 +1232,109 @@ fn convert(config_key_binding: ConfigKeyBinding) -> Result<KeyBinding, String> {
         action,
         mode: res_mode.mode,
         notmode: res_mode.not_mode,
+        prefix,
+        table,
+        when: conditions::parse(&config_key_binding.when)?,
+    })
+}
+
+fn convert_mouse(
+    config_mouse_binding: ConfigMouseBinding,
+) -> Result<MouseBinding, String> {
+    let button = match config_mouse_binding.button.trim().to_lowercase().as_str() {
+        "left" => MouseButton::Left,
+        "middle" => MouseButton::Middle,
+        "right" => MouseButton::Right,
+        "back" => MouseButton::Back,
+        "forward" => MouseButton::Forward,
+        button => return Err(format!("Unable to find mouse button '{button}'")),
+    };
+
+    if config_mouse_binding
+        .clicks
+        .is_some_and(|clicks| !(1..=3).contains(&clicks))
+    {
+        return Err(String::from("Number of clicks must be between 1 and 3"));
+    }
+
+    let mut mods = ModifiersState::empty();
+    for modifier in config_mouse_binding.with.split('|') {
+        mods |= modifier_from_name(modifier).unwrap_or_default();
+    }
+
+    let res_mode = modes_from_names(&config_mouse_binding.mode);
+
+    Ok(MouseBinding {
+        trigger: MouseTrigger {
+            button,
+            clicks: config_mouse_binding.clicks,
+        },
+        mods,
+        action: config_mouse_binding.action.into(),
+        mode: res_mode.mode,
+        notmode: res_mode.not_mode,
+        prefix: Vec::new(),
+        table: None,
+        when: conditions::parse(&config_mouse_binding.when)?,
     })
 }
 
+/// Adds the mouse bindings of the config, replacing the bindings with the
+/// same triggers.
+pub fn mouse_bindings(
+    config_mouse_bindings: Vec<ConfigMouseBinding>,
+    mut bindings: Vec<MouseBinding>,
+) -> Vec<MouseBinding> {
+    let mut defaults = bindings.len();
+    for config_mouse_binding in config_mouse_bindings {
+        match convert_mouse(config_mouse_binding) {
+            Ok(mouse_binding) => {
+                if mouse_binding.when.is_empty() {
+                    defaults -= bindings[..defaults]
+                        .iter()
+                        .filter(|binding| binding.triggers_match(&mouse_binding))
+                        .count();
+                    bindings.retain(|binding| !binding.triggers_match(&mouse_binding));
+                } else {
+                    remove_default_bindings(&mut bindings, &mut defaults, &mouse_binding);
+                }
+                tracing::info!("added a new mouse_binding: {:?}", mouse_binding);
+                bindings.push(mouse_binding);
+            }
+            Err(err_message) => {
+                tracing::error!("error loading a mouse binding: {:?}", err_message);
+            }
+        }
+    }
+
+    bindings
+}
+
+/// Removes the default bindings with the same triggers as a binding with
+/// `when` conditions, whatever their conditions are. Otherwise the default
+/// would still fire when the conditions don't hold, like inside the editor
+/// of `~process=nvim`. Bindings of the config are kept, so several ones
+/// with different conditions can share the same keys.
+fn remove_default_bindings<T: Eq>(
+    bindings: &mut Vec<Binding<T>>,
+    defaults: &mut usize,
+    conditional: &Binding<T>,
+) {
+    let mut idx = 0;
+    while idx < *defaults {
+        if bindings[idx].triggers_match(conditional) {
+            bindings.remove(idx);
+            *defaults -= 1;
+            tracing::warn!(
+                "overwritten a default binding with {:?}",
+                conditional.action
+            );
+        } else {
+            idx += 1;
+        }
+    }
+}
+
 pub fn config_key_bindings(
     config_key_bindings: Vec<ConfigKeyBinding>,
     mut bindings: Vec<KeyBinding>,
@@ -851,8 +1343,14 @@ pub fn config_key_bindings(
         return bindings;
     }
 
+    let mut defaults = bindings.len();
     for ckb in config_key_bindings {
         match convert(ckb) {
+            Ok(key_binding) if !key_binding.when.is_empty() => {
+                remove_default_bindings(&mut bindings, &mut defaults, &key_binding);
+                tracing::info!("added a new key_binding: {:?}", key_binding);
+                bindings.push(key_binding)
+            }
             Ok(key_binding) => match key_binding.action {
                 Action::None | Action::ReceiveChar => {
                     let mut found_idx = None;
@@ -865,6 +1363,9 @@ pub fn config_key_bindings(
 
                     if let Some(idx) = found_idx {
                         bindings.remove(idx);
+                        if idx < defaults {
+                            defaults -= 1;
+                        }
                         tracing::warn!(
                             "overwritten a previous key_binding with new one: {:?}",
                             key_binding
@@ -919,6 +1420,7 @@ pub fn platform_key_bindings(
         "q", ModifiersState::SUPER; Action::Quit;
         "n", ModifiersState::SUPER; Action::WindowCreateNew;
         ",", ModifiersState::SUPER; Action::ConfigEditor;
+        "p", ModifiersState::SUPER | ModifiersState::SHIFT; Action::OpenCommandPalette;
 
         // Search
         "f", ModifiersState::SUPER, ~BindingMode::SEARCH; Action::SearchForward;
@@ -998,6 +1500,7 @@ pub fn platform_key_bindings(
         "-", ModifiersState::CONTROL;  Action::DecreaseFontSize;
         "n", ModifiersState::CONTROL | ModifiersState::SHIFT; Action::WindowCreateNew;
         ",", ModifiersState::CONTROL | ModifiersState::SHIFT; Action::ConfigEditor;
+        "p", ModifiersState::CONTROL | ModifiersState::SHIFT; Action::OpenCommandPalette;
 
         // Search
         "f", ModifiersState::CONTROL | ModifiersState::SHIFT, ~BindingMode::SEARCH; Action::SearchForward;
@@ -1058,6 +1561,7 @@ pub fn platform_key_bindings(
         Key::Named(Enter), ModifiersState::ALT; Action::ToggleFullscreen;
         "n", ModifiersState::CONTROL | ModifiersState::SHIFT; Action::WindowCreateNew;
         ",", ModifiersState::CONTROL | ModifiersState::SHIFT; Action::ConfigEditor;
+        "p", ModifiersState::CONTROL | ModifiersState::SHIFT; Action::OpenCommandPalette;
         // This is actually a Windows Powershell shortcut
         // https://github.com/alacritty/alacritty/issues/2930
         // https://github.com/raphamorim/rio/issues/220#issuecomment-1761651339
@@ -1122,6 +1626,9 @@ mod tests {
                 mode: BindingMode::empty(),
                 notmode: BindingMode::empty(),
                 trigger: Default::default(),
+                prefix: Vec::new(),
+                table: None,
+                when: Vec::new(),
             }
         }
     }
@@ -1370,11 +1877,14 @@ mod tests {
 
         let config_bindings = vec![ConfigKeyBinding {
             key: String::from("q"),
+            keys: String::from(""),
             action: String::from("receivechar"),
             with: String::from("super"),
             bytes: vec![],
             text: String::from(""),
             mode: String::from(""),
+            table: String::from(""),
+            when: String::from(""),
         }];
 
         let new_bindings = config_key_bindings(config_bindings, bindings);
@@ -1382,4 +1892,276 @@ mod tests {
         assert_eq!(new_bindings.len(), 2);
         assert_eq!(new_bindings[1].action, Action::ReceiveChar);
     }
+
+    #[test]
+    fn bindings_with_conditions_overwrite_defaults() {
+        let bindings = bindings!(
+            KeyBinding;
+            "h", ModifiersState::CONTROL; Action::SelectPrevSplit;
+            "l", ModifiersState::CONTROL; Action::SelectNextSplit;
+        );
+
+        let config_binding = |when: &str, action: &str| ConfigKeyBinding {
+            key: String::from("h"),
+            keys: String::from(""),
+            action: action.to_string(),
+            with: String::from("control"),
+            bytes: vec![],
+            text: String::from(""),
+            mode: String::from(""),
+            table: String::from(""),
+            when: when.to_string(),
+        };
+        let config_bindings = vec![
+            config_binding("~process=nvim", "selectprevsplit"),
+            config_binding("process=nvim | altscreen", "none"),
+        ];
+
+        let new_bindings = config_key_bindings(config_bindings, bindings);
+
+        assert_eq!(new_bindings.len(), 3);
+        assert_eq!(new_bindings[0].action, Action::SelectNextSplit);
+        assert_eq!(new_bindings[1].action, Action::SelectPrevSplit);
+        assert_eq!(
+            new_bindings[1].when,
+            vec![Condition::Not(Box::new(Condition::Process(vec![
+                String::from("nvim")
+            ])))]
+        );
+        assert_eq!(new_bindings[2].action, Action::None);
+        assert_eq!(
+            new_bindings[2].when,
+            vec![
+                Condition::Process(vec![String::from("nvim")]),
+                Condition::AltScreen
+            ]
+        );
+    }
+
+    #[test]
+    fn mouse_bindings_from_config() {
+        let config_binding =
+            |button: &str, clicks, with: &str, action: &str| ConfigMouseBinding {
+                button: button.to_string(),
+                clicks,
+                with: with.to_string(),
+                action: action.to_string(),
+                mode: String::from("~vi"),
+                when: String::new(),
+            };
+        let bindings = default_mouse_bindings(vec![
+            config_binding("middle", None, "", "paste"),
+            config_binding("left", Some(1), "control", "openhyperlink"),
+            config_binding("back", None, "", "selectprevtab"),
+            config_binding("left", Some(4), "", "paste"),
+            config_binding("wheel", None, "", "paste"),
+        ]);
+
+        // The default middle click binding is replaced, invalid ones are ignored
+        assert_eq!(bindings.len(), 5);
+        let middle: Vec<&MouseBinding> = bindings
+            .iter()
+            .filter(|binding| binding.trigger.button == MouseButton::Middle)
+            .collect();
+        assert_eq!(middle.len(), 1);
+        assert_eq!(middle[0].action, Action::Paste);
+
+        let mode = BindingMode::empty();
+        let open = &bindings[3];
+        assert_eq!(open.action, Action::OpenHyperlink);
+        assert!(open.is_triggered_by_click(
+            &mode,
+            ModifiersState::CONTROL,
+            MouseButton::Left,
+            1
+        ));
+        assert!(!open.is_triggered_by_click(
+            &mode,
+            ModifiersState::CONTROL,
+            MouseButton::Left,
+            2
+        ));
+        assert!(!open.is_triggered_by_click(
+            &mode,
+            ModifiersState::empty(),
+            MouseButton::Left,
+            1
+        ));
+        assert!(!open.is_triggered_by_click(
+            &BindingMode::VI,
+            ModifiersState::CONTROL,
+            MouseButton::Left,
+            1
+        ));
+        assert!(bindings[4].is_triggered_by_click(
+            &mode,
+            ModifiersState::empty(),
+            MouseButton::Back,
+            3
+        ));
+        assert_eq!(
+            Action::from(String::from("ExpandSelection")),
+            Action::Mouse(MouseAction::ExpandSelection)
+        );
+    }
+
+    #[test]
+    fn split_actions_from_string() {
+        assert_eq!(
+            Action::from(String::from("selectsplitleft")),
+            Action::SelectSplit(SplitDirection::Left)
+        );
+        assert_eq!(
+            Action::from(String::from("resizesplitup")),
+            Action::ResizeSplit(SplitDirection::Up, SplitSize::Cells(1))
+        );
+        assert_eq!(
+            Action::from(String::from("ResizeSplitRight(5)")),
+            Action::ResizeSplit(SplitDirection::Right, SplitSize::Cells(5))
+        );
+        assert_eq!(
+            Action::from(String::from("resizesplitdown(10%)")),
+            Action::ResizeSplit(SplitDirection::Down, SplitSize::Percentage(10))
+        );
+        assert_eq!(
+            Action::from(String::from("equalizesplits")),
+            Action::EqualizeSplits
+        );
+        assert_eq!(
+            Action::from(String::from("ToggleSplitZoom")),
+            Action::ToggleSplitZoom
+        );
+        assert_eq!(
+            Action::from(String::from("movesplittotab(2)")),
+            Action::MoveSplitToTab(2)
+        );
+        assert_eq!(
+            Action::from(String::from("movesplittonewtab")),
+            Action::MoveSplitToNewTab
+        );
+        assert_eq!(
+            Action::from(String::from("CreateTab:Dev")),
+            Action::TabCreateNewWithLayout(String::from("Dev"))
+        );
+        assert_eq!(Action::from(String::from("createtab:")), Action::None);
+        assert_eq!(
+            Action::from(String::from("ToggleBroadcast")),
+            Action::ToggleBroadcast(BroadcastScope::Tab)
+        );
+        assert_eq!(
+            Action::from(String::from("togglebroadcast(all)")),
+            Action::ToggleBroadcast(BroadcastScope::AllTabs)
+        );
+        assert_eq!(
+            Action::from(String::from("ToggleBroadcast(marked)")),
+            Action::ToggleBroadcast(BroadcastScope::Marked)
+        );
+        assert_eq!(
+            Action::from(String::from("marksplitforbroadcast")),
+            Action::MarkSplitForBroadcast
+        );
+        assert_eq!(
+            Action::from(String::from("ToggleRecording")),
+            Action::ToggleRecording
+        );
+        assert_eq!(
+            Action::from(String::from("ToggleHighlight")),
+            Action::ToggleHighlight
+        );
+        assert_eq!(
+            Action::from(String::from("ToggleTimestamps")),
+            Action::ToggleTimestamps
+        );
+        assert_eq!(
+            Action::from(String::from("ToggleMonitorActivity")),
+            Action::ToggleMonitorActivity
+        );
+        assert_eq!(
+            Action::from(String::from("ToggleMonitorSilence")),
+            Action::ToggleMonitorSilence
+        );
+        assert_eq!(
+            Action::from(String::from("SaveScreen")),
+            Action::SaveScreen(None)
+        );
+        assert_eq!(
+            Action::from(String::from("savescrollback(html)")),
+            Action::SaveScrollback(Some(ExportFormat::Html))
+        );
+        assert_eq!(
+            Action::from(String::from("OpenScrollbackInEditor")),
+            Action::OpenScrollbackInEditor
+        );
+        assert_eq!(
+            Action::from(String::from("SaveScreen(svg)")),
+            Action::SaveScreen(Some(ExportFormat::Svg))
+        );
+        assert_eq!(
+            Action::from(String::from("PlaybackSeek(-10)")),
+            Action::PlaybackSeek(-10)
+        );
+        assert_eq!(
+            Action::from(String::from("playbacktogglepause")),
+            Action::PlaybackTogglePause
+        );
+        assert_eq!(
+            Action::from(String::from("OpenCommandPalette")),
+            Action::OpenCommandPalette
+        );
+        assert_eq!(
+            Action::from(String::from("ActivateKeyTable:Resize")),
+            Action::ActivateKeyTable(String::from("Resize"))
+        );
+        assert_eq!(
+            Action::from(String::from("PopKeyTable")),
+            Action::PopKeyTable
+        );
+    }
+
+    #[test]
+    fn action_names_are_valid() {
+        let mut names = std::collections::HashSet::new();
+        for (name, action) in NAMED_ACTIONS.iter().chain(UNLISTED_ACTIONS) {
+            assert!(names.insert(name.to_lowercase()), "{name} is repeated");
+            assert_eq!(Action::from(name.to_lowercase()), action(), "{name}");
+        }
+        for (name, _) in NAMED_ACTIONS {
+            assert_ne!(Action::from(name.to_string()), Action::None, "{name}");
+        }
+    }
+
+    #[test]
+    fn key_binding_label() {
+        let binding = KeyBinding {
+            mods: ModifiersState::SUPER | ModifiersState::SHIFT,
+            action: Action::OpenCommandPalette,
+            mode: BindingMode::empty(),
+            notmode: BindingMode::empty(),
+            trigger: BindingKey::Keycode {
+                key: Character("p".into()),
+                location: KeyLocation::Standard,
+            },
+            prefix: Vec::new(),
+            table: None,
+            when: Vec::new(),
+        };
+        assert_eq!(binding.label(), "Super+Shift+P");
+
+        let binding = KeyBinding {
+            mods: ModifiersState::CONTROL,
+            trigger: BindingKey::Keycode {
+                key: Key::Named(Tab),
+                location: KeyLocation::Standard,
+            },
+            ..binding
+        };
+        assert_eq!(binding.label(), "Control+Tab");
+
+        let binding = KeyBinding {
+            mods: ModifiersState::empty(),
+            prefix: vec![(ModifiersState::CONTROL, binding.trigger.clone())],
+            ..binding
+        };
+        assert_eq!(binding.label(), "Control+Tab Tab");
+    }
 }
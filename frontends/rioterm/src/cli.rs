@@ -1,8 +1,10 @@
 // cli.rs was retired originally from https://github.com/alacritty/alacritty/blob/e35e5ad14fce8456afdd89f2b392b9924bb27471/alacritty/src/cli.rs
 // which is licensed under Apache 2.0 license.
 
-use clap::{Args, Parser, ValueHint};
+use clap::{Args, Parser, Subcommand, ValueEnum, ValueHint};
+use rio_backend::config::overrides::ConfigOverrides;
 use rio_backend::config::Shell;
+use rio_backend::crosswords::export::ExportFormat;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -12,6 +14,82 @@ pub struct Cli {
     /// Options which can be passed via IPC.
     #[clap(flatten)]
     pub window_options: WindowOptions,
+
+    /// Use a different config file instead of the default one.
+    #[clap(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    pub config_file: Option<PathBuf>,
+
+    /// Prints the config after defaults, platform overrides and themes are applied.
+    #[clap(
+        long,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        default_missing_value = "toml"
+    )]
+    pub print_config: Option<ConfigFormat>,
+
+    /// Converts a theme of another terminal (iTerm2, Alacritty, kitty,
+    /// Windows Terminal or base16) to a Rio theme in the themes directory.
+    #[clap(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub convert_theme: Option<PathBuf>,
+
+    /// Prints the JSON Schema of the config file.
+    #[cfg(feature = "schema")]
+    #[clap(long)]
+    pub print_config_schema: bool,
+
+    #[cfg(unix)]
+    #[clap(subcommand)]
+    pub subcommands: Option<Subcommands>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Send a message to a running Rio instance.
+    #[cfg(unix)]
+    Msg(MessageOptions),
+}
+
+#[cfg(unix)]
+#[derive(Args, Debug)]
+pub struct MessageOptions {
+    /// IPC socket of the instance (default: $RIO_SOCKET).
+    #[clap(short, long, value_hint = ValueHint::FilePath)]
+    pub socket: Option<PathBuf>,
+
+    #[clap(subcommand)]
+    pub message: SocketMessage,
+}
+
+/// Messages accepted by the IPC socket.
+#[derive(Subcommand, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum SocketMessage {
+    /// Save the screen of the focused split to a file.
+    SaveScreen(SaveScreenOptions),
+
+    /// Open a new window, with its own config overrides.
+    CreateWindow(WindowOptions),
+}
+
+#[derive(Args, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SaveScreenOptions {
+    /// File to write, the format is guessed from its extension.
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub output: PathBuf,
+
+    /// Format of the file: text, ansi, html or svg.
+    #[clap(short, long)]
+    pub format: Option<ExportFormat>,
+
+    /// Also save the scrollback history.
+    #[clap(long)]
+    pub scrollback: bool,
 }
 
 #[derive(Serialize, Deserialize, Args, Default, Clone, Debug, PartialEq, Eq)]
@@ -23,6 +101,10 @@ pub struct WindowOptions {
 
 #[derive(Serialize, Deserialize, Args, Default, Debug, Clone, PartialEq, Eq)]
 pub struct TerminalOptions {
+    /// Override a config value, parsed as TOML (e.g: `-o fonts.size=18`)
+    #[clap(short = 'o', long = "option", value_name = "KEY=VALUE", num_args = 1)]
+    pub option: Vec<String>,
+
     /// Command and args to execute (must be last argument).
     #[clap(short = 'e', long, allow_hyphen_values = true, num_args = 1..)]
     pub command: Vec<String>,
@@ -42,6 +124,22 @@ pub struct TerminalOptions {
     /// Start window with specified title
     #[clap(long, name = "title-placeholder")]
     pub title_placeholder: Option<String>,
+
+    /// Start with a split layout declared in the config (e.g: `[layouts.dev]`)
+    #[clap(long)]
+    pub layout: Option<String>,
+
+    /// Record the session in asciicast v2 format to the given file
+    #[clap(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub record: Option<PathBuf>,
+
+    /// Replay an asciicast or typescript file instead of starting a shell
+    #[clap(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub play: Option<PathBuf>,
+
+    /// Timing file of the typescript used by `--play` (e.g: `script -t`)
+    #[clap(long, value_name = "FILE", value_hint = ValueHint::FilePath, requires = "play")]
+    pub play_timing: Option<PathBuf>,
 }
 
 impl TerminalOptions {
@@ -58,6 +156,32 @@ impl TerminalOptions {
         })
     }
 
+    /// Config overrides passed with `-o`.
+    pub fn config_overrides(&self) -> Result<ConfigOverrides, String> {
+        ConfigOverrides::parse(&self.option)
+    }
+
+    /// Config overrides of a window opened through IPC, which also carry
+    /// the command, working directory and title.
+    #[cfg(unix)]
+    pub fn window_config_overrides(&self) -> Result<ConfigOverrides, String> {
+        use toml::Value;
+
+        let mut overrides = self.config_overrides()?;
+        if let Some(command) = self.command() {
+            let shell = Value::try_from(command).map_err(|err| err.to_string())?;
+            overrides.insert("shell", shell);
+            overrides.insert("use-fork", Value::Boolean(false));
+        }
+        if let Some(working_dir) = &self.working_dir {
+            overrides.insert("working-dir", Value::String(working_dir.to_owned()));
+        }
+        if let Some(title) = &self.title_placeholder {
+            overrides.insert("title.placeholder", Value::String(title.to_owned()));
+        }
+        Ok(overrides)
+    }
+
     // pub fn override_pty_config(&self, pty_config: &mut PtyConfig) {
     //     if let Some(working_directory) = &self.working_directory {
     //         if working_directory.is_dir() {
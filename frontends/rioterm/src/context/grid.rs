@@ -1,6 +1,8 @@
+use crate::context::monitor::TabMonitor;
 use crate::context::Context;
 use crate::mouse::Mouse;
 use rio_backend::crosswords::grid::Dimensions;
+use rio_backend::crosswords::timestamp::GUTTER_COLUMNS;
 use rio_backend::event::EventListener;
 use rio_backend::sugarloaf::{
     layout::SugarDimensions, ComposedQuad, Object, Quad, RichText, Sugarloaf,
@@ -11,6 +13,10 @@ const MIN_LINES: usize = 1;
 
 const PADDING: f32 = 2.;
 
+// Limit of passes used to spread the borders evenly, each pass moves
+// every border to the middle of its own container.
+const EQUALIZE_MAX_ITERATIONS: usize = 64;
+
 // $ tput columns
 // $ tput lines
 fn compute(
@@ -58,6 +64,49 @@ pub struct Delta<T: Default> {
     pub bottom_y: T,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// Amount used to resize a split.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitSize {
+    /// Number of columns (left/right) or lines (up/down).
+    Cells(usize),
+    /// Percentage of the grid width (left/right) or height (up/down).
+    Percentage(u8),
+}
+
+/// Area occupied by a grid item, in the same units used by `plot_objects`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct SplitRect {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+impl SplitRect {
+    #[inline]
+    fn right(&self) -> f32 {
+        self.x + self.width
+    }
+
+    #[inline]
+    fn bottom(&self) -> f32 {
+        self.y + self.height
+    }
+}
+
+#[inline]
+fn is_close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1.
+}
+
 pub struct ContextGrid<T: EventListener> {
     pub width: f32,
     pub height: f32,
@@ -65,12 +114,28 @@ pub struct ContextGrid<T: EventListener> {
     pub margin: Delta<f32>,
     border_color: [f32; 4],
     inner: Vec<ContextGridItem<T>>,
+    zoomed: Option<ZoomedSplit>,
+    pub monitor: TabMonitor,
+    /// A split rang the bell while the tab was in background.
+    pub bell: bool,
+}
+
+// Dimension of the current split before it got zoomed,
+// it's restored once the zoom is toggled off.
+#[derive(Clone, Copy)]
+struct ZoomedSplit {
+    width: f32,
+    height: f32,
+    margin: Delta<f32>,
 }
 
 pub struct ContextGridItem<T: EventListener> {
     val: Context<T>,
     right: Option<usize>,
     down: Option<usize>,
+    /// Context hidden while `val` is shown in its place, see
+    /// `ContextGrid::overlay_current`.
+    overlaid: Option<Box<Context<T>>>,
 }
 
 impl<T: rio_backend::event::EventListener> ContextGridItem<T> {
@@ -79,6 +144,7 @@ impl<T: rio_backend::event::EventListener> ContextGridItem<T> {
             val: context,
             right: None,
             down: None,
+            overlaid: None,
         }
     }
 }
@@ -108,6 +174,9 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
             width,
             height,
             border_color,
+            zoomed: None,
+            monitor: TabMonitor::default(),
+            bell: false,
         }
     }
 
@@ -122,8 +191,7 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
     }
 
     #[inline]
-    #[allow(unused)]
-    pub fn contexts(&mut self) -> &Vec<ContextGridItem<T>> {
+    pub fn contexts(&self) -> &Vec<ContextGridItem<T>> {
         &self.inner
     }
 
@@ -133,6 +201,8 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
             return;
         }
 
+        self.unzoom();
+
         if self.current >= self.inner.len() - 1 {
             self.current = 0;
         } else {
@@ -146,6 +216,8 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
             return;
         }
 
+        self.unzoom();
+
         if self.current == 0 {
             self.current = self.inner.len() - 1;
         } else {
@@ -153,8 +225,83 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
         }
     }
 
+    /// Select the closest split in the given direction based on the
+    /// splits geometry, returns true if the current split has changed.
+    pub fn select_split(&mut self, direction: SplitDirection) -> bool {
+        if self.inner.len() <= 1 {
+            return false;
+        }
+
+        self.unzoom();
+
+        let rects = self.rects();
+        let current = rects[self.current];
+        let mut selected: Option<(usize, f32, f32)> = None;
+        for (index, rect) in rects.iter().enumerate() {
+            if index == self.current {
+                continue;
+            }
+
+            let (distance, offset) = match direction {
+                SplitDirection::Left | SplitDirection::Right => {
+                    if rect.y >= current.bottom() || rect.bottom() <= current.y {
+                        continue;
+                    }
+
+                    let distance = if direction == SplitDirection::Right {
+                        rect.x - current.right()
+                    } else {
+                        current.x - rect.right()
+                    };
+                    (distance, (rect.y - current.y).abs())
+                }
+                SplitDirection::Up | SplitDirection::Down => {
+                    if rect.x >= current.right() || rect.right() <= current.x {
+                        continue;
+                    }
+
+                    let distance = if direction == SplitDirection::Down {
+                        rect.y - current.bottom()
+                    } else {
+                        current.y - rect.bottom()
+                    };
+                    (distance, (rect.x - current.x).abs())
+                }
+            };
+
+            if distance < 0. {
+                continue;
+            }
+
+            let is_closer = match selected {
+                Some((_, selected_distance, selected_offset)) => {
+                    distance < selected_distance
+                        || (is_close(distance, selected_distance)
+                            && offset < selected_offset)
+                }
+                None => true,
+            };
+
+            if is_closer {
+                selected = Some((index, distance, offset));
+            }
+        }
+
+        if let Some((new_current, _, _)) = selected {
+            // Reset old cursor to hollow
+            self.inner[self.current]
+                .val
+                .renderable_content
+                .mark_pending_updates();
+
+            self.current = new_current;
+            return true;
+        }
+
+        false
+    }
+
     #[inline]
-    #[allow(unused)]
     pub fn current_index(&self) -> usize {
         self.current
     }
@@ -186,6 +333,12 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
                     position: [self.margin.x, self.margin.top_y],
                 }));
             }
+        } else if self.zoomed.is_some() {
+            // Zoomed split takes the whole grid and hides the others
+            objects.push(Object::RichText(RichText {
+                id: self.inner[self.current].val.rich_text_id,
+                position: [self.margin.x, self.margin.top_y],
+            }));
         } else {
             self.plot_objects(&mut objects, 0, self.margin);
         }
@@ -194,7 +347,7 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
 
     pub fn current_context_with_computed_dimension(&self) -> (&Context<T>, Delta<f32>) {
         let len = self.inner.len();
-        if len <= 1 {
+        if len <= 1 || self.zoomed.is_some() {
             return (&self.inner[self.current].val, self.margin);
         }
 
@@ -220,7 +373,7 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
     #[inline]
     pub fn select_current_based_on_mouse(&mut self, mouse: &Mouse) -> bool {
         let len = self.inner.len();
-        if len <= 1 {
+        if len <= 1 || self.zoomed.is_some() {
             return false;
         }
 
@@ -346,6 +499,472 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
         }
     }
 
+    /// Draws a frame around the splits that own any of `rich_text_ids`.
+    pub fn plot_highlight(
+        &self,
+        objects: &mut Vec<Object>,
+        rich_text_ids: &[usize],
+        color: [f32; 4],
+    ) {
+        for rect in self.visible_rects(rich_text_ids) {
+            objects.push(create_border(color, [rect.x, rect.y], [rect.width, 1.]));
+            objects.push(create_border(
+                color,
+                [rect.x, rect.bottom() - 1.],
+                [rect.width, 1.],
+            ));
+            objects.push(create_border(color, [rect.x, rect.y], [1., rect.height]));
+            objects.push(create_border(
+                color,
+                [rect.right() - 1., rect.y],
+                [1., rect.height],
+            ));
+        }
+    }
+
+    /// Covers the whole area of a split with `color`.
+    pub fn plot_fill(
+        &self,
+        objects: &mut Vec<Object>,
+        rich_text_id: usize,
+        color: [f32; 4],
+    ) {
+        for rect in self.visible_rects(&[rich_text_id]) {
+            objects.push(create_border(
+                color,
+                [rect.x, rect.y],
+                [rect.width, rect.height],
+            ));
+        }
+    }
+
+    fn visible_rects(&self, rich_text_ids: &[usize]) -> Vec<SplitRect> {
+        let mut visible = Vec::with_capacity(rich_text_ids.len());
+        for (index, rect) in self.rects().into_iter().enumerate() {
+            if !rich_text_ids.contains(&self.inner[index].val.rich_text_id) {
+                continue;
+            }
+
+            // Zoomed split is the only one visible and it's placed in the grid margin
+            let rect = match self.zoomed {
+                Some(_) if index != self.current => continue,
+                Some(_) => SplitRect {
+                    x: self.margin.x,
+                    y: self.margin.top_y,
+                    ..rect
+                },
+                None => rect,
+            };
+            visible.push(rect);
+        }
+        visible
+    }
+
+    // Same walk done by plot_objects but it only collects the area of each
+    // item, rects are indexed in the same order of the grid items.
+    fn rects(&self) -> Vec<SplitRect> {
+        let mut rects = vec![SplitRect::default(); self.inner.len()];
+        if !self.inner.is_empty() {
+            self.plot_rects(&mut rects, 0, self.margin.x, self.margin.top_y);
+        }
+        rects
+    }
+
+    fn plot_rects(&self, rects: &mut Vec<SplitRect>, index: usize, x: f32, y: f32) {
+        if let Some(item) = self.inner.get(index) {
+            let scale = item.val.dimension.dimension.scale;
+            let scaled_padding =
+                PADDING * self.inner[self.current].val.dimension.dimension.scale;
+            let rect = SplitRect {
+                x,
+                y,
+                width: item.val.dimension.width / scale,
+                height: item.val.dimension.height / scale,
+            };
+            rects[index] = rect;
+
+            if let Some(down_item) = item.down {
+                self.plot_rects(rects, down_item, x, rect.bottom() + scaled_padding);
+            }
+
+            if let Some(right_item) = item.right {
+                self.plot_rects(rects, right_item, rect.right() + scaled_padding, y);
+            }
+        }
+    }
+
+    // Finds every item that shares the border placed on `edge` of the item
+    // in `index`. The border is the one that would be plot in the same line
+    // so items are only considered if they are touching each other.
+    // Returns the border position and the items before/after it.
+    fn border_groups(
+        &self,
+        rects: &[SplitRect],
+        index: usize,
+        edge: SplitDirection,
+    ) -> Option<(f32, Vec<usize>, Vec<usize>)> {
+        let scale = self.inner[self.current].val.dimension.dimension.scale;
+        let scaled_padding = PADDING * scale;
+        let rect = rects.get(index)?;
+
+        let is_vertical = matches!(edge, SplitDirection::Left | SplitDirection::Right);
+        let border = match edge {
+            SplitDirection::Right => rect.right(),
+            SplitDirection::Left => rect.x - scaled_padding,
+            SplitDirection::Down => rect.bottom(),
+            SplitDirection::Up => rect.y - scaled_padding,
+        };
+
+        let mut candidates = Vec::new();
+        for (position, item) in rects.iter().enumerate() {
+            let (start, end) = if is_vertical {
+                (item.x, item.right())
+            } else {
+                (item.y, item.bottom())
+            };
+
+            if is_close(end, border) {
+                candidates.push((position, true));
+            } else if is_close(start, border + scaled_padding) {
+                candidates.push((position, false));
+            }
+        }
+
+        if !candidates.iter().any(|(position, _)| *position == index) {
+            return None;
+        }
+
+        let overlaps = |a: &SplitRect, b: &SplitRect| {
+            if is_vertical {
+                a.y < b.bottom() + scaled_padding + 1.
+                    && b.y < a.bottom() + scaled_padding + 1.
+            } else {
+                a.x < b.right() + scaled_padding + 1.
+                    && b.x < a.right() + scaled_padding + 1.
+            }
+        };
+
+        let mut members = vec![index];
+        let mut has_changed = true;
+        while has_changed {
+            has_changed = false;
+            for (position, _) in &candidates {
+                if members.contains(position) {
+                    continue;
+                }
+
+                if members
+                    .iter()
+                    .any(|member| overlaps(&rects[*member], &rects[*position]))
+                {
+                    members.push(*position);
+                    has_changed = true;
+                }
+            }
+        }
+
+        let mut before = Vec::new();
+        let mut after = Vec::new();
+        for (position, is_before) in candidates {
+            if members.contains(&position) {
+                if is_before {
+                    before.push(position);
+                } else {
+                    after.push(position);
+                }
+            }
+        }
+
+        if before.is_empty() || after.is_empty() {
+            return None;
+        }
+
+        Some((border, before, after))
+    }
+
+    // Moves the border on the `edge` of the item in `index` by `delta`
+    // (in pixels), items before the border grow and items after shrink.
+    // The delta is clamped so every item keeps the minimum dimension.
+    // Returns the indexes of the items that were updated.
+    fn shift_border(
+        &mut self,
+        index: usize,
+        edge: SplitDirection,
+        delta: f32,
+    ) -> Vec<usize> {
+        let rects = self.rects();
+        let (_, before, after) = match self.border_groups(&rects, index, edge) {
+            Some(groups) => groups,
+            None => return vec![],
+        };
+
+        let is_vertical = matches!(edge, SplitDirection::Left | SplitDirection::Right);
+        let available = |items: &[usize]| {
+            items
+                .iter()
+                .map(|item| {
+                    let dimension = &self.inner[*item].val.dimension;
+                    if is_vertical {
+                        dimension.width - dimension.min_width()
+                    } else {
+                        dimension.height - dimension.min_height()
+                    }
+                })
+                .fold(f32::MAX, f32::min)
+                .max(0.)
+        };
+
+        let delta = if delta > 0. {
+            delta.min(available(&after))
+        } else {
+            delta.max(-available(&before))
+        };
+
+        if delta.abs() < 1. {
+            return vec![];
+        }
+
+        for item in &before {
+            let dimension = &mut self.inner[*item].val.dimension;
+            if is_vertical {
+                dimension.increase_width(delta);
+            } else {
+                dimension.increase_height(delta);
+            }
+        }
+
+        for item in &after {
+            let dimension = &mut self.inner[*item].val.dimension;
+            if is_vertical {
+                dimension.increase_width(-delta);
+            } else {
+                dimension.increase_height(-delta);
+            }
+        }
+
+        let mut changed = before;
+        changed.extend(after);
+        changed
+    }
+
+    /// Moves the border on `edge` of the split in `index` by `delta` pixels,
+    /// used for dragging borders with the mouse.
+    pub fn move_border(
+        &mut self,
+        index: usize,
+        edge: SplitDirection,
+        delta: f32,
+    ) -> bool {
+        self.unzoom();
+
+        let changed = self.shift_border(index, edge, delta);
+        for item in &changed {
+            self.request_resize(*item);
+        }
+
+        !changed.is_empty()
+    }
+
+    /// Returns the split and the edge of the border located at the
+    /// position (in physical pixels), only borders between splits count.
+    pub fn border_at(&self, x: f32, y: f32) -> Option<(usize, SplitDirection)> {
+        if self.inner.len() <= 1 || self.zoomed.is_some() {
+            return None;
+        }
+
+        let scale = self.inner[self.current].val.dimension.dimension.scale;
+        let scaled_padding = PADDING * scale;
+        let x = x / scale;
+        let y = y / scale;
+        let rects = self.rects();
+
+        for (index, rect) in rects.iter().enumerate() {
+            if y >= rect.y
+                && y <= rect.bottom()
+                && x >= rect.right() - 1.
+                && x <= rect.right() + scaled_padding + 1.
+                && self
+                    .border_groups(&rects, index, SplitDirection::Right)
+                    .is_some()
+            {
+                return Some((index, SplitDirection::Right));
+            }
+
+            if x >= rect.x
+                && x <= rect.right()
+                && y >= rect.bottom() - 1.
+                && y <= rect.bottom() + scaled_padding + 1.
+                && self
+                    .border_groups(&rects, index, SplitDirection::Down)
+                    .is_some()
+            {
+                return Some((index, SplitDirection::Down));
+            }
+        }
+
+        None
+    }
+
+    /// Resize the current split towards `direction`. Like tmux it moves
+    /// the right/bottom border and fallbacks to the left/top border
+    /// whenever the split is the last one.
+    pub fn resize_split(&mut self, direction: SplitDirection, size: SplitSize) -> bool {
+        if self.inner.len() <= 1 {
+            return false;
+        }
+
+        self.unzoom();
+
+        let dimension = self.inner[self.current].val.dimension;
+        let is_vertical =
+            matches!(direction, SplitDirection::Left | SplitDirection::Right);
+        let amount = match size {
+            SplitSize::Cells(cells) if is_vertical => {
+                cells as f32 * dimension.dimension.width
+            }
+            SplitSize::Cells(cells) => {
+                cells as f32 * dimension.dimension.height * dimension.line_height
+            }
+            SplitSize::Percentage(percentage) if is_vertical => {
+                self.width * percentage as f32 / 100.
+            }
+            SplitSize::Percentage(percentage) => self.height * percentage as f32 / 100.,
+        };
+
+        let (edges, delta) = match direction {
+            SplitDirection::Right => {
+                ([SplitDirection::Right, SplitDirection::Left], amount)
+            }
+            SplitDirection::Left => {
+                ([SplitDirection::Right, SplitDirection::Left], -amount)
+            }
+            SplitDirection::Down => ([SplitDirection::Down, SplitDirection::Up], amount),
+            SplitDirection::Up => ([SplitDirection::Down, SplitDirection::Up], -amount),
+        };
+
+        let rects = self.rects();
+        for edge in edges {
+            if self.border_groups(&rects, self.current, edge).is_some() {
+                return self.move_border(self.current, edge, delta);
+            }
+        }
+
+        false
+    }
+
+    /// Gives `percentage` of the area shared by the current split and the
+    /// split it came from (`parent`) to the current split.
+    pub fn set_split_percentage(
+        &mut self,
+        parent: usize,
+        is_down: bool,
+        percentage: u8,
+    ) -> bool {
+        if parent >= self.inner.len() || parent == self.current {
+            return false;
+        }
+
+        let scale = self.inner[self.current].val.dimension.dimension.scale;
+        let scaled_padding = PADDING * scale;
+        let current = self.inner[self.current].val.dimension;
+        let parent_dimension = self.inner[parent].val.dimension;
+        let (edge, total, size) = if is_down {
+            (
+                SplitDirection::Down,
+                parent_dimension.height + current.height + scaled_padding,
+                current.height,
+            )
+        } else {
+            (
+                SplitDirection::Right,
+                parent_dimension.width + current.width + scaled_padding,
+                current.width,
+            )
+        };
+
+        let target = total * percentage.min(100) as f32 / 100.;
+        self.move_border(parent, edge, size - target)
+    }
+
+    /// Shows (or hides) the timestamps gutter of the current split,
+    /// returns true if the gutter ended up visible.
+    pub fn toggle_timestamps(&mut self) -> bool {
+        let context = &mut self.inner[self.current].val;
+        let gutter = if context.dimension.gutter == 0 {
+            GUTTER_COLUMNS
+        } else {
+            0
+        };
+        context.dimension.update_gutter(gutter);
+        context.renderable_content.mark_pending_updates();
+        self.request_resize(self.current);
+
+        gutter != 0
+    }
+
+    /// Spread the borders of the grid so splits sharing the same
+    /// container end up with the same size.
+    pub fn equalize_splits(&mut self) {
+        if self.inner.len() <= 1 {
+            return;
+        }
+
+        self.unzoom();
+
+        let scale = self.inner[self.current].val.dimension.dimension.scale;
+        let scaled_padding = PADDING * scale;
+        let mut changed = vec![false; self.inner.len()];
+
+        for _ in 0..EQUALIZE_MAX_ITERATIONS {
+            let mut has_moved = false;
+            for edge in [SplitDirection::Right, SplitDirection::Down] {
+                for index in 0..self.inner.len() {
+                    let rects = self.rects();
+                    let (border, before, after) =
+                        match self.border_groups(&rects, index, edge) {
+                            Some(groups) => groups,
+                            None => continue,
+                        };
+
+                    let (start, end) = if edge == SplitDirection::Right {
+                        (
+                            before.iter().map(|i| rects[*i].x).fold(f32::MAX, f32::min),
+                            after
+                                .iter()
+                                .map(|i| rects[*i].right())
+                                .fold(f32::MIN, f32::max),
+                        )
+                    } else {
+                        (
+                            before.iter().map(|i| rects[*i].y).fold(f32::MAX, f32::min),
+                            after
+                                .iter()
+                                .map(|i| rects[*i].bottom())
+                                .fold(f32::MIN, f32::max),
+                        )
+                    };
+
+                    let target = (start + end - scaled_padding) / 2.;
+                    for item in self.shift_border(index, edge, (target - border) * scale)
+                    {
+                        changed[item] = true;
+                        has_moved = true;
+                    }
+                }
+            }
+
+            if !has_moved {
+                break;
+            }
+        }
+
+        for (index, has_changed) in changed.into_iter().enumerate() {
+            if has_changed {
+                self.request_resize(index);
+            }
+        }
+    }
+
     pub fn update_margin(&mut self, padding: (f32, f32, f32)) {
         self.margin = Delta {
             x: padding.0,
@@ -355,6 +974,10 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
         for context in &mut self.inner {
             context.val.dimension.update_margin(self.margin);
         }
+
+        if let Some(zoomed) = &mut self.zoomed {
+            zoomed.margin = self.margin;
+        }
     }
 
     pub fn update_line_height(&mut self, line_height: f32) {
@@ -371,6 +994,10 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
     }
 
     pub fn resize(&mut self, new_width: f32, new_height: f32) {
+        // Zoom is applied again after the splits get the new dimensions
+        let is_zoomed = self.zoomed.is_some();
+        self.unzoom();
+
         let width_difference = new_width - self.width;
         let height_difference = new_height - self.height;
         self.width = new_width;
@@ -387,12 +1014,11 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
             let current_height = context.val.dimension.height;
             context.val.dimension.update_height(current_height + val.1);
 
-            let mut terminal = context.val.terminal.lock();
-            terminal.resize::<ContextDimension>(context.val.dimension);
-            drop(terminal);
-            let winsize =
-                crate::renderer::utils::terminal_dimensions(&context.val.dimension);
-            let _ = context.val.messenger.send_resize(winsize);
+            self.request_resize(index);
+        }
+
+        if is_zoomed {
+            self.zoom();
         }
     }
 
@@ -435,16 +1061,185 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
         (available_width, available_height)
     }
 
-    fn request_resize(&mut self, index: usize) {
-        let mut terminal = self.inner[index].val.terminal.lock();
-        terminal.resize::<ContextDimension>(self.inner[index].val.dimension);
-        drop(terminal);
+    pub fn request_resize(&mut self, index: usize) {
+        // Recordings played with their own size keep it
+        if !self.inner[index].val.playback_sized {
+            let mut terminal = self.inner[index].val.terminal.lock();
+            terminal.resize::<ContextDimension>(self.inner[index].val.dimension);
+            drop(terminal);
+        }
         let winsize =
             crate::renderer::utils::terminal_dimensions(&self.inner[index].val.dimension);
         let _ = self.inner[index].val.messenger.send_resize(winsize);
     }
 
-    pub fn remove_current(&mut self) {
+    #[inline]
+    #[allow(unused)]
+    pub fn is_zoomed(&self) -> bool {
+        self.zoomed.is_some()
+    }
+
+    /// Makes the current split take the whole grid (or restores it),
+    /// returns true if the grid ended up zoomed.
+    pub fn toggle_zoom(&mut self) -> bool {
+        if self.zoomed.is_some() {
+            self.unzoom();
+        } else if self.inner.len() > 1 {
+            self.zoom();
+        }
+
+        self.zoomed.is_some()
+    }
+
+    fn zoom(&mut self) {
+        let dimension = &mut self.inner[self.current].val.dimension;
+        self.zoomed = Some(ZoomedSplit {
+            width: dimension.width,
+            height: dimension.height,
+            margin: dimension.margin,
+        });
+
+        dimension.update_margin(self.margin);
+        dimension.update_width(self.width);
+        dimension.update_height(self.height);
+        self.request_resize(self.current);
+    }
+
+    fn unzoom(&mut self) {
+        if let Some(zoomed) = self.zoomed.take() {
+            let dimension = &mut self.inner[self.current].val.dimension;
+            dimension.update_margin(zoomed.margin);
+            dimension.update_width(zoomed.width);
+            dimension.update_height(zoomed.height);
+            self.request_resize(self.current);
+
+            for item in &mut self.inner {
+                item.val.renderable_content.mark_pending_updates();
+            }
+        }
+    }
+
+    /// Swaps the current split with the next one, the layout stays
+    /// the same and only the terminals switch places.
+    pub fn swap_with_next(&mut self) -> bool {
+        let len = self.inner.len();
+        if len <= 1 {
+            return false;
+        }
+
+        let target = if self.current >= len - 1 {
+            0
+        } else {
+            self.current + 1
+        };
+        self.swap_with(target);
+        true
+    }
+
+    /// Swaps the current split with the previous one.
+    pub fn swap_with_prev(&mut self) -> bool {
+        let len = self.inner.len();
+        if len <= 1 {
+            return false;
+        }
+
+        let target = if self.current == 0 {
+            len - 1
+        } else {
+            self.current - 1
+        };
+        self.swap_with(target);
+        true
+    }
+
+    fn swap_with(&mut self, target: usize) {
+        self.unzoom();
+
+        let current = self.current;
+        let (first, second) = if current < target {
+            (current, target)
+        } else {
+            (target, current)
+        };
+
+        let (head, tail) = self.inner.split_at_mut(second);
+        let first_item = &mut head[first].val;
+        let second_item = &mut tail[0].val;
+        std::mem::swap(first_item, second_item);
+        // Each slot keeps its own area
+        std::mem::swap(&mut first_item.dimension, &mut second_item.dimension);
+
+        first_item.renderable_content.mark_pending_updates();
+        second_item.renderable_content.mark_pending_updates();
+
+        self.request_resize(first);
+        self.request_resize(second);
+        self.current = target;
+    }
+
+    /// Shows `context` in place of the current split, which comes back
+    /// once the context is closed with `restore_overlaid`.
+    pub fn overlay_current(&mut self, mut context: Context<T>) {
+        let current = self.current;
+        let item = &mut self.inner[current];
+        context.dimension = item.val.dimension;
+        let original = std::mem::replace(&mut item.val, context);
+        item.overlaid = Some(Box::new(original));
+        self.request_resize(current);
+    }
+
+    /// Brings back the split hidden by the overlay with `route_id`, the
+    /// overlay context is dropped. Returns if any split was restored.
+    pub fn restore_overlaid(&mut self, route_id: usize) -> bool {
+        let index = match self
+            .inner
+            .iter()
+            .position(|item| item.overlaid.is_some() && item.val.route_id == route_id)
+        {
+            Some(index) => index,
+            None => return false,
+        };
+
+        let item = &mut self.inner[index];
+        if let Some(mut original) = item.overlaid.take() {
+            // Window may have been resized while the overlay was shown
+            original.dimension = item.val.dimension;
+            original.renderable_content.mark_pending_updates();
+            item.val = *original;
+            self.request_resize(index);
+        }
+
+        true
+    }
+
+    /// Drops the split with `route_id` hidden by an overlay, the overlay
+    /// then closes like any other split. Returns if any split was dropped.
+    pub fn drop_overlaid(&mut self, route_id: usize) -> bool {
+        match self.inner.iter_mut().find(|item| {
+            item.overlaid
+                .as_ref()
+                .is_some_and(|overlaid| overlaid.route_id == route_id)
+        }) {
+            Some(item) => {
+                item.overlaid = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Consumes the grid returning the current context, used to move
+    /// the context of a grid with a single split.
+    pub fn into_current(mut self) -> Context<T> {
+        let current = self.current;
+        self.inner.swap_remove(current).val
+    }
+
+    /// Removes the current split and returns its context, the context
+    /// will be dropped (and the process killed) unless it's kept.
+    pub fn remove_current(&mut self) -> Option<Context<T>> {
+        self.unzoom();
+
         // Note: if is to_be_removed is first item then do not look for parenting,
         // should not exist an item without parenting and isn't zero as index
         let to_be_removed = self.current;
@@ -489,7 +1284,7 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
                         // remove index first to update all children before set
                         // stuff
                         self.request_resize(current_down);
-                        self.remove_index(to_be_removed);
+                        let removed = self.remove_index(to_be_removed);
                         next_current = current_down.wrapping_sub(1);
 
                         // If the bottom item had also we need to place
@@ -530,7 +1325,7 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
 
                         self.inner[parent_index].right = Some(next_current);
                         self.current = next_current;
-                        return;
+                        return Some(removed.val);
                     // If current has no down items then check right items to inherit
                     } else {
                         let parent_width = self.inner[parent_index].val.dimension.width;
@@ -585,9 +1380,9 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
                     }
                 }
 
-                self.remove_index(to_be_removed);
+                let removed = self.remove_index(to_be_removed);
                 self.current = next_current;
-                return;
+                return Some(removed.val);
             }
         }
 
@@ -618,7 +1413,7 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
             // First item of the children will move to first position (0)
             self.inner.swap(to_be_removed, down_val);
             self.request_resize(to_be_removed);
-            self.remove_index(down_val);
+            let removed = self.remove_index(down_val);
 
             let new_index = to_be_removed;
 
@@ -649,6 +1444,8 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
                     self.inner[new_index].right = Some(right_val);
                 }
             }
+
+            return Some(removed.val);
         } else if let Some(right_val) = self.inner[to_be_removed].right {
             let right_width = self.inner[right_val].val.dimension.width;
             self.inner[right_val]
@@ -674,7 +1471,7 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
             // First item of the children will move to first position (0)
             self.inner.swap(to_be_removed, right_val);
             self.request_resize(to_be_removed);
-            self.remove_index(right_val);
+            let removed = self.remove_index(right_val);
 
             let new_index = to_be_removed;
 
@@ -705,10 +1502,14 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
                     self.inner[new_index].down = Some(down_val);
                 }
             }
+
+            return Some(removed.val);
         }
+
+        None
     }
 
-    fn remove_index(&mut self, index: usize) {
+    fn remove_index(&mut self, index: usize) -> ContextGridItem<T> {
         // If an index is in the middle, example 6th
         // then [0,1,2,3,4,5,6,7,8,9,10]
         //
@@ -730,10 +1531,12 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
                 }
             }
         }
-        self.inner.remove(index);
+        self.inner.remove(index)
     }
 
     pub fn split_right(&mut self, context: Context<T>) {
+        self.unzoom();
+
         let old_right = self.inner[self.current].right;
         // let margin_x = self.margin.x;
 
@@ -792,6 +1595,8 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
     }
 
     pub fn split_down(&mut self, context: Context<T>) {
+        self.unzoom();
+
         let old_down = self.inner[self.current].down;
 
         let old_grid_item_height = self.inner[self.current].val.dimension.height;
@@ -856,6 +1661,8 @@ pub struct ContextDimension {
     pub dimension: SugarDimensions,
     pub margin: Delta<f32>,
     pub line_height: f32,
+    /// Columns taken by the timestamps gutter.
+    pub gutter: usize,
 }
 
 impl Default for ContextDimension {
@@ -868,6 +1675,7 @@ impl Default for ContextDimension {
             line_height: 1.,
             dimension: SugarDimensions::default(),
             margin: Delta::<f32>::default(),
+            gutter: 0,
         }
     }
 }
@@ -889,6 +1697,7 @@ impl ContextDimension {
             dimension,
             margin,
             line_height,
+            gutter: 0,
         }
     }
 
@@ -934,6 +1743,27 @@ impl ContextDimension {
         self.update();
     }
 
+    #[inline]
+    pub fn update_gutter(&mut self, gutter: usize) {
+        self.gutter = gutter;
+        self.update();
+    }
+
+    /// Smallest width (in pixels) that still fits the minimum of columns.
+    #[inline]
+    pub fn min_width(&self) -> f32 {
+        (MIN_COLS as f32 * self.dimension.width)
+            + (self.margin.x * self.dimension.scale).floor()
+    }
+
+    /// Smallest height (in pixels) that still fits the minimum of lines.
+    #[inline]
+    pub fn min_height(&self) -> f32 {
+        let margin_spaces = self.margin.top_y + self.margin.bottom_y;
+        (MIN_LINES as f32 * self.dimension.height * self.line_height)
+            + (margin_spaces * self.dimension.scale)
+    }
+
     #[inline]
     fn update(&mut self) {
         let (columns, lines) = compute(
@@ -944,7 +1774,7 @@ impl ContextDimension {
             self.margin,
         );
 
-        self.columns = columns;
+        self.columns = std::cmp::max(columns.saturating_sub(self.gutter), MIN_COLS);
         self.lines = lines;
     }
 }
@@ -3597,4 +4427,393 @@ pub mod test {
         assert_eq!(grid.current_index(), 2);
         assert_eq!(grid.current().rich_text_id, third_context_id);
     }
+
+    #[test]
+    fn test_resize_select_and_equalize_splits() {
+        let margin = Delta {
+            x: 0.,
+            top_y: 0.,
+            bottom_y: 0.,
+        };
+
+        let context_dimension = ContextDimension::build(
+            600.0,
+            600.0,
+            SugarDimensions {
+                scale: 1.,
+                width: 10.,
+                height: 20.,
+            },
+            1.0,
+            Delta::<f32>::default(),
+        );
+
+        let create_context = |rich_text_id: usize| {
+            create_mock_context(
+                VoidListener {},
+                WindowId::from(0),
+                0,
+                rich_text_id,
+                context_dimension,
+            )
+        };
+
+        let mut grid =
+            ContextGrid::<VoidListener>::new(create_context(0), margin, [0., 0., 0., 0.]);
+
+        // Nothing to resize or select with a single split
+        assert!(!grid.resize_split(SplitDirection::Right, SplitSize::Cells(1)));
+        assert!(!grid.select_split(SplitDirection::Right));
+
+        // [0 | 1]
+        grid.split_right(create_context(1));
+        assert_eq!(grid.current_index(), 1);
+        assert_eq!(grid.inner[0].val.dimension.width, 298.);
+        assert_eq!(grid.inner[1].val.dimension.width, 300.);
+
+        // [0 | 1]
+        //     [-]
+        //     [2]
+        grid.split_down(create_context(2));
+        assert_eq!(grid.current_index(), 2);
+
+        assert!(grid.select_split(SplitDirection::Up));
+        assert_eq!(grid.current_index(), 1);
+        assert!(!grid.select_split(SplitDirection::Up));
+        assert!(!grid.select_split(SplitDirection::Right));
+        assert!(grid.select_split(SplitDirection::Left));
+        assert_eq!(grid.current_index(), 0);
+        assert!(grid.select_split(SplitDirection::Right));
+        assert_eq!(grid.current_index(), 1);
+        assert!(grid.select_split(SplitDirection::Down));
+        assert_eq!(grid.current_index(), 2);
+
+        // Moving the left border of 2 also moves the border of 1
+        // and shrinks 0, since the border is shared.
+        assert!(grid.resize_split(SplitDirection::Left, SplitSize::Cells(5)));
+        assert_eq!(grid.inner[0].val.dimension.width, 248.);
+        assert_eq!(grid.inner[1].val.dimension.width, 350.);
+        assert_eq!(grid.inner[2].val.dimension.width, 350.);
+        assert_eq!(grid.inner[1].val.dimension.columns, 35);
+
+        // The last split moves the top border
+        let height = grid.inner[2].val.dimension.height;
+        assert!(grid.resize_split(SplitDirection::Up, SplitSize::Percentage(10)));
+        assert_eq!(grid.inner[2].val.dimension.height, height + 60.);
+        assert_eq!(grid.inner[0].val.dimension.width, 248.);
+
+        // Splits can't go lower than the minimum of columns
+        assert!(grid.resize_split(SplitDirection::Left, SplitSize::Cells(100)));
+        assert_eq!(
+            grid.inner[0].val.dimension.width,
+            grid.inner[0].val.dimension.min_width()
+        );
+        assert_eq!(grid.inner[0].val.dimension.columns, MIN_COLS);
+        assert!(!grid.resize_split(SplitDirection::Left, SplitSize::Cells(1)));
+
+        grid.equalize_splits();
+        assert_eq!(grid.inner[0].val.dimension.width, 299.);
+        assert_eq!(grid.inner[1].val.dimension.width, 299.);
+        assert_eq!(grid.inner[2].val.dimension.width, 299.);
+        assert_eq!(
+            grid.inner[1].val.dimension.height,
+            grid.inner[2].val.dimension.height
+        );
+    }
+
+    #[test]
+    fn test_move_border_based_on_mouse() {
+        let margin = Delta {
+            x: 0.,
+            top_y: 0.,
+            bottom_y: 0.,
+        };
+
+        let context_dimension = ContextDimension::build(
+            600.0,
+            600.0,
+            SugarDimensions {
+                scale: 2.,
+                width: 14.,
+                height: 8.,
+            },
+            1.0,
+            Delta::<f32>::default(),
+        );
+
+        let create_context = |rich_text_id: usize| {
+            create_mock_context(
+                VoidListener {},
+                WindowId::from(0),
+                0,
+                rich_text_id,
+                context_dimension,
+            )
+        };
+
+        let mut grid =
+            ContextGrid::<VoidListener>::new(create_context(0), margin, [0., 0., 0., 0.]);
+
+        assert_eq!(grid.border_at(300., 10.), None);
+
+        grid.split_right(create_context(1));
+        assert_eq!(grid.inner[0].val.dimension.width, 296.);
+
+        // The border is placed right after the first split
+        assert_eq!(grid.border_at(10., 10.), None);
+        assert_eq!(grid.border_at(450., 10.), None);
+        assert_eq!(grid.border_at(298., 10.), Some((0, SplitDirection::Right)));
+
+        assert!(grid.move_border(0, SplitDirection::Right, 50.));
+        assert_eq!(grid.inner[0].val.dimension.width, 346.);
+        assert_eq!(grid.inner[1].val.dimension.width, 250.);
+        assert_eq!(grid.border_at(298., 10.), None);
+        assert_eq!(grid.border_at(348., 10.), Some((0, SplitDirection::Right)));
+
+        assert!(grid.move_border(0, SplitDirection::Right, -100.));
+        assert_eq!(grid.inner[0].val.dimension.width, 246.);
+        assert_eq!(grid.inner[1].val.dimension.width, 350.);
+
+        // There's no border on the bottom
+        assert!(!grid.move_border(0, SplitDirection::Down, 10.));
+    }
+
+    #[test]
+    fn test_toggle_zoom_and_swap_splits() {
+        let margin = Delta {
+            x: 0.,
+            top_y: 0.,
+            bottom_y: 0.,
+        };
+
+        let context_dimension = ContextDimension::build(
+            600.0,
+            600.0,
+            SugarDimensions {
+                scale: 1.,
+                width: 10.,
+                height: 20.,
+            },
+            1.0,
+            Delta::<f32>::default(),
+        );
+
+        let create_context = |rich_text_id: usize| {
+            create_mock_context(
+                VoidListener {},
+                WindowId::from(0),
+                0,
+                rich_text_id,
+                context_dimension,
+            )
+        };
+
+        let mut grid =
+            ContextGrid::<VoidListener>::new(create_context(0), margin, [0., 0., 0., 0.]);
+
+        // A single split can't be zoomed
+        assert!(!grid.toggle_zoom());
+
+        grid.split_right(create_context(1));
+        assert_eq!(grid.current().dimension.width, 300.);
+        assert_eq!(grid.current().dimension.columns, 30);
+
+        assert!(grid.toggle_zoom());
+        assert!(grid.is_zoomed());
+        assert_eq!(grid.current().dimension.width, 600.);
+        assert_eq!(grid.current().dimension.columns, 60);
+        assert_eq!(
+            grid.objects(),
+            vec![Object::RichText(RichText {
+                id: 1,
+                position: [0., 0.],
+            })]
+        );
+
+        // Resize keeps the zoom
+        grid.resize(800., 600.);
+        assert!(grid.is_zoomed());
+        assert_eq!(grid.current().dimension.width, 800.);
+
+        assert!(!grid.toggle_zoom());
+        assert_eq!(grid.current().dimension.width, 400.);
+        let rich_texts = grid
+            .objects()
+            .into_iter()
+            .filter(|object| matches!(object, Object::RichText(_)))
+            .count();
+        assert_eq!(rich_texts, 2);
+
+        // Selecting other split leaves the zoom
+        grid.toggle_zoom();
+        grid.select_prev_split();
+        assert!(!grid.is_zoomed());
+        assert_eq!(grid.current_index(), 0);
+        assert_eq!(grid.contexts()[1].val.dimension.width, 400.);
+
+        // Swap keeps the layout, only contexts change places
+        let first_width = grid.contexts()[0].val.dimension.width;
+        assert!(grid.swap_with_next());
+        assert_eq!(grid.current_index(), 1);
+        assert_eq!(grid.current().rich_text_id, 0);
+        assert_eq!(grid.contexts()[0].val.rich_text_id, 1);
+        assert_eq!(grid.contexts()[0].val.dimension.width, first_width);
+        assert_eq!(grid.contexts()[1].val.dimension.width, 400.);
+
+        assert!(grid.swap_with_prev());
+        assert_eq!(grid.current_index(), 0);
+        assert_eq!(grid.current().rich_text_id, 0);
+
+        let removed = grid.remove_current();
+        assert_eq!(removed.map(|context| context.rich_text_id), Some(0));
+        assert_eq!(grid.len(), 1);
+    }
+
+    #[test]
+    fn test_set_split_percentage() {
+        let margin = Delta {
+            x: 0.,
+            top_y: 0.,
+            bottom_y: 0.,
+        };
+
+        let context_dimension = ContextDimension::build(
+            600.0,
+            600.0,
+            SugarDimensions {
+                scale: 1.,
+                width: 10.,
+                height: 20.,
+            },
+            1.0,
+            Delta::<f32>::default(),
+        );
+
+        let create_context = |rich_text_id: usize| {
+            create_mock_context(
+                VoidListener {},
+                WindowId::from(0),
+                0,
+                rich_text_id,
+                context_dimension,
+            )
+        };
+
+        let mut grid =
+            ContextGrid::<VoidListener>::new(create_context(0), margin, [0., 0., 0., 0.]);
+
+        grid.split_right(create_context(1));
+        assert!(grid.set_split_percentage(0, false, 25));
+        assert_eq!(grid.current().dimension.width, 150.);
+        assert_eq!(grid.contexts()[0].val.dimension.width, 448.);
+
+        grid.split_down(create_context(2));
+        assert!(grid.set_split_percentage(1, true, 75));
+        assert_eq!(grid.current().dimension.height, 450.);
+        assert_eq!(grid.contexts()[1].val.dimension.height, 148.);
+
+        // Parent must be other split
+        assert!(!grid.set_split_percentage(2, true, 50));
+    }
+
+    #[test]
+    fn test_toggle_timestamps_gutter() {
+        let margin = Delta {
+            x: 0.,
+            top_y: 0.,
+            bottom_y: 0.,
+        };
+
+        let context_dimension = ContextDimension::build(
+            600.0,
+            600.0,
+            SugarDimensions {
+                scale: 1.,
+                width: 10.,
+                height: 20.,
+            },
+            1.0,
+            Delta::<f32>::default(),
+        );
+
+        let context = create_mock_context(
+            VoidListener {},
+            WindowId::from(0),
+            0,
+            0,
+            context_dimension,
+        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(context, margin, [0., 0., 0., 0.]);
+        assert_eq!(grid.current().dimension.columns, 60);
+
+        assert!(grid.toggle_timestamps());
+        assert_eq!(grid.current().dimension.gutter, GUTTER_COLUMNS);
+        assert_eq!(grid.current().dimension.columns, 60 - GUTTER_COLUMNS);
+        assert_eq!(
+            grid.current().terminal.lock().columns(),
+            60 - GUTTER_COLUMNS
+        );
+
+        // The gutter survives a resize of the window
+        grid.resize(800.0, 600.0);
+        assert_eq!(grid.current().dimension.columns, 80 - GUTTER_COLUMNS);
+
+        assert!(!grid.toggle_timestamps());
+        assert_eq!(grid.current().dimension.columns, 80);
+    }
+
+    #[test]
+    fn test_overlay_current_split() {
+        let margin = Delta {
+            x: 0.,
+            top_y: 0.,
+            bottom_y: 0.,
+        };
+
+        let context_dimension = ContextDimension::build(
+            600.0,
+            600.0,
+            SugarDimensions {
+                scale: 1.,
+                width: 10.,
+                height: 20.,
+            },
+            1.0,
+            Delta::<f32>::default(),
+        );
+
+        let create_context = |route_id: usize| {
+            create_mock_context(
+                VoidListener {},
+                WindowId::from(0),
+                route_id,
+                0,
+                context_dimension,
+            )
+        };
+
+        let mut grid =
+            ContextGrid::<VoidListener>::new(create_context(0), margin, [0., 0., 0., 0.]);
+        grid.overlay_current(create_context(1));
+        assert_eq!(grid.len(), 1);
+        assert_eq!(grid.current().route_id, 1);
+
+        // Only the overlay brings the split back
+        assert!(!grid.restore_overlaid(0));
+
+        grid.resize(800.0, 600.0);
+        assert!(grid.restore_overlaid(1));
+        assert_eq!(grid.current().route_id, 0);
+        assert_eq!(grid.current().dimension.columns, 80);
+        assert_eq!(grid.current().terminal.lock().columns(), 80);
+        assert!(!grid.restore_overlaid(1));
+
+        // Hidden split exited, the overlay has nothing to bring back
+        grid.overlay_current(create_context(2));
+        assert!(!grid.drop_overlaid(2));
+        assert!(grid.drop_overlaid(0));
+        assert!(!grid.restore_overlaid(2));
+        assert_eq!(grid.current().route_id, 2);
+    }
 }
@@ -1,4 +1,5 @@
 pub mod grid;
+pub mod monitor;
 pub mod renderable;
 pub mod title;
 
@@ -6,34 +7,56 @@ use crate::ansi::CursorShape;
 use crate::context::grid::ContextDimension;
 use crate::context::grid::ContextGrid;
 use crate::context::grid::Delta;
+use crate::context::grid::{SplitDirection, SplitSize};
 use crate::context::title::{
     create_title_extra_from_context, update_title, ContextManagerTitles,
 };
+use crate::crosswords::Mode;
 use crate::event::sync::FairMutex;
 use crate::event::RioEvent;
 use crate::ime::Ime;
 use crate::messenger::Messenger;
 use crate::performer::Machine;
+use crate::renderer::HighlightState;
+use monitor::MonitorAlert;
 use renderable::Cursor;
 use renderable::RenderableContent;
+use rio_backend::config::bell::Bell;
+use rio_backend::config::export::Export;
+use rio_backend::config::layout::{Layout, LayoutSplit};
+use rio_backend::config::monitor::{Monitor, MonitorAction};
+use rio_backend::config::record::Record;
+use rio_backend::config::scrollback_editor::{ScrollbackEditor, ScrollbackEditorTarget};
+use rio_backend::config::triggers::{Trigger, TriggerAction};
 use rio_backend::config::Shell;
+use rio_backend::crosswords::export::{ExportFormat, ExportStyle};
 use rio_backend::crosswords::{Crosswords, MIN_COLUMNS, MIN_LINES};
 use rio_backend::error::{RioError, RioErrorLevel, RioErrorType};
 use rio_backend::event::EventListener;
 use rio_backend::event::WindowId;
+use rio_backend::performer::player::{Player, PlayerMsg, Recording};
+use rio_backend::performer::recorder::Recorder;
 use rio_backend::selection::SelectionRange;
 use rio_backend::sugarloaf::{font::SugarloafFont, Object, SugarloafErrors};
 use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
-use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 #[cfg(target_os = "windows")]
 use teletypewriter::create_pty;
 #[cfg(not(target_os = "windows"))]
-use teletypewriter::{create_pty_with_fork, create_pty_with_spawn};
+use teletypewriter::{create_pty_with_fork_and_env, create_pty_with_spawn_and_env};
 
 const DEFAULT_CONTEXT_CAPACITY: usize = 28;
+const BROADCAST_TITLE_INDICATOR: &str = "[broadcast] ";
+const RECORDING_TITLE_INDICATOR: &str = "[rec] ";
+const BELL_TITLE_INDICATOR: &str = "[bell] ";
 
 pub struct Context<T: EventListener> {
     pub route_id: usize,
@@ -47,16 +70,42 @@ pub struct Context<T: EventListener> {
     pub rich_text_id: usize,
     pub dimension: ContextDimension,
     pub ime: Ime,
+    /// Path of the session recording, while it's active.
+    pub recording: Option<PathBuf>,
+    /// Controls of the recording being played instead of a shell.
+    pub playback: Option<mpsc::Sender<PlayerMsg>>,
+    /// Whether the recording being played sets the terminal size, which
+    /// then doesn't follow the split size.
+    pub playback_sized: bool,
+    /// Last bell that wasn't dropped by the rate limit.
+    pub last_bell: Option<Instant>,
+    pub highlight: HighlightState,
+    /// Temporary file opened by the scrollback editor, removed along
+    /// with the context.
+    pub scrollback_file: Option<PathBuf>,
 }
 
 impl<T: rio_backend::event::EventListener> Drop for Context<T> {
     fn drop(&mut self) {
+        self.send_playback(PlayerMsg::Shutdown);
+
         #[cfg(not(target_os = "windows"))]
         teletypewriter::kill_pid(self.shell_pid as i32);
+
+        if let Some(path) = &self.scrollback_file {
+            let _ = std::fs::remove_file(path);
+        }
     }
 }
 
 impl<T: EventListener> Context<T> {
+    #[inline]
+    pub fn send_playback(&self, msg: PlayerMsg) {
+        if let Some(playback) = &self.playback {
+            let _ = playback.send(msg);
+        }
+    }
+
     #[inline]
     pub fn set_selection(&mut self, selection_range: Option<SelectionRange>) {
         let has_updated = (self.renderable_content.selection_range.is_none()
@@ -127,12 +176,42 @@ pub struct ContextManagerConfig {
     #[cfg(not(target_os = "windows"))]
     pub use_fork: bool,
     pub working_dir: Option<String>,
+    /// Variables added to the environment of the spawned shells.
+    #[cfg(not(target_os = "windows"))]
+    pub env: Vec<(String, String)>,
     pub spawn_performer: bool,
     pub use_current_path: bool,
     pub is_native: bool,
     pub should_update_title_extra: bool,
     pub split_color: [f32; 4],
+    pub broadcast_color: [f32; 4],
     pub title: rio_backend::config::title::Title,
+    pub layouts: HashMap<String, Layout>,
+    pub record: Record,
+    pub export: Export,
+    pub export_style: ExportStyle,
+    pub triggers: Vec<Trigger>,
+    /// Whether the `[highlight]` rules are applied to new splits.
+    pub highlight: bool,
+    pub copy_timestamps: bool,
+    pub monitor: Monitor,
+    /// Shared with the performers of the window, which only report their
+    /// output while a monitor is enabled.
+    pub monitor_output: Arc<AtomicBool>,
+    pub bell: Bell,
+    pub editor: Shell,
+    pub scrollback_editor: ScrollbackEditor,
+}
+
+/// Panes that also receive the input written to the current pane.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BroadcastScope {
+    /// Every split of the current tab.
+    Tab,
+    /// Every split of every tab.
+    AllTabs,
+    /// Splits marked with `MarkSplitForBroadcast`.
+    Marked,
 }
 
 pub struct ContextManager<T: EventListener> {
@@ -146,6 +225,16 @@ pub struct ContextManager<T: EventListener> {
     window_id: WindowId,
     pub config: ContextManagerConfig,
     pub titles: ContextManagerTitles,
+    broadcast: Option<BroadcastScope>,
+    broadcast_marked: HashSet<usize>,
+    visual_bell: Option<VisualBell>,
+}
+
+/// Flash of the visual bell, it fades out along `[bell] visual-duration`.
+#[derive(Clone, Copy, Debug)]
+struct VisualBell {
+    route_id: usize,
+    start: Instant,
 }
 
 pub fn create_dead_context<T: rio_backend::event::EventListener>(
@@ -177,6 +266,44 @@ pub fn create_dead_context<T: rio_backend::event::EventListener>(
         rich_text_id,
         dimension,
         ime: Ime::new(),
+        recording: None,
+        playback: None,
+        playback_sized: false,
+        last_bell: None,
+        highlight: HighlightState::default(),
+        scrollback_file: None,
+    }
+}
+
+/// Creates a file in the temporary directory that only the user can
+/// read, its name is never reused.
+fn create_scrollback_file(extension: &str) -> std::io::Result<(PathBuf, File)> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut attempts = 0;
+    loop {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default();
+        let file_name = format!(
+            "rio-{}-{nanos:x}-scrollback.{extension}",
+            std::process::id()
+        );
+        let path = std::env::temp_dir().join(file_name);
+        match options.open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists && attempts < 16 => {
+                attempts += 1;
+            }
+            Err(err) => return Err(err),
+        }
     }
 }
 
@@ -238,6 +365,7 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
             route_id,
         );
         terminal.blinking_cursor = cursor_state.1;
+        terminal.set_triggers(&config.triggers);
         let terminal: Arc<FairMutex<Crosswords<T>>> = Arc::new(FairMutex::new(terminal));
 
         let pty;
@@ -245,8 +373,9 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
         {
             if config.use_fork {
                 tracing::info!("rio -> teletypewriter: create_pty_with_fork");
-                pty = match create_pty_with_fork(
+                pty = match create_pty_with_fork_and_env(
                     &Cow::Borrowed(&config.shell.program),
+                    &config.env,
                     cols,
                     rows,
                 ) {
@@ -258,10 +387,11 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
                 }
             } else {
                 tracing::info!("rio -> teletypewriter: create_pty_with_spawn");
-                pty = match create_pty_with_spawn(
+                pty = match create_pty_with_spawn_and_env(
                     &Cow::Borrowed(&config.shell.program),
                     config.shell.args.clone(),
                     &config.working_dir,
+                    &config.env,
                     cols,
                     rows,
                 ) {
@@ -302,7 +432,8 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
             event_proxy.clone(),
             window_id,
             route_id,
-        )?;
+        )?
+        .with_output_events(Arc::clone(&config.monitor_output));
         let channel = machine.channel();
         if config.spawn_performer {
             machine.spawn();
@@ -322,6 +453,12 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
             renderable_content: RenderableContent::new(cursor_state.0.clone()),
             dimension,
             ime: Ime::new(),
+            recording: None,
+            playback: None,
+            playback_sized: false,
+            last_bell: None,
+            highlight: HighlightState::new(config.highlight),
+            scrollback_file: None,
         })
     }
 
@@ -332,22 +469,41 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
         event_proxy: T,
         window_id: WindowId,
         route_id: usize,
-        rich_text_id: usize,
+        rich_text_ids: &[usize],
+        layout: Option<&Layout>,
         ctx_config: ContextManagerConfig,
         size: ContextDimension,
         margin: Delta<f32>,
         sugarloaf_errors: Option<SugarloafErrors>,
     ) -> Result<Self, Box<dyn Error>> {
-        let initial_context = match ContextManager::create_context(
-            cursor_state,
-            event_proxy.clone(),
-            window_id,
-            route_id,
-            rich_text_id,
-            size,
-            &ctx_config,
-        ) {
-            Ok(context) => context,
+        // The layout takes the place of the first tab, with a rich text
+        // for each pane
+        let initial_grid = match layout {
+            Some(layout) => ContextManager::create_layout_grid(
+                layout,
+                cursor_state,
+                event_proxy.clone(),
+                window_id,
+                route_id,
+                rich_text_ids,
+                size,
+                margin,
+                &ctx_config,
+            ),
+            None => ContextManager::create_context(
+                cursor_state,
+                event_proxy.clone(),
+                window_id,
+                route_id,
+                rich_text_ids[0],
+                size,
+                &ctx_config,
+            )
+            .map(|context| ContextGrid::new(context, margin, ctx_config.split_color)),
+        };
+
+        let initial_grid = match initial_grid {
+            Ok(grid) => grid,
             Err(err_message) => {
                 tracing::error!("{:?}", err_message);
 
@@ -361,12 +517,16 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
                     window_id,
                 );
 
-                create_dead_context(
-                    event_proxy.clone(),
-                    window_id,
-                    route_id,
-                    0,
-                    ContextDimension::default(),
+                ContextGrid::new(
+                    create_dead_context(
+                        event_proxy.clone(),
+                        window_id,
+                        route_id,
+                        0,
+                        ContextDimension::default(),
+                    ),
+                    margin,
+                    ctx_config.split_color,
                 )
             }
         };
@@ -388,21 +548,23 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
             }
         }
 
-        Ok(ContextManager {
+        let context_manager = ContextManager {
             current_index: 0,
             current_route: 0,
-            acc_current_route: 0,
-            contexts: vec![ContextGrid::new(
-                initial_context,
-                margin,
-                ctx_config.split_color,
-            )],
+            acc_current_route: route_id + initial_grid.len() - 1,
+            contexts: vec![initial_grid],
             capacity: DEFAULT_CONTEXT_CAPACITY,
             event_proxy,
             window_id,
             config: ctx_config,
             titles,
-        })
+            broadcast: None,
+            broadcast_marked: HashSet::new(),
+            visual_bell: None,
+        };
+        context_manager.update_monitor_output();
+
+        Ok(context_manager)
     }
 
     #[cfg(test)]
@@ -451,12 +613,17 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
             window_id,
             config,
             titles,
+            broadcast: None,
+            broadcast_marked: HashSet::new(),
+            visual_bell: None,
         })
     }
 
     #[inline]
     pub fn should_close_context_manager(&mut self, route_id: usize) -> bool {
         let requires_change_route = self.current_route == route_id;
+        // Every closed split ends up here, route ids aren't reused
+        self.broadcast_marked.remove(&route_id);
 
         // should_close_context_manager is only called when terminal.exit()
         // is triggered. The terminal.exit() happens for any drop on context
@@ -469,6 +636,26 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
         // it means we need to clean the context with the specified route_id.
         // If there's no context then should return true and kill the window.
         if !self.contexts.is_empty() {
+            // Split hidden by an editor exited, there's nothing to bring back
+            if self
+                .contexts
+                .iter_mut()
+                .any(|grid| grid.drop_overlaid(route_id))
+            {
+                return false;
+            }
+
+            // Editor opened over a split, the split comes back
+            if self
+                .contexts
+                .iter_mut()
+                .any(|grid| grid.restore_overlaid(route_id))
+            {
+                self.current_route = self.current().route_id;
+                self.mark_pending_updates();
+                return false;
+            }
+
             // In case Grid has more than one item
             if self.current_grid().len() > 1 {
                 if self.current().route_id == route_id {
@@ -541,6 +728,12 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
             .send_event(RioEvent::CreateWindow, self.window_id);
     }
 
+    #[inline]
+    pub fn open_command_palette(&self) {
+        self.event_proxy
+            .send_event(RioEvent::CommandPalette, self.window_id);
+    }
+
     #[inline]
     pub fn close_unfocused_tabs(&mut self) {
         let current_route_id = self.current().route_id;
@@ -568,6 +761,654 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
         self.current_route = self.current().route_id;
     }
 
+    #[inline]
+    pub fn select_split(&mut self, direction: SplitDirection) {
+        self.contexts[self.current_index].select_split(direction);
+        self.current_route = self.current().route_id;
+    }
+
+    #[inline]
+    pub fn resize_split(&mut self, direction: SplitDirection, size: SplitSize) {
+        self.contexts[self.current_index].resize_split(direction, size);
+    }
+
+    #[inline]
+    pub fn equalize_splits(&mut self) {
+        self.contexts[self.current_index].equalize_splits();
+    }
+
+    #[inline]
+    pub fn toggle_split_zoom(&mut self) -> bool {
+        self.contexts[self.current_index].toggle_zoom()
+    }
+
+    #[inline]
+    pub fn toggle_timestamps(&mut self) -> bool {
+        self.contexts[self.current_index].toggle_timestamps()
+    }
+
+    #[inline]
+    pub fn swap_split_next(&mut self) {
+        self.contexts[self.current_index].swap_with_next();
+        self.current_route = self.current().route_id;
+    }
+
+    #[inline]
+    pub fn swap_split_prev(&mut self) {
+        self.contexts[self.current_index].swap_with_prev();
+        self.current_route = self.current().route_id;
+    }
+
+    /// Moves the current split to a new tab, the context is moved as it is
+    /// so the process, the terminal state and rich text are kept.
+    pub fn move_split_to_new_tab(&mut self) -> bool {
+        if self.config.is_native
+            || self.contexts.len() >= self.capacity
+            || self.current_grid_len() <= 1
+        {
+            return false;
+        }
+
+        let grid = &mut self.contexts[self.current_index];
+        let (width, height, margin) = (grid.width, grid.height, grid.margin);
+        let mut context = match grid.remove_current() {
+            Some(context) => context,
+            None => return false,
+        };
+
+        context.dimension.update_margin(margin);
+        context.dimension.update_width(width);
+        context.dimension.update_height(height);
+        context.renderable_content.mark_pending_updates();
+
+        let mut new_grid = ContextGrid::new(context, margin, self.config.split_color);
+        new_grid.request_resize(0);
+        self.contexts.push(new_grid);
+        self.set_current(self.contexts.len() - 1);
+        true
+    }
+
+    /// Moves the current split into the tab in `tab_index` as a split,
+    /// if the current tab has only this split then the tab is closed.
+    pub fn move_split_to_tab(&mut self, tab_index: usize) -> bool {
+        if tab_index == self.current_index || tab_index >= self.contexts.len() {
+            return false;
+        }
+
+        let mut target_index = tab_index;
+        let mut context = if self.current_grid_len() > 1 {
+            match self.contexts[self.current_index].remove_current() {
+                Some(context) => context,
+                None => return false,
+            }
+        } else {
+            let index_to_remove = self.current_index;
+            self.titles.titles.remove(&index_to_remove);
+            if target_index > index_to_remove {
+                target_index -= 1;
+            }
+            self.contexts.remove(index_to_remove).into_current()
+        };
+
+        let target_grid = &mut self.contexts[target_index];
+        context
+            .dimension
+            .update_margin(target_grid.current().dimension.margin);
+        context.renderable_content.mark_pending_updates();
+        target_grid.split_right(context);
+
+        self.set_current(target_index);
+        true
+    }
+
+    /// Turns broadcast on for `scope`, or off in case it's already
+    /// broadcasting to the same scope. Returns if broadcast is active.
+    pub fn toggle_broadcast(&mut self, scope: BroadcastScope) -> bool {
+        if self.broadcast == Some(scope) {
+            self.broadcast = None;
+        } else {
+            self.broadcast = Some(scope);
+        }
+
+        // Titles carry the broadcast indicator so it can't wait the interval
+        self.titles.last_title_update = None;
+        self.broadcast.is_some()
+    }
+
+    /// Marks or unmarks the current split to receive broadcast input.
+    pub fn toggle_broadcast_mark(&mut self) {
+        let route_id = self.current().route_id;
+        if !self.broadcast_marked.remove(&route_id) {
+            self.broadcast_marked.insert(route_id);
+        }
+
+        self.titles.last_title_update = None;
+    }
+
+    #[inline]
+    fn is_broadcast_target(&self, tab_index: usize, route_id: usize) -> bool {
+        match self.broadcast {
+            Some(BroadcastScope::Tab) => tab_index == self.current_index,
+            Some(BroadcastScope::AllTabs) => true,
+            Some(BroadcastScope::Marked) => self.broadcast_marked.contains(&route_id),
+            None => false,
+        }
+    }
+
+    #[inline]
+    fn has_broadcast_target(&self, tab_index: usize) -> bool {
+        self.broadcast.is_some()
+            && self.contexts[tab_index]
+                .contexts()
+                .iter()
+                .any(|item| self.is_broadcast_target(tab_index, item.context().route_id))
+    }
+
+    /// Writes input to the current split and, while broadcasting,
+    /// to every other split targeted by the broadcast scope.
+    pub fn send_input<B: Into<Cow<'static, [u8]>>>(&self, data: B) {
+        let bytes = data.into();
+        self.send_encoded_input(|_| bytes.clone());
+    }
+
+    /// Like `send_input` but the input is encoded with the mode of each
+    /// split, since broadcast targets can have other keyboard or paste
+    /// modes than the current split. Empty input isn't written.
+    pub fn send_encoded_input<F>(&self, encode: F)
+    where
+        F: Fn(Mode) -> Cow<'static, [u8]>,
+    {
+        // Playback is read-only, input shouldn't reach the other splits either
+        if self.current().playback.is_some() {
+            return;
+        }
+
+        let write = |context: &Context<T>| {
+            let mode = context.terminal.lock().mode();
+            let bytes = encode(mode);
+            if !bytes.is_empty() {
+                context.messenger.send_write(bytes);
+            }
+        };
+
+        let current_route_id = self.current().route_id;
+        if self.broadcast.is_some() {
+            for (tab_index, grid) in self.contexts.iter().enumerate() {
+                for item in grid.contexts() {
+                    let context = item.context();
+                    if context.route_id != current_route_id
+                        && self.is_broadcast_target(tab_index, context.route_id)
+                    {
+                        write(context);
+                    }
+                }
+            }
+        }
+
+        write(self.current());
+    }
+
+    /// Replaces the current split by a read-only terminal replaying `recording`.
+    pub fn start_playback(&mut self, recording: Recording) {
+        let current = self.current();
+        let route_id = current.route_id;
+        let mut context = create_dead_context(
+            self.event_proxy.clone(),
+            self.window_id,
+            route_id,
+            current.rich_text_id,
+            current.dimension,
+        );
+
+        context.playback_sized = recording.size().is_some()
+            || recording.frames.iter().any(|frame| frame.resize.is_some());
+        let player = Player::new(
+            recording,
+            Arc::clone(&context.terminal),
+            self.event_proxy.clone(),
+            self.window_id,
+            route_id,
+        );
+        context.playback = Some(player.channel());
+        if self.config.spawn_performer {
+            player.spawn();
+        }
+
+        *self.current_mut() = context;
+    }
+
+    /// Starts recording the current split into `path` (asciicast v2).
+    pub fn start_recording(&mut self, path: PathBuf) -> bool {
+        let record_input = self.config.record.input;
+        let context = self.current_mut();
+        let recorder = match Recorder::create(
+            &path,
+            context.dimension.columns,
+            context.dimension.lines,
+            record_input,
+        ) {
+            Ok(recorder) => recorder,
+            Err(err) => {
+                tracing::error!("unable to record session to {path:?}: {err}");
+                return false;
+            }
+        };
+
+        context.messenger.send_start_recording(recorder);
+        context.recording = Some(path);
+        self.titles.last_title_update = None;
+        true
+    }
+
+    /// Stops the recording of the current split, or starts a new one
+    /// in the record directory. Returns if the split is recording.
+    pub fn toggle_recording(&mut self) -> bool {
+        let context = self.current_mut();
+        if context.recording.take().is_some() {
+            context.messenger.send_stop_recording();
+            self.titles.last_title_update = None;
+            return false;
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let file_name = format!("rio-{}-{}.cast", timestamp, self.current().route_id);
+        let path = self.config.record.directory().join(file_name);
+        self.start_recording(path)
+    }
+
+    /// Writes the content of the current split to `path`.
+    pub fn export(
+        &self,
+        path: &Path,
+        format: ExportFormat,
+        scrollback: bool,
+    ) -> std::io::Result<()> {
+        let content = self.current().terminal.lock().export(
+            format,
+            scrollback,
+            &self.config.export_style,
+        );
+        std::fs::write(path, content)
+    }
+
+    /// Saves the screen (or the scrollback) of the current split in the
+    /// export directory, with the configured format if none is given.
+    pub fn save_screen(&self, format: Option<ExportFormat>, scrollback: bool) -> bool {
+        let format = format.unwrap_or(self.config.export.format);
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let file_name = format!(
+            "rio-{}-{}-{}.{}",
+            timestamp,
+            self.current().route_id,
+            if scrollback { "scrollback" } else { "screen" },
+            format.extension()
+        );
+        let path = self.config.export.directory().join(file_name);
+        match self.export(&path, format, scrollback) {
+            Ok(()) => {
+                tracing::info!("saved {}", path.display());
+                true
+            }
+            Err(err) => {
+                tracing::error!("unable to save {path:?}: {err}");
+                false
+            }
+        }
+    }
+
+    /// Writes the scrollback of the current split to a temporary file and
+    /// opens it with the scrollback editor, `create_rich_text` is only
+    /// called once the editor runs in a new split or tab.
+    pub fn open_scrollback_in_editor(
+        &mut self,
+        create_rich_text: impl FnOnce() -> usize,
+    ) -> bool {
+        let scrollback_editor = &self.config.scrollback_editor;
+        let format = if scrollback_editor.ansi {
+            ExportFormat::Ansi
+        } else {
+            ExportFormat::Text
+        };
+
+        let target = scrollback_editor.open_in;
+        if target == ScrollbackEditorTarget::Tab
+            && (self.config.is_native || self.contexts.len() >= self.capacity)
+        {
+            return false;
+        }
+
+        let content = self.current().terminal.lock().export(
+            format,
+            true,
+            &self.config.export_style,
+        );
+        let path = match create_scrollback_file(format.extension()).and_then(
+            |(path, mut file)| {
+                file.write_all(content.as_bytes())?;
+                Ok(path)
+            },
+        ) {
+            Ok(path) => path,
+            Err(err) => {
+                tracing::error!("unable to save the scrollback: {err}");
+                return false;
+            }
+        };
+
+        let mut config = self.config.clone();
+        config.shell =
+            scrollback_editor.shell(&self.config.editor, &path.display().to_string());
+        config.spawn_performer = true;
+        // Fork does not support custom programs
+        #[cfg(not(target_os = "windows"))]
+        {
+            config.use_fork = false;
+        }
+
+        let dimension = match target {
+            ScrollbackEditorTarget::Tab => self.current_grid().grid_dimension(),
+            _ => self.current().dimension,
+        };
+
+        self.acc_current_route += 1;
+        let current = self.current();
+        let cursor = current.cursor_from_ref();
+        let mut context = match ContextManager::create_context(
+            (&cursor, current.renderable_content.has_blinking_enabled),
+            self.event_proxy.clone(),
+            self.window_id,
+            self.acc_current_route,
+            current.rich_text_id,
+            dimension,
+            &config,
+        ) {
+            Ok(context) => context,
+            Err(..) => {
+                tracing::error!("not able to create a new context");
+                let _ = std::fs::remove_file(&path);
+                return false;
+            }
+        };
+        context.scrollback_file = Some(path);
+
+        // Rich texts can't be removed, so only created once the editor runs
+        if target != ScrollbackEditorTarget::Overlay {
+            context.rich_text_id = create_rich_text();
+        }
+
+        match target {
+            ScrollbackEditorTarget::Overlay => {
+                self.contexts[self.current_index].overlay_current(context);
+            }
+            ScrollbackEditorTarget::Split => {
+                self.contexts[self.current_index].split_right(context);
+            }
+            ScrollbackEditorTarget::Tab => {
+                let margin = self.contexts[self.current_index].margin;
+                self.contexts.push(ContextGrid::new(
+                    context,
+                    margin,
+                    self.config.split_color,
+                ));
+                self.current_index = self.contexts.len() - 1;
+            }
+        }
+
+        self.current_route = self.acc_current_route;
+        true
+    }
+
+    /// Forces every split to be fully rendered again.
+    pub fn mark_pending_updates(&mut self) {
+        for grid in self.contexts.iter_mut() {
+            for item in grid.contexts_mut() {
+                item.context_mut().renderable_content.mark_pending_updates();
+            }
+        }
+    }
+
+    /// Runs the action of a trigger that matched the output of `route_id`.
+    pub fn run_trigger(&self, route_id: usize, action: TriggerAction) {
+        let context = match self
+            .contexts
+            .iter()
+            .flat_map(|grid| grid.contexts())
+            .map(|item| item.context())
+            .find(|context| context.route_id == route_id)
+        {
+            Some(context) => context,
+            None => return,
+        };
+
+        match action {
+            TriggerAction::SendText { text } => {
+                context.messenger.send_write(text.into_bytes());
+            }
+            TriggerAction::Run { program, args } => spawn(context, &program, &args),
+            TriggerAction::Notify { title, message } => {
+                let title = title.unwrap_or_else(|| String::from("Rio"));
+                let message = message.unwrap_or_default();
+                match crate::platform::notification_command(&title, &message) {
+                    Some((program, args)) => spawn(context, &program, &args),
+                    None => tracing::warn!("desktop notifications are not supported"),
+                }
+            }
+            TriggerAction::Highlight { .. } | TriggerAction::Bell => {}
+        }
+    }
+
+    /// Bell rang by the program of `route_id`. Bells of splits that aren't
+    /// focused are rate limited, returns if the bell should be signaled.
+    pub fn bell(&mut self, route_id: usize, window_focused: bool) -> bool {
+        let Some(tab_index) = self.tab_index_by_route(route_id) else {
+            return false;
+        };
+
+        let focused = window_focused && self.current_route == route_id;
+        let interval = Duration::from_millis(self.config.bell.unfocused_interval);
+        let Some(context) = self.contexts[tab_index]
+            .contexts_mut()
+            .iter_mut()
+            .map(|item| item.context_mut())
+            .find(|context| context.route_id == route_id)
+        else {
+            return false;
+        };
+
+        if !focused
+            && context
+                .last_bell
+                .is_some_and(|last_bell| last_bell.elapsed() < interval)
+        {
+            return false;
+        }
+        context.last_bell = Some(Instant::now());
+
+        let grid = &mut self.contexts[tab_index];
+        if self.config.bell.indicator && tab_index != self.current_index && !grid.bell {
+            grid.bell = true;
+            self.titles.last_title_update = None;
+            self.update_titles();
+        }
+
+        self.ring_bell(route_id);
+        true
+    }
+
+    /// Starts the visual bell and runs the bell command for `route_id`.
+    pub fn ring_bell(&mut self, route_id: usize) {
+        if self.config.bell.visual {
+            self.visual_bell = Some(VisualBell {
+                route_id,
+                start: Instant::now(),
+            });
+        }
+
+        if let Some(command) = &self.config.bell.command {
+            if let Some(context) = self
+                .contexts
+                .iter()
+                .flat_map(|grid| grid.contexts())
+                .map(|item| item.context())
+                .find(|context| context.route_id == route_id)
+            {
+                spawn(context, &command.program, &command.args);
+            }
+        }
+    }
+
+    /// Whether the visual bell is still fading, frames need to be
+    /// requested until it's over.
+    #[inline]
+    pub fn has_visual_bell(&self) -> bool {
+        self.visual_bell_color().is_some()
+    }
+
+    /// Color of the visual bell at this moment.
+    fn visual_bell_color(&self) -> Option<[f32; 4]> {
+        let visual_bell = self.visual_bell?;
+        let duration = self.config.bell.visual_duration as f32;
+        let elapsed = visual_bell.start.elapsed().as_millis() as f32;
+        if elapsed >= duration {
+            return None;
+        }
+
+        let mut color = self.config.bell.visual_color;
+        color[3] *= 1.0 - elapsed / duration;
+        Some(color)
+    }
+
+    /// Whether a background tab rang the bell since it was last selected.
+    #[inline]
+    pub fn tab_bell(&self, tab_index: usize) -> bool {
+        self.contexts.get(tab_index).is_some_and(|grid| grid.bell)
+    }
+
+    #[inline]
+    fn tab_index_by_route(&self, route_id: usize) -> Option<usize> {
+        self.contexts.iter().position(|grid| {
+            grid.contexts()
+                .iter()
+                .any(|item| item.context().route_id == route_id)
+        })
+    }
+
+    /// New output of `route_id`, runs the activity monitor of its tab and
+    /// returns the interval of the silence monitor to (re)start. Tabs of
+    /// unfocused windows are in background as well.
+    pub fn monitor_output(
+        &mut self,
+        route_id: usize,
+        is_focused: bool,
+    ) -> Option<Duration> {
+        let index = self.tab_index_by_route(route_id)?;
+        let background = index != self.current_index || !is_focused;
+        let monitor = &mut self.contexts[index].monitor;
+        if let Some(alert) = monitor.output(&self.config.monitor, background) {
+            self.run_monitor_alert(index, alert);
+        }
+
+        self.contexts[index]
+            .monitor
+            .silence_interval(&self.config.monitor)
+    }
+
+    /// Silence timer of `route_id` expired.
+    pub fn monitor_silence(&mut self, route_id: usize, is_focused: bool) {
+        if let Some(index) = self.tab_index_by_route(route_id) {
+            let background = index != self.current_index || !is_focused;
+            let monitor = &mut self.contexts[index].monitor;
+            if let Some(alert) = monitor.silence(&self.config.monitor, background) {
+                self.run_monitor_alert(index, alert);
+            }
+        }
+    }
+
+    /// Watches (or stops watching) new output of the current tab.
+    pub fn toggle_monitor_activity(&mut self) -> bool {
+        let enabled = self.contexts[self.current_index]
+            .monitor
+            .toggle_activity(&self.config.monitor);
+        self.update_monitor_output();
+        enabled
+    }
+
+    /// Watches (or stops watching) silence of the current tab.
+    pub fn toggle_monitor_silence(&mut self) -> bool {
+        let enabled = self.contexts[self.current_index]
+            .monitor
+            .toggle_silence(&self.config.monitor);
+        self.update_monitor_output();
+        enabled
+    }
+
+    /// The performers only report their output while a monitor is enabled,
+    /// by the config or in any of the tabs.
+    pub fn update_monitor_output(&self) {
+        let config = &self.config.monitor;
+        let enabled = self.contexts.iter().any(|grid| {
+            grid.monitor.has_activity(config) || grid.monitor.has_silence(config)
+        });
+        self.config.monitor_output.store(enabled, Ordering::Relaxed);
+    }
+
+    fn run_monitor_alert(&mut self, tab_index: usize, alert: MonitorAlert) {
+        for action in self.config.monitor.actions.clone() {
+            match action {
+                MonitorAction::Mark => {
+                    self.titles.last_title_update = None;
+                    self.update_titles();
+                    // Background tabs don't redraw the navigation on output
+                    self.event_proxy
+                        .send_event(RioEvent::Render, self.window_id);
+                }
+                MonitorAction::Bell => {
+                    self.event_proxy.send_event(RioEvent::Bell, self.window_id);
+                }
+                MonitorAction::Notify => {
+                    let tab = self
+                        .titles
+                        .titles
+                        .get(&tab_index)
+                        .map(|title| title.content.to_owned())
+                        .unwrap_or_else(|| format!("tab {}", tab_index + 1));
+                    let message = format!("{} in {}", alert.message(), tab);
+                    let context = self.contexts[tab_index].current();
+                    match crate::platform::notification_command("Rio", &message) {
+                        Some((program, args)) => spawn(context, &program, &args),
+                        None => tracing::warn!("desktop notifications are not supported"),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Alert of a tab to be shown in the navigation.
+    #[inline]
+    pub fn tab_alert(&self, tab_index: usize) -> Option<MonitorAlert> {
+        if !self.config.monitor.actions.contains(&MonitorAction::Mark) {
+            return None;
+        }
+
+        self.contexts
+            .get(tab_index)
+            .and_then(|grid| grid.monitor.alert)
+    }
+
+    #[inline]
+    fn has_recording(&self, tab_index: usize) -> bool {
+        self.contexts[tab_index]
+            .contexts()
+            .iter()
+            .any(|item| item.context().recording.is_some())
+    }
+
     #[inline]
     pub fn select_tab(&mut self, tab_index: usize) {
         if self.config.is_native {
@@ -632,7 +1473,32 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
 
     #[inline]
     pub fn grid_objects(&self) -> Vec<Object> {
-        self.contexts[self.current_index].objects()
+        let grid = &self.contexts[self.current_index];
+        let mut objects = grid.objects();
+        if self.has_broadcast_target(self.current_index) {
+            let targets: Vec<usize> = grid
+                .contexts()
+                .iter()
+                .map(|item| item.context())
+                .filter(|context| {
+                    self.is_broadcast_target(self.current_index, context.route_id)
+                })
+                .map(|context| context.rich_text_id)
+                .collect();
+            grid.plot_highlight(&mut objects, &targets, self.config.broadcast_color);
+        }
+        if let (Some(visual_bell), Some(color)) =
+            (self.visual_bell, self.visual_bell_color())
+        {
+            if let Some(item) = grid
+                .contexts()
+                .iter()
+                .find(|item| item.context().route_id == visual_bell.route_id)
+            {
+                grid.plot_fill(&mut objects, item.context().rich_text_id, color);
+            }
+        }
+        objects
     }
 
     #[inline]
@@ -650,8 +1516,21 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
         {
             self.titles.last_title_update = Some(Instant::now());
             let mut id = String::default();
-            for (i, context) in self.contexts.iter_mut().enumerate() {
-                let content = update_title(&self.config.title.content, context.current());
+            for (i, context) in self.contexts.iter().enumerate() {
+                let mut content =
+                    update_title(&self.config.title.content, context.current());
+                if self.has_broadcast_target(i) {
+                    content.insert_str(0, BROADCAST_TITLE_INDICATOR);
+                }
+                if self.has_recording(i) {
+                    content.insert_str(0, RECORDING_TITLE_INDICATOR);
+                }
+                if self.tab_bell(i) {
+                    content.insert_str(0, BELL_TITLE_INDICATOR);
+                }
+                if let Some(alert) = self.tab_alert(i) {
+                    content.insert_str(0, alert.indicator());
+                }
 
                 self.event_proxy
                     .send_event(RioEvent::Title(content.to_owned()), self.window_id);
@@ -684,6 +1563,18 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
     }
 
     #[inline]
+    /// Closes the editor opened over the current split, which comes back.
+    /// Returns false if there's no editor over it.
+    pub fn restore_current_overlaid(&mut self) -> bool {
+        let route_id = self.current_route;
+        if !self.contexts[self.current_index].restore_overlaid(route_id) {
+            return false;
+        }
+
+        self.current_route = self.current().route_id;
+        true
+    }
+
     pub fn remove_current_grid(&mut self) {
         self.contexts[self.current_index].remove_current();
         self.current_route = self.contexts[self.current_index].current().route_id;
@@ -709,6 +1600,13 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
         if context_id < self.contexts.len() {
             self.current_index = context_id;
             self.current_route = self.current().route_id;
+
+            let grid = &mut self.contexts[context_id];
+            let had_bell = std::mem::take(&mut grid.bell);
+            if grid.monitor.seen() || had_bell {
+                self.titles.last_title_update = None;
+                self.update_titles();
+            }
         }
     }
 
@@ -891,10 +1789,13 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
             None,
         );
 
+        let export_style = ExportStyle::from(&config);
         let context_manager_config = ContextManagerConfig {
             use_current_path: config.navigation.use_current_path,
             shell,
             working_dir,
+            #[cfg(not(target_os = "windows"))]
+            env: self.config.env.clone(),
             spawn_performer: true,
             #[cfg(not(target_os = "windows"))]
             use_fork: config.use_fork,
@@ -903,7 +1804,20 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
             // does not make sense fetch for foreground process names
             should_update_title_extra: !config.navigation.color_automation.is_empty(),
             split_color: config.colors.split,
+            broadcast_color: config.colors.broadcast,
             title: config.title,
+            layouts: config.layouts,
+            record: config.record,
+            export: config.export,
+            export_style,
+            triggers: config.triggers,
+            highlight: config.highlight.enabled,
+            copy_timestamps: config.timestamps.copy,
+            monitor: config.monitor,
+            monitor_output: Arc::clone(&self.config.monitor_output),
+            bell: config.bell,
+            editor: config.editor,
+            scrollback_editor: config.scrollback_editor,
         };
 
         self.acc_current_route += 1;
@@ -934,6 +1848,144 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
         }
     }
 
+    /// Number of panes of the layout if it can be opened as a new tab.
+    pub fn layout_panes(&self, name: &str) -> Option<usize> {
+        let panes = match self.config.layouts.get(name) {
+            Some(layout) if !layout.panes.is_empty() => layout.panes.len(),
+            _ => {
+                tracing::warn!("layout {name} not found");
+                return None;
+            }
+        };
+
+        if self.config.is_native || self.contexts.len() >= self.capacity {
+            return None;
+        }
+        Some(panes)
+    }
+
+    /// Creates a tab from a layout declared in the configuration, each pane
+    /// uses one of `rich_text_ids`.
+    pub fn add_layout(&mut self, name: &str, rich_text_ids: &[usize]) -> bool {
+        if self
+            .layout_panes(name)
+            .map_or(true, |panes| rich_text_ids.len() < panes)
+        {
+            return false;
+        }
+        let layout = &self.config.layouts[name];
+
+        let current = self.current();
+        let cursor = current.cursor_from_ref();
+        let has_blinking_enabled = current.renderable_content.has_blinking_enabled;
+
+        let layout_grid = match ContextManager::create_layout_grid(
+            layout,
+            (&cursor, has_blinking_enabled),
+            self.event_proxy.clone(),
+            self.window_id,
+            self.acc_current_route + 1,
+            rich_text_ids,
+            self.current_grid().grid_dimension(),
+            self.current_grid().margin,
+            &self.config,
+        ) {
+            Ok(grid) => grid,
+            Err(..) => {
+                tracing::error!("not able to create a new context");
+                return false;
+            }
+        };
+
+        self.acc_current_route += layout_grid.len();
+        self.contexts.push(layout_grid);
+        self.current_index = self.contexts.len() - 1;
+        self.current_route = self.current().route_id;
+        true
+    }
+
+    /// Creates the grid of the layout panes, the pane in `index` takes
+    /// the route `route_id + index` and `rich_text_ids[index]`.
+    #[allow(clippy::too_many_arguments)]
+    fn create_layout_grid(
+        layout: &Layout,
+        cursor_state: (&Cursor, bool),
+        event_proxy: T,
+        window_id: WindowId,
+        route_id: usize,
+        rich_text_ids: &[usize],
+        dimension: ContextDimension,
+        margin: Delta<f32>,
+        ctx_config: &ContextManagerConfig,
+    ) -> Result<ContextGrid<T>, Box<dyn Error>> {
+        let mut layout_grid: Option<ContextGrid<T>> = None;
+        for (index, pane) in layout.panes.iter().enumerate() {
+            let mut config = ctx_config.clone();
+            // Fork does not support custom programs or working directories
+            if let Some(shell) = &pane.shell {
+                config.shell = shell.clone();
+                #[cfg(not(target_os = "windows"))]
+                {
+                    config.use_fork = false;
+                }
+            }
+
+            if let Some(working_dir) = &pane.working_dir {
+                config.working_dir = Some(working_dir.to_owned());
+                #[cfg(not(target_os = "windows"))]
+                {
+                    config.use_fork = false;
+                }
+            }
+
+            if let Some(highlight) = pane.highlight {
+                config.highlight = highlight;
+            }
+
+            let parent = pane.parent(index);
+            let pane_dimension = match &mut layout_grid {
+                Some(grid) => {
+                    grid.current = parent;
+                    grid.current().dimension
+                }
+                None => dimension,
+            };
+
+            let context = ContextManager::create_context(
+                cursor_state,
+                event_proxy.clone(),
+                window_id,
+                route_id + index,
+                rich_text_ids[index],
+                pane_dimension,
+                &config,
+            )?;
+
+            match &mut layout_grid {
+                Some(grid) => {
+                    let is_down = pane.split == LayoutSplit::Down;
+                    if is_down {
+                        grid.split_down(context);
+                    } else {
+                        grid.split_right(context);
+                    }
+
+                    if let Some(size) = pane.size {
+                        grid.set_split_percentage(parent, is_down, size);
+                    }
+                }
+                None => {
+                    layout_grid =
+                        Some(ContextGrid::new(context, margin, ctx_config.split_color));
+                }
+            }
+        }
+
+        let mut layout_grid = layout_grid.ok_or("layout without panes")?;
+        layout_grid.current = 0;
+        Ok(layout_grid)
+    }
+
     #[inline]
     pub fn add_context(&mut self, redirect: bool, rich_text_id: usize) {
         let mut working_dir = self.config.working_dir.clone();
@@ -1006,6 +2058,23 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
     }
 }
 
+/// Launches `program` in the background from the directory of `context`.
+fn spawn<T: EventListener>(context: &Context<T>, program: &str, args: &[String]) {
+    #[cfg(not(target_os = "windows"))]
+    let result =
+        teletypewriter::spawn_daemon(program, args, *context.main_fd, context.shell_pid);
+    #[cfg(target_os = "windows")]
+    let result = {
+        let _ = context;
+        teletypewriter::spawn_daemon(program, args)
+    };
+
+    match result {
+        Ok(_) => tracing::debug!("Launched {} with args {:?}", program, args),
+        Err(_) => tracing::warn!("Unable to launch {} with args {:?}", program, args),
+    }
+}
+
 pub fn process_open_url(
     mut shell: Shell,
     mut working_dir: Option<String>,
@@ -1312,4 +2381,256 @@ pub mod test {
         assert_eq!(context_manager.current_index, 4);
         assert_eq!(context_manager.current().rich_text_id, 1);
     }
+
+    #[test]
+    fn test_move_split_to_tab() {
+        let window_id = WindowId::from(0);
+
+        let mut context_manager =
+            ContextManager::start_with_capacity(5, VoidListener {}, window_id).unwrap();
+        let should_redirect = false;
+
+        context_manager.current_mut().rich_text_id = 1;
+        context_manager.add_context(should_redirect, 2);
+        assert_eq!(context_manager.len(), 2);
+
+        // Single split in the tab can't move to a new tab
+        assert!(!context_manager.move_split_to_new_tab());
+
+        context_manager.split(3, false);
+        assert_eq!(context_manager.current_grid_len(), 2);
+        assert_eq!(context_manager.current().rich_text_id, 3);
+        let route_id = context_manager.current_route();
+
+        assert!(context_manager.move_split_to_new_tab());
+        assert_eq!(context_manager.len(), 3);
+        assert_eq!(context_manager.current_index(), 2);
+        assert_eq!(context_manager.current_grid_len(), 1);
+        assert_eq!(context_manager.current().rich_text_id, 3);
+        assert_eq!(context_manager.current_route(), route_id);
+        assert_eq!(context_manager.contexts[0].len(), 1);
+
+        // Moving the last split of a tab closes it
+        assert!(!context_manager.move_split_to_tab(2));
+        assert!(!context_manager.move_split_to_tab(10));
+        assert!(context_manager.move_split_to_tab(0));
+        assert_eq!(context_manager.len(), 2);
+        assert_eq!(context_manager.current_index(), 0);
+        assert_eq!(context_manager.current_grid_len(), 2);
+        assert_eq!(context_manager.current().rich_text_id, 3);
+        assert_eq!(context_manager.current_route(), route_id);
+
+        assert!(context_manager.move_split_to_tab(1));
+        assert_eq!(context_manager.current_index(), 1);
+        assert_eq!(context_manager.current_grid_len(), 2);
+        assert_eq!(context_manager.contexts[0].len(), 1);
+        assert_eq!(context_manager.contexts[0].current().rich_text_id, 1);
+    }
+
+    #[test]
+    fn test_broadcast_targets() {
+        let window_id = WindowId::from(0);
+
+        let mut context_manager =
+            ContextManager::start_with_capacity(5, VoidListener {}, window_id).unwrap();
+        let should_redirect = false;
+
+        context_manager.current_mut().rich_text_id = 1;
+        context_manager.split(2, false);
+        let marked_route = context_manager.current_route();
+        context_manager.add_context(should_redirect, 3);
+        assert_eq!(context_manager.current_index(), 0);
+
+        let objects_len = context_manager.grid_objects().len();
+        assert!(!context_manager.has_broadcast_target(0));
+
+        assert!(context_manager.toggle_broadcast(BroadcastScope::Tab));
+        assert!(context_manager.is_broadcast_target(0, marked_route));
+        assert!(context_manager.has_broadcast_target(0));
+        assert!(!context_manager.has_broadcast_target(1));
+        // Both splits of the current tab are framed
+        assert_eq!(context_manager.grid_objects().len(), objects_len + 8);
+
+        assert!(context_manager.toggle_broadcast(BroadcastScope::AllTabs));
+        assert!(context_manager.has_broadcast_target(1));
+
+        assert!(context_manager.toggle_broadcast(BroadcastScope::Marked));
+        assert!(!context_manager.has_broadcast_target(0));
+        context_manager.toggle_broadcast_mark();
+        assert!(context_manager.is_broadcast_target(0, marked_route));
+        assert!(!context_manager.has_broadcast_target(1));
+        assert_eq!(context_manager.grid_objects().len(), objects_len + 4);
+
+        context_manager.update_titles();
+        assert!(context_manager.titles.titles[&0]
+            .content
+            .starts_with(BROADCAST_TITLE_INDICATOR));
+        assert!(!context_manager.titles.titles[&1]
+            .content
+            .starts_with(BROADCAST_TITLE_INDICATOR));
+
+        // Unmark and turn it off
+        context_manager.toggle_broadcast_mark();
+        assert!(!context_manager.has_broadcast_target(0));
+        assert!(!context_manager.toggle_broadcast(BroadcastScope::Marked));
+        assert_eq!(context_manager.broadcast, None);
+        assert_eq!(context_manager.grid_objects().len(), objects_len);
+
+        // Closed splits are unmarked
+        context_manager.toggle_broadcast_mark();
+        assert!(!context_manager.should_close_context_manager(marked_route));
+        assert!(context_manager.broadcast_marked.is_empty());
+    }
+
+    #[test]
+    fn test_toggle_recording() {
+        let window_id = WindowId::from(0);
+
+        let mut context_manager =
+            ContextManager::start_with_capacity(5, VoidListener {}, window_id).unwrap();
+        let directory = std::env::temp_dir();
+        context_manager.config.record.directory =
+            Some(directory.to_string_lossy().to_string());
+        context_manager.add_context(false, 1);
+        assert_eq!(context_manager.current_index(), 0);
+
+        assert!(context_manager.toggle_recording());
+        let path = context_manager.current().recording.clone().unwrap();
+        assert!(path.starts_with(&directory));
+        assert!(context_manager.has_recording(0));
+        assert!(!context_manager.has_recording(1));
+
+        context_manager.update_titles();
+        assert!(context_manager.titles.titles[&0]
+            .content
+            .starts_with(RECORDING_TITLE_INDICATOR));
+
+        assert!(!context_manager.toggle_recording());
+        assert!(context_manager.current().recording.is_none());
+        assert!(!context_manager.has_recording(0));
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("{\"version\":2"));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_monitor_output() {
+        let window_id = WindowId::from(0);
+
+        let mut context_manager =
+            ContextManager::start_with_capacity(5, VoidListener {}, window_id).unwrap();
+        context_manager.add_context(false, 1);
+        let monitor_output = Arc::clone(&context_manager.config.monitor_output);
+        assert!(!monitor_output.load(Ordering::Relaxed));
+
+        assert!(context_manager.toggle_monitor_silence());
+        assert!(monitor_output.load(Ordering::Relaxed));
+        assert!(!context_manager.toggle_monitor_silence());
+        assert!(!monitor_output.load(Ordering::Relaxed));
+
+        context_manager.config.monitor.activity = true;
+        context_manager.update_monitor_output();
+        assert!(monitor_output.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_bell() {
+        let window_id = WindowId::from(0);
+
+        let mut context_manager =
+            ContextManager::start_with_capacity(5, VoidListener {}, window_id).unwrap();
+        context_manager.config.bell.visual = true;
+        let current_route = context_manager.current_route();
+        context_manager.add_context(false, 1);
+        let background_route = context_manager.contexts[1].current().route_id;
+        assert_eq!(context_manager.current_index(), 0);
+
+        // Focused split is never rate limited
+        assert!(context_manager.bell(current_route, true));
+        assert!(context_manager.bell(current_route, true));
+        assert!(!context_manager.tab_bell(0));
+        assert!(context_manager.has_visual_bell());
+
+        assert!(context_manager.bell(background_route, true));
+        assert!(!context_manager.bell(background_route, true));
+        assert!(context_manager.tab_bell(1));
+        assert!(context_manager.titles.titles[&1]
+            .content
+            .starts_with(BELL_TITLE_INDICATOR));
+
+        // Window isn't focused, so neither is the current split
+        assert!(!context_manager.bell(current_route, false));
+
+        context_manager.set_current(1);
+        assert!(!context_manager.tab_bell(1));
+        assert!(!context_manager.titles.titles[&1]
+            .content
+            .starts_with(BELL_TITLE_INDICATOR));
+    }
+
+    #[test]
+    fn test_start_playback() {
+        let window_id = WindowId::from(0);
+
+        let mut context_manager =
+            ContextManager::start_with_capacity(5, VoidListener {}, window_id).unwrap();
+        context_manager.current_mut().rich_text_id = 1;
+        context_manager.split(2, false);
+        let route_id = context_manager.current_route();
+        assert!(context_manager.current().playback.is_none());
+
+        context_manager.start_playback(Recording::default());
+        assert_eq!(context_manager.current_grid_len(), 2);
+        assert!(context_manager.current().playback.is_some());
+        assert!(!context_manager.current().playback_sized);
+        assert_eq!(context_manager.current().route_id, route_id);
+        assert_eq!(context_manager.current().rich_text_id, 2);
+
+        context_manager.select_prev_split();
+        assert!(context_manager.current().playback.is_none());
+    }
+
+    #[test]
+    fn test_add_layout() {
+        let window_id = WindowId::from(0);
+
+        let mut context_manager =
+            ContextManager::start_with_capacity(5, VoidListener {}, window_id).unwrap();
+
+        let pane = |split: LayoutSplit| rio_backend::config::layout::LayoutPane {
+            split,
+            ..Default::default()
+        };
+        context_manager.config.layouts.insert(
+            String::from("dev"),
+            Layout {
+                panes: vec![
+                    pane(LayoutSplit::Right),
+                    pane(LayoutSplit::Right),
+                    pane(LayoutSplit::Down),
+                ],
+            },
+        );
+
+        assert!(!context_manager.add_layout("unknown", &[1, 2, 3]));
+        // Requires one rich text for each pane
+        assert!(!context_manager.add_layout("dev", &[1, 2]));
+        assert_eq!(context_manager.len(), 1);
+
+        assert!(context_manager.add_layout("dev", &[1, 2, 3]));
+        assert_eq!(context_manager.len(), 2);
+        assert_eq!(context_manager.current_index(), 1);
+        assert_eq!(context_manager.current_grid_len(), 3);
+        assert_eq!(context_manager.current().rich_text_id, 1);
+        assert_eq!(context_manager.current_route(), 1);
+
+        let grid = context_manager.current_grid_mut();
+        assert_eq!(grid.contexts()[1].context().rich_text_id, 2);
+        assert_eq!(grid.contexts()[2].context().rich_text_id, 3);
+
+        // Routes after the ones of the layout are still free
+        context_manager.add_context(true, 4);
+        assert_eq!(context_manager.current_route(), 4);
+    }
 }
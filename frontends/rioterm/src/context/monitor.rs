@@ -0,0 +1,157 @@
+use rio_backend::config::monitor::Monitor;
+use std::time::{Duration, Instant};
+
+/// Monitor that fired for a background tab.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MonitorAlert {
+    Activity,
+    Silence,
+}
+
+impl MonitorAlert {
+    /// Prefix added to the tab title while the alert wasn't seen.
+    pub fn indicator(&self) -> &'static str {
+        match self {
+            MonitorAlert::Activity => "[activity] ",
+            MonitorAlert::Silence => "[silence] ",
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            MonitorAlert::Activity => "New output",
+            MonitorAlert::Silence => "Output stopped",
+        }
+    }
+}
+
+/// Monitors of a tab, `None` follows the `[monitor]` config.
+#[derive(Default, Debug)]
+pub struct TabMonitor {
+    activity: Option<bool>,
+    silence: Option<bool>,
+    /// Alert kept until the tab gets selected.
+    pub alert: Option<MonitorAlert>,
+    /// Last output since the silence monitor fired.
+    last_output: Option<Instant>,
+}
+
+impl TabMonitor {
+    #[inline]
+    pub fn has_activity(&self, config: &Monitor) -> bool {
+        self.activity.unwrap_or(config.activity)
+    }
+
+    #[inline]
+    pub fn has_silence(&self, config: &Monitor) -> bool {
+        self.silence.unwrap_or(config.silence)
+    }
+
+    pub fn toggle_activity(&mut self, config: &Monitor) -> bool {
+        let activity = !self.has_activity(config);
+        self.activity = Some(activity);
+        activity
+    }
+
+    pub fn toggle_silence(&mut self, config: &Monitor) -> bool {
+        let silence = !self.has_silence(config);
+        self.silence = Some(silence);
+        silence
+    }
+
+    /// Interval of the silence monitor, if it's enabled.
+    #[inline]
+    pub fn silence_interval(&self, config: &Monitor) -> Option<Duration> {
+        self.has_silence(config)
+            .then(|| Duration::from_secs(config.silence_seconds.max(1)))
+    }
+
+    /// New output arrived, returns the alert to fire if the tab is in
+    /// background. Activity only fires once until the tab is seen.
+    pub fn output(&mut self, config: &Monitor, background: bool) -> Option<MonitorAlert> {
+        self.last_output = Some(Instant::now());
+
+        if background && self.has_activity(config) && self.alert.is_none() {
+            self.alert = Some(MonitorAlert::Activity);
+            return self.alert;
+        }
+
+        None
+    }
+
+    /// Silence timer expired, returns the alert to fire if nothing was
+    /// written for the whole interval and the tab is in background.
+    pub fn silence(
+        &mut self,
+        config: &Monitor,
+        background: bool,
+    ) -> Option<MonitorAlert> {
+        let interval = self.silence_interval(config)?;
+        if self.last_output?.elapsed() < interval {
+            return None;
+        }
+
+        // The tab needs to write again before the next alert
+        self.last_output = None;
+        if !background {
+            return None;
+        }
+
+        self.alert = Some(MonitorAlert::Silence);
+        self.alert
+    }
+
+    /// Tab was selected.
+    #[inline]
+    pub fn seen(&mut self) -> bool {
+        self.alert.take().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activity_fires_once_until_seen() {
+        let config = Monitor {
+            activity: true,
+            ..Monitor::default()
+        };
+        let mut monitor = TabMonitor::default();
+
+        assert_eq!(monitor.output(&config, false), None);
+        assert_eq!(monitor.output(&config, true), Some(MonitorAlert::Activity));
+        assert_eq!(monitor.output(&config, true), None);
+        assert!(monitor.seen());
+        assert!(!monitor.seen());
+
+        assert!(!monitor.toggle_activity(&config));
+        assert_eq!(monitor.output(&config, true), None);
+    }
+
+    #[test]
+    fn test_silence_requires_output() {
+        let config = Monitor {
+            silence: true,
+            silence_seconds: 0,
+            ..Monitor::default()
+        };
+        let mut monitor = TabMonitor::default();
+        assert_eq!(
+            monitor.silence_interval(&config),
+            Some(Duration::from_secs(1))
+        );
+
+        // Nothing was written yet
+        assert_eq!(monitor.silence(&config, true), None);
+
+        monitor.last_output = Some(Instant::now() - Duration::from_secs(2));
+        assert_eq!(monitor.silence(&config, true), Some(MonitorAlert::Silence));
+        assert_eq!(monitor.silence(&config, true), None);
+
+        // Still writing
+        monitor.output(&config, true);
+        assert_eq!(monitor.silence(&config, true), None);
+    }
+}
@@ -0,0 +1,104 @@
+// ipc.rs was based on https://github.com/alacritty/alacritty/blob/e35e5ad14fce8456afdd89f2b392b9924bb27471/alacritty/src/ipc.rs
+// which is licensed under Apache 2.0 license.
+//
+// Unix socket used by `rio msg` to send requests to a running instance.
+
+use crate::cli::SocketMessage;
+use rio_backend::event::{EventProxy, RioEvent, RioEventType, WindowId};
+use rio_backend::performer::spawn_named;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Result, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::{env, fs, process};
+
+/// Environment variable with the socket path of the running instance.
+pub const RIO_SOCKET_ENV: &str = "RIO_SOCKET";
+
+static SOCKET_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Creates the IPC socket and listens to its messages in a new thread.
+pub fn spawn_ipc_socket(event_proxy: EventProxy) -> Option<PathBuf> {
+    let socket_path = socket_dir().join(format!("rio-{}.sock", process::id()));
+    let _ = fs::remove_file(&socket_path);
+
+    let listener = match UnixListener::bind(&socket_path) {
+        Ok(listener) => listener,
+        Err(err) => {
+            tracing::warn!("unable to create ipc socket {socket_path:?}: {err}");
+            return None;
+        }
+    };
+
+    // Shells started by this instance will reach it through `rio msg`
+    let _ = SOCKET_PATH.set(socket_path.clone());
+
+    spawn_named("ipc socket", move || {
+        for stream in listener.incoming().filter_map(Result::ok) {
+            let mut data = String::new();
+            if BufReader::new(stream).read_line(&mut data).is_err() {
+                continue;
+            }
+
+            let message: SocketMessage = match serde_json::from_str(&data) {
+                Ok(message) => message,
+                Err(err) => {
+                    tracing::warn!("invalid ipc message {data:?}: {err}");
+                    continue;
+                }
+            };
+
+            let event = match message {
+                SocketMessage::SaveScreen(options) => RioEvent::SaveScreen(
+                    options.output,
+                    options.format,
+                    options.scrollback,
+                ),
+                SocketMessage::CreateWindow(options) => {
+                    match options.terminal_options.window_config_overrides() {
+                        Ok(overrides) => RioEvent::CreateWindowWithOverrides(overrides),
+                        Err(err) => {
+                            tracing::warn!("invalid ipc window options: {err}");
+                            continue;
+                        }
+                    }
+                }
+            };
+            event_proxy.send_event(RioEventType::Rio(event), WindowId::from(0));
+        }
+    });
+
+    Some(socket_path)
+}
+
+/// Environment passed to the shells so `rio msg` reaches this instance.
+pub fn shell_env() -> Vec<(String, String)> {
+    SOCKET_PATH
+        .get()
+        .map(|path| vec![(RIO_SOCKET_ENV.to_string(), path.display().to_string())])
+        .unwrap_or_default()
+}
+
+/// Sends a message to the instance listening in `socket`, or in the
+/// socket of the instance running the current shell.
+pub fn send_message(socket: Option<PathBuf>, message: SocketMessage) -> Result<()> {
+    let socket = match socket.or_else(|| env::var_os(RIO_SOCKET_ENV).map(PathBuf::from)) {
+        Some(socket) => socket,
+        None => {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("no socket found, use --socket or set {RIO_SOCKET_ENV}"),
+            ));
+        }
+    };
+
+    let mut stream = UnixStream::connect(socket)?;
+    let mut data = serde_json::to_string(&message)?;
+    data.push('\n');
+    stream.write_all(data.as_bytes())?;
+    stream.flush()
+}
+
+fn socket_dir() -> PathBuf {
+    dirs::runtime_dir().unwrap_or_else(env::temp_dir)
+}
@@ -10,6 +10,8 @@ mod cli;
 mod constants;
 mod context;
 mod ime;
+#[cfg(unix)]
+mod ipc;
 mod messenger;
 mod mouse;
 #[cfg(windows)]
@@ -22,7 +24,7 @@ mod screen;
 mod watcher;
 
 use clap::Parser;
-use rio_backend::config::config_dir_path;
+use rio_backend::config::{config_dir_path, ConfigFile};
 use rio_backend::event::EventPayload;
 use rio_backend::{ansi, crosswords, event, performer, selection};
 use std::path::PathBuf;
@@ -124,6 +126,51 @@ fn setup_logs_by_filter_level(
     Ok(())
 }
 
+/// Prints the effective config, issues found while loading it go to stderr.
+/// There is no window to read the appearance of the system from yet, so
+/// the colors of an adaptive theme are the dark ones.
+fn print_config(
+    mut config: rio_backend::config::Config,
+    config_error: Option<rio_backend::config::ConfigError>,
+    format: cli::ConfigFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match config_error {
+        Some(rio_backend::config::ConfigError::PathNotFound) | None => {}
+        Some(err) => eprintln!("{}", rio_backend::error::RioError::from(err).report),
+    }
+    for diagnostic in &config.diagnostics {
+        eprintln!("{diagnostic}");
+    }
+    renderer::utils::update_colors_based_on_theme(
+        &mut config,
+        Some(rio_window::window::Theme::Dark),
+    );
+
+    let printed = match format {
+        cli::ConfigFormat::Toml => config.to_string()?,
+        cli::ConfigFormat::Json => serde_json::to_string_pretty(&config)?,
+    };
+    println!("{printed}");
+    Ok(())
+}
+
+/// Writes the theme at `path` as a Rio theme in the themes directory.
+fn convert_theme(path: &std::path::Path) -> Result<PathBuf, String> {
+    let theme = rio_backend::config::theme_formats::to_rio_theme(path)?;
+    let name = path
+        .file_stem()
+        .ok_or_else(|| format!("{} is not a file", path.display()))?;
+    let themes_dir = rio_backend::config::themes_dir_path();
+    let output = themes_dir.join(name).with_extension("toml");
+    if output.exists() {
+        return Err(format!("{} already exists", output.display()));
+    }
+
+    std::fs::create_dir_all(&themes_dir).map_err(|err| err.to_string())?;
+    std::fs::write(&output, theme).map_err(|err| err.to_string())?;
+    Ok(output)
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     #[cfg(windows)]
     panic::attach_handler();
@@ -139,6 +186,54 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load command line options.
     let args = cli::Cli::parse();
 
+    #[cfg(unix)]
+    if let Some(cli::Subcommands::Msg(options)) = args.subcommands {
+        let mut message = options.message;
+        // Paths are resolved by the instance, which has another directory
+        let current_dir = std::env::current_dir().unwrap_or_default();
+        match &mut message {
+            cli::SocketMessage::SaveScreen(save_screen) => {
+                save_screen.output = current_dir.join(&save_screen.output);
+            }
+            cli::SocketMessage::CreateWindow(window_options) => {
+                let terminal_options = &mut window_options.terminal_options;
+                if let Err(err) = terminal_options.window_config_overrides() {
+                    eprintln!("{err}");
+                    std::process::exit(1);
+                }
+                if let Some(working_dir) = &mut terminal_options.working_dir {
+                    *working_dir = current_dir.join(&working_dir).display().to_string();
+                }
+            }
+        }
+
+        if let Err(err) = ipc::send_message(options.socket, message) {
+            eprintln!("unable to send message: {err}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(config_file) = &args.config_file {
+        let current_dir = std::env::current_dir().unwrap_or_default();
+        rio_backend::config::set_config_file_path(current_dir.join(config_file));
+    }
+
+    let startup_layout = args.window_options.terminal_options.layout.clone();
+    let startup_record = args.window_options.terminal_options.record.clone();
+
+    let mut startup_playback = None;
+    if let Some(play) = &args.window_options.terminal_options.play {
+        let timing = args.window_options.terminal_options.play_timing.as_deref();
+        match rio_backend::performer::player::Recording::load(play, timing) {
+            Ok(recording) => startup_playback = Some(recording),
+            Err(err) => {
+                eprintln!("unable to play {}: {err}", play.display());
+                std::process::exit(1);
+            }
+        }
+    }
+
     let write_config_path = args.window_options.terminal_options.write_config.clone();
     if let Some(config_path) = write_config_path {
         let _ = setup_logs_by_filter_level("TRACE", false);
@@ -146,23 +241,53 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
-    let (mut config, config_error) = match rio_backend::config::Config::try_load() {
-        Ok(config) => (config, None),
-        Err(err) => (rio_backend::config::Config::default(), Some(err)),
+    if let Some(path) = &args.convert_theme {
+        match convert_theme(path) {
+            Ok(output) => {
+                let name = output.file_stem().unwrap_or_default().to_string_lossy();
+                println!("theme written to {}", output.display());
+                println!("use it with `theme = \"{name}\"` in the config");
+            }
+            Err(err) => {
+                eprintln!("unable to convert {}: {err}", path.display());
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    #[cfg(feature = "schema")]
+    if args.print_config_schema {
+        let schema = rio_backend::config::schema::generate();
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        return Ok(());
+    }
+
+    let config_overrides = match args.window_options.terminal_options.config_overrides() {
+        Ok(config_overrides) => config_overrides,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
     };
 
-    // Read platform property and overwrite values per OS
-    //
-    // [shell]
-    // # default (in this case will be used on MacOS/Linux)
-    // program = "/bin/fish"
-    // args = ["--login"]
-    //
-    // [platform]
-    // # Microsoft Windows overwrite
-    // windows.shell.program = "pwsh"
-    // windows.shell.args = ["-l"]
-    config.overwrite_based_on_platform();
+    let loaded = ConfigFile::read().and_then(|config_file| {
+        let config = config_file.load(&config_overrides)?;
+        Ok((config_file, config))
+    });
+    let (mut config, config_file, config_error) = match loaded {
+        Ok((config_file, config)) => (config, Some(config_file), None),
+        Err(err) => (
+            rio_backend::config::Config::default_with_overrides(&config_overrides),
+            None,
+            Some(err),
+        ),
+    };
+
+    if let Some(format) = args.print_config {
+        print_config(config, config_error, format)?;
+        return Ok(());
+    }
 
     {
         let log_to_file = args.window_options.terminal_options.enable_log_file;
@@ -199,8 +324,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let window_event_loop =
         rio_window::event_loop::EventLoop::<EventPayload>::with_user_event().build()?;
 
-    let mut application =
-        crate::application::Application::new(config, config_error, &window_event_loop);
+    let mut application = crate::application::Application::new(
+        config,
+        config_error,
+        config_file,
+        config_overrides,
+        startup_layout,
+        startup_record,
+        startup_playback,
+        &window_event_loop,
+    );
     let _ = application.run(window_event_loop);
 
     #[cfg(windows)]
@@ -1,4 +1,5 @@
 use crate::event::Msg;
+use rio_backend::performer::recorder::Recorder;
 use std::borrow::Cow;
 use teletypewriter::WinsizeBuilder;
 
@@ -27,6 +28,16 @@ impl Messenger {
         let _ = self.channel.send(Msg::Input(bytes));
     }
 
+    #[inline]
+    pub fn send_start_recording(&self, recorder: Recorder) {
+        let _ = self.channel.send(Msg::StartRecording(recorder));
+    }
+
+    #[inline]
+    pub fn send_stop_recording(&self) {
+        let _ = self.channel.send(Msg::StopRecording);
+    }
+
     #[inline]
     pub fn send_resize(&self, new_size: WinsizeBuilder) -> Result<&str, String> {
         match self.channel.send(Msg::Resize(new_size)) {
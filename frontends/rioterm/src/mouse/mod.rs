@@ -1,3 +1,4 @@
+use crate::context::grid::SplitDirection;
 use crate::crosswords::pos::Column;
 use crate::crosswords::pos::Line;
 use crate::crosswords::pos::Side;
@@ -16,6 +17,15 @@ pub struct AccumulatedScroll {
     pub y: f64,
 }
 
+/// Border between splits that is being dragged by the mouse.
+#[derive(Debug, Clone, Copy)]
+pub struct SplitBorderDrag {
+    pub index: usize,
+    pub edge: SplitDirection,
+    pub x: usize,
+    pub y: usize,
+}
+
 #[derive(Debug)]
 pub struct Mouse {
     pub multiplier: f64,
@@ -29,6 +39,9 @@ pub struct Mouse {
     pub accumulated_scroll: AccumulatedScroll,
     pub square_side: Side,
     pub inside_text_area: bool,
+    pub split_border_drag: Option<SplitBorderDrag>,
+    /// Whether the cursor shows the resize icon of a border between splits.
+    pub over_split_border: bool,
     pub x: usize,
     pub y: usize,
 }
@@ -46,6 +59,8 @@ impl Default for Mouse {
             click_state: ClickState::None,
             square_side: Side::Left,
             inside_text_area: Default::default(),
+            split_border_drag: None,
+            over_split_border: false,
             accumulated_scroll: AccumulatedScroll::default(),
             x: Default::default(),
             y: Default::default(),
@@ -1,2 +1,35 @@
 #[cfg(target_os = "macos")]
 pub mod macos;
+
+/// Program and arguments that show a desktop notification.
+pub fn notification_command(title: &str, message: &str) -> Option<(String, Vec<String>)> {
+    #[cfg(target_os = "macos")]
+    {
+        let escape = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+        Some((
+            String::from("osascript"),
+            vec![
+                String::from("-e"),
+                format!(
+                    "display notification \"{}\" with title \"{}\"",
+                    escape(message),
+                    escape(title)
+                ),
+            ],
+        ))
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        Some((
+            String::from("notify-send"),
+            vec![String::from(title), String::from(message)],
+        ))
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = (title, message);
+        None
+    }
+}
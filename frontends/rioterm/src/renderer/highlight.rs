@@ -0,0 +1,245 @@
+// Highlight rules are matched against each row when it's rendered, the
+// grid isn't changed. Matches don't continue on wrapped rows.
+
+use crate::crosswords::grid::row::Row;
+use crate::crosswords::square::{Flags, Square};
+use rio_backend::config::colors::ColorArray;
+use rio_backend::config::highlight::Highlight;
+use rio_backend::sugarloaf::{
+    FragmentStyle, FragmentStyleDecoration, UnderlineInfo, UnderlineShape, Weight,
+};
+
+struct Rule {
+    regex: regex::Regex,
+    foreground: Option<ColorArray>,
+    background: Option<ColorArray>,
+    underline: bool,
+    bold: bool,
+}
+
+pub struct HighlightRules {
+    rules: Vec<Rule>,
+}
+
+/// Whether the rules are applied to a split, along with the matches of
+/// its visible rows so they are only searched again when a row changes.
+#[derive(Debug, Default)]
+pub struct HighlightState {
+    pub enabled: bool,
+    rows: Vec<CachedRow>,
+}
+
+#[derive(Debug, Default)]
+struct CachedRow {
+    /// Characters of the row, with `\0` in the wide char spacers.
+    squares: Vec<char>,
+    matches: Vec<Option<usize>>,
+}
+
+impl HighlightState {
+    pub fn new(enabled: bool) -> HighlightState {
+        HighlightState {
+            enabled,
+            rows: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+    /// Drops the matches, needed whenever the rules change.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.rows.clear();
+    }
+}
+
+impl CachedRow {
+    fn square(square: &Square) -> char {
+        if square.flags.contains(Flags::WIDE_CHAR_SPACER) {
+            '\0'
+        } else {
+            square.c
+        }
+    }
+
+    fn is_row(&self, row: &Row<Square>) -> bool {
+        self.squares.len() == row.len()
+            && self
+                .squares
+                .iter()
+                .zip(row.inner.iter())
+                .all(|(character, square)| *character == CachedRow::square(square))
+    }
+}
+
+impl HighlightRules {
+    pub fn new(config: &Highlight) -> HighlightRules {
+        let mut rules = Vec::with_capacity(config.rules.len());
+        for rule in &config.rules {
+            match regex::Regex::new(&rule.regex) {
+                Ok(regex) => rules.push(Rule {
+                    regex,
+                    foreground: rule.foreground,
+                    background: rule.background,
+                    underline: rule.underline,
+                    bold: rule.bold,
+                }),
+                Err(err) => {
+                    tracing::warn!("invalid highlight regex {:?}: {err}", rule.regex)
+                }
+            }
+        }
+
+        HighlightRules { rules }
+    }
+
+    /// Index of the rule that matches each column of the visible row in
+    /// `line`, the first rule wins when matches overlap. Empty if nothing
+    /// matches.
+    pub fn row_matches<'a>(
+        &self,
+        row: &Row<Square>,
+        line: usize,
+        state: &'a mut HighlightState,
+    ) -> &'a [Option<usize>] {
+        if !state.enabled || self.rules.is_empty() {
+            return &[];
+        }
+
+        if state.rows.len() <= line {
+            state.rows.resize_with(line + 1, CachedRow::default);
+        }
+
+        let cached = &mut state.rows[line];
+        if !cached.is_row(row) {
+            cached.squares = row.inner.iter().map(CachedRow::square).collect();
+            cached.matches = self.find_matches(&cached.squares);
+        }
+
+        &cached.matches
+    }
+
+    fn find_matches(&self, squares: &[char]) -> Vec<Option<usize>> {
+        let mut text = String::with_capacity(squares.len());
+        let mut columns = Vec::with_capacity(squares.len());
+        for (column, character) in squares.iter().enumerate() {
+            if *character == '\0' {
+                continue;
+            }
+
+            columns.push((text.len(), column));
+            text.push(*character);
+        }
+
+        let column_at = |offset: usize| {
+            let index = columns.partition_point(|(start, _)| *start <= offset);
+            columns[index.saturating_sub(1)].1
+        };
+
+        let mut matches: Vec<Option<usize>> = Vec::new();
+        for (index, rule) in self.rules.iter().enumerate() {
+            for found in rule.regex.find_iter(&text) {
+                if found.is_empty() {
+                    continue;
+                }
+
+                if matches.is_empty() {
+                    matches.resize(squares.len(), None);
+                }
+
+                let start = column_at(found.start());
+                let end = column_at(found.end() - 1);
+                for column in matches.iter_mut().take(end + 1).skip(start) {
+                    if column.is_none() {
+                        *column = Some(index);
+                    }
+                }
+            }
+        }
+
+        matches
+    }
+
+    /// Applies the style of the rule in `index` over `style`.
+    pub fn apply(&self, index: usize, style: &mut FragmentStyle) {
+        let rule = &self.rules[index];
+        if let Some(foreground) = rule.foreground {
+            style.color = foreground;
+        }
+        if let Some(background) = rule.background {
+            style.background_color = Some(background);
+        }
+        if rule.underline {
+            style.decoration = Some(FragmentStyleDecoration::Underline(UnderlineInfo {
+                offset: -1.0,
+                size: 1.0,
+                is_doubled: false,
+                shape: UnderlineShape::Regular,
+            }));
+        }
+        if rule.bold {
+            let attrs = style.font_attrs;
+            style.font_attrs = (attrs.stretch(), Weight::BOLD, attrs.style()).into();
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use rio_backend::config::highlight::HighlightRule;
+
+    fn row(text: &str) -> Row<Square> {
+        let mut row: Row<Square> = Row::new(10);
+        for (column, character) in text.chars().enumerate() {
+            row.inner[column].c = character;
+        }
+        row
+    }
+
+    #[test]
+    fn test_row_matches() {
+        let rule = |regex: &str| HighlightRule {
+            regex: String::from(regex),
+            foreground: None,
+            background: None,
+            underline: true,
+            bold: false,
+        };
+        let rules = HighlightRules::new(&Highlight {
+            enabled: true,
+            rules: vec![rule("WARN"), rule("[A-Z]+")],
+        });
+        let mut state = HighlightState::new(true);
+
+        assert_eq!(
+            rules.row_matches(&row("a WARN ok"), 0, &mut state),
+            [
+                None,
+                None,
+                Some(0),
+                Some(0),
+                Some(0),
+                Some(0),
+                None,
+                None,
+                None,
+                None
+            ]
+        );
+        assert_eq!(rules.row_matches(&row("AB"), 1, &mut state).len(), 10);
+        assert!(rules.row_matches(&row("nothing"), 1, &mut state).is_empty());
+        assert_eq!(
+            rules.row_matches(&row("a WARN ok"), 0, &mut state)[2],
+            Some(0)
+        );
+
+        state.toggle();
+        assert!(rules
+            .row_matches(&row("a WARN ok"), 0, &mut state)
+            .is_empty());
+    }
+}
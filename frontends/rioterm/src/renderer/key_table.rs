@@ -0,0 +1,34 @@
+use crate::constants::*;
+use rio_backend::config::colors::Colors;
+use rio_backend::sugarloaf::{Object, Rect, Text};
+
+/// Width taken by each character of the indicator.
+const CHARACTER_WIDTH: f32 = 8.;
+
+/// Shows the active key table (or the chords typed of a sequence) on the
+/// bottom right corner.
+#[inline]
+pub fn draw_key_table_indicator(
+    objects: &mut Vec<Object>,
+    colors: &Colors,
+    dimensions: (f32, f32, f32),
+    content: &str,
+) {
+    let (width, height, scale) = dimensions;
+    let indicator_width = content.chars().count() as f32 * CHARACTER_WIDTH + 16.;
+    let position_x = (width / scale) - indicator_width - 4.;
+    let position_y = (height / scale) - PADDING_Y_BOTTOM_TABS - 4.;
+
+    objects.push(Object::Rect(Rect {
+        position: [position_x, position_y],
+        color: colors.bar,
+        size: [indicator_width * 2., PADDING_Y_BOTTOM_TABS],
+    }));
+
+    objects.push(Object::Text(Text::single_line(
+        (position_x + 8., position_y + 10.),
+        content.to_string(),
+        14.,
+        colors.foreground,
+    )));
+}
@@ -1,3 +1,5 @@
+mod highlight;
+mod key_table;
 pub mod navigation;
 mod search;
 pub mod utils;
@@ -8,7 +10,10 @@ use crate::context::ContextManager;
 use crate::crosswords::grid::row::Row;
 use crate::crosswords::pos::{Column, Line, Pos};
 use crate::crosswords::square::{Flags, Square};
+use crate::crosswords::timestamp;
 use crate::screen::hint::HintMatches;
+use highlight::HighlightRules;
+pub use highlight::HighlightState;
 use navigation::ScreenNavigation;
 use rio_backend::config::colors::{
     term::{List, TermColors},
@@ -17,7 +22,7 @@ use rio_backend::config::colors::{
 use rio_backend::config::Config;
 use rio_backend::event::EventProxy;
 use rio_backend::sugarloaf::{
-    Content, FragmentStyle, FragmentStyleDecoration, Graphic, Stretch, Style,
+    Content, FragmentStyle, FragmentStyleDecoration, Graphic, Object, Stretch, Style,
     SugarCursor, Sugarloaf, UnderlineInfo, UnderlineShape, Weight,
 };
 use std::collections::HashMap;
@@ -47,6 +52,11 @@ pub struct Renderer {
         (usize, f32),
     >,
     active_search: Option<String>,
+    /// Key table (or sequence) shown by the indicator.
+    key_table: Option<String>,
+    /// Objects drawn on top of everything else for the next frame.
+    overlay: Vec<Object>,
+    highlight: HighlightRules,
 }
 
 impl Renderer {
@@ -94,6 +104,9 @@ impl Renderer {
             named_colors,
             dynamic_background,
             active_search: None,
+            key_table: None,
+            overlay: Vec::new(),
+            highlight: HighlightRules::new(&config.highlight),
             font_cache: FxHashMap::default(),
             font_context: font_context.clone(),
         }
@@ -104,6 +117,16 @@ impl Renderer {
         self.active_search = active_search;
     }
 
+    #[inline]
+    pub fn set_key_table(&mut self, key_table: Option<String>) {
+        self.key_table = key_table;
+    }
+
+    #[inline]
+    pub fn set_overlay(&mut self, overlay: Vec<Object>) {
+        self.overlay = overlay;
+    }
+
     #[inline]
     fn create_style(&mut self, square: &Square) -> (FragmentStyle, char) {
         let flags = square.flags;
@@ -219,14 +242,30 @@ impl Renderer {
         &mut self,
         builder: &mut Content,
         row: &Row<Square>,
+        line_index: usize,
         has_cursor: bool,
         line_opt: Option<usize>,
         line: Line,
         renderable_content: &RenderableContent,
+        highlight: &mut HighlightState,
         search_hints: &mut Option<HintMatches>,
         focused_match: &Option<RangeInclusive<Pos>>,
         is_active: bool,
+        show_timestamps: bool,
     ) {
+        if show_timestamps {
+            let style = FragmentStyle {
+                color: self.named_colors.dim_foreground,
+                ..FragmentStyle::default()
+            };
+            let gutter = timestamp::gutter(row.timestamp());
+            if let Some(line) = line_opt {
+                builder.add_text_on_line(line, &gutter, style);
+            } else {
+                builder.add_text(&gutter, style);
+            }
+        }
+
         let cursor = &renderable_content.cursor;
         let hyperlink_range = renderable_content.hyperlink_range;
         let selection_range = renderable_content.selection_range;
@@ -234,6 +273,7 @@ impl Renderer {
         let mut content = String::default();
         let mut last_char_was_space = false;
         let mut last_style = FragmentStyle::default();
+        let highlights = self.highlight.row_matches(row, line_index, highlight);
 
         for column in 0..columns {
             let square = &row.inner[column];
@@ -290,6 +330,8 @@ impl Renderer {
                     style.background_color =
                         Some(self.named_colors.search_match_background);
                 }
+            } else if let Some(Some(rule)) = highlights.get(column) {
+                self.highlight.apply(*rule, &mut style);
             }
 
             if square.flags.contains(Flags::GRAPHICS) {
@@ -662,7 +704,10 @@ impl Renderer {
             let is_active = active_index == index;
             let context = grid_context.context_mut();
             let rich_text_id = context.rich_text_id;
-            let renderable_content = context.renderable_content();
+            let show_timestamps = context.dimension.gutter > 0;
+            context.renderable_content();
+            let renderable_content = &context.renderable_content;
+            let highlight = &mut context.highlight;
             let mut is_cursor_visible = renderable_content.is_cursor_visible
                 && renderable_content.cursor.state.is_visible();
             if !is_active && renderable_content.cursor.state.is_visible() {
@@ -686,13 +731,16 @@ impl Renderer {
                         self.create_line(
                             content,
                             row,
+                            i,
                             has_cursor,
                             None,
                             Line((i as i32) - display_offset),
                             renderable_content,
+                            highlight,
                             hints,
                             focused_match,
                             is_active,
+                            show_timestamps,
                         );
                     }
                     content.build();
@@ -708,13 +756,16 @@ impl Renderer {
                             self.create_line(
                                 content,
                                 line_data,
+                                line,
                                 has_cursor,
                                 Some(line),
                                 Line((line as i32) - display_offset),
                                 renderable_content,
+                                highlight,
                                 hints,
                                 focused_match,
                                 is_active,
+                                show_timestamps,
                             );
                         }
                     }
@@ -745,9 +796,19 @@ impl Renderer {
             self.active_search = None;
         }
 
+        if let Some(key_table) = &self.key_table {
+            key_table::draw_key_table_indicator(
+                &mut objects,
+                &self.named_colors,
+                (window_size.width, window_size.height, scale_factor),
+                key_table,
+            );
+        }
+
         for rte in context_manager.grid_objects() {
             objects.push(rte);
         }
+        objects.append(&mut self.overlay);
 
         sugarloaf.set_objects(objects);
     }
@@ -99,17 +99,27 @@ impl ScreenNavigation {
         self.objects.clear();
 
         let titles = &context_manager.titles.titles;
+        let alerts: Vec<bool> = (0..len)
+            .map(|i| {
+                context_manager.tab_alert(i).is_some() || context_manager.tab_bell(i)
+            })
+            .collect();
 
         match self.navigation.mode {
             #[cfg(target_os = "macos")]
             NavigationMode::NativeTab => {}
-            NavigationMode::Bookmark => {
-                self.bookmark(titles, colors, len, self.navigation.hide_if_single)
-            }
+            NavigationMode::Bookmark => self.bookmark(
+                titles,
+                &alerts,
+                colors,
+                len,
+                self.navigation.hide_if_single,
+            ),
             NavigationMode::TopTab => {
                 let position_y = 0.0;
                 self.tab(
                     titles,
+                    &alerts,
                     colors,
                     len,
                     position_y,
@@ -120,6 +130,7 @@ impl ScreenNavigation {
                 let position_y = (self.height / self.scale) - PADDING_Y_BOTTOM_TABS;
                 self.tab(
                     titles,
+                    &alerts,
                     colors,
                     len,
                     position_y,
@@ -137,6 +148,7 @@ impl ScreenNavigation {
     pub fn bookmark(
         &mut self,
         titles: &FxHashMap<usize, ContextTitle>,
+        alerts: &[bool],
         colors: &Colors,
         len: usize,
         hide_if_single: bool,
@@ -169,6 +181,10 @@ impl ScreenNavigation {
                 }
             }
 
+            if alerts.get(i).copied().unwrap_or(false) {
+                color = colors.tabs_alert;
+            }
+
             let renderable = Rect {
                 position: [initial_position, 0.0],
                 color,
@@ -183,6 +199,7 @@ impl ScreenNavigation {
     pub fn tab(
         &mut self,
         titles: &FxHashMap<usize, ContextTitle>,
+        alerts: &[bool],
         colors: &Colors,
         len: usize,
         position_y: f32,
@@ -240,6 +257,10 @@ impl ScreenNavigation {
                 }
             }
 
+            if !is_current && alerts.get(i).copied().unwrap_or(false) {
+                foreground_color = colors.tabs_alert;
+            }
+
             let name_modifier = 90.;
             if name.len() >= 14 {
                 name = name[0..14].to_string();
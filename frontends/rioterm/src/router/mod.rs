@@ -7,16 +7,19 @@ use crate::screen::{Screen, ScreenWindowProperties};
 use assistant::Assistant;
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use rio_backend::clipboard::Clipboard;
+use rio_backend::config::bell::Bell;
 use rio_backend::config::Config as RioConfig;
 use rio_backend::error::{RioError, RioErrorLevel, RioErrorType};
 use rio_backend::event::{EventPayload, RioEvent, RioEventType};
+use rio_window::event::ElementState;
 use rio_window::event_loop::ActiveEventLoop;
 use rio_window::keyboard::{Key, NamedKey};
 #[cfg(not(any(target_os = "macos", windows)))]
 use rio_window::platform::startup_notify::{
     self, EventLoopExtStartupNotify, WindowAttributesExtStartupNotify,
 };
-use rio_window::window::{Window, WindowId};
+use rio_window::window::{UserAttentionType, Window, WindowId};
+use routes::palette::{Palette, PaletteCommand};
 use routes::{assistant, RoutePath};
 use rustc_hash::FxHashMap;
 use std::cell::RefCell;
@@ -32,6 +35,7 @@ const RIO_TITLE: &str = "▲";
 
 pub struct Route<'a> {
     pub assistant: assistant::Assistant,
+    pub palette: Palette,
     pub path: RoutePath,
     pub window: RouteWindow<'a>,
 }
@@ -46,6 +50,7 @@ impl Route<'_> {
     ) -> Route {
         Route {
             assistant,
+            palette: Palette::default(),
             path,
             window,
         }
@@ -58,6 +63,19 @@ impl Route<'_> {
         self.window.winit_window.request_redraw();
     }
 
+    /// Signals a bell that rang on the window.
+    pub fn signal_bell(&mut self, bell: &Bell) {
+        if bell.urgent && !self.window.is_focused {
+            self.window
+                .winit_window
+                .request_user_attention(Some(UserAttentionType::Informational));
+        }
+
+        if bell.visual {
+            self.request_redraw();
+        }
+    }
+
     pub fn request_frame(&mut self, scheduler: &mut Scheduler) {
         let timer_id =
             TimerId::new(Topic::RenderRoute, self.window.screen.ctx().current_route());
@@ -121,6 +139,50 @@ impl Route<'_> {
         self.path = RoutePath::ConfirmQuit;
     }
 
+    pub fn open_command_palette(&mut self) {
+        if self.path != RoutePath::Terminal {
+            return;
+        }
+
+        let entries = self.window.screen.palette_entries();
+        self.palette.open(entries);
+        self.path = RoutePath::CommandPalette;
+    }
+
+    /// Handles the keys typed while the command palette is open, returns
+    /// the command picked with enter.
+    pub fn palette_key(
+        &mut self,
+        key_event: &rio_window::event::KeyEvent,
+    ) -> Option<PaletteCommand> {
+        if key_event.state == ElementState::Released {
+            return None;
+        }
+
+        match &key_event.logical_key {
+            Key::Named(NamedKey::Escape) => {
+                self.palette.close();
+                self.path = RoutePath::Terminal;
+            }
+            Key::Named(NamedKey::Enter) => {
+                self.path = RoutePath::Terminal;
+                return self.palette.take_selected();
+            }
+            Key::Named(NamedKey::ArrowUp) => self.palette.select_prev(),
+            Key::Named(NamedKey::ArrowDown) | Key::Named(NamedKey::Tab) => {
+                self.palette.select_next()
+            }
+            Key::Named(NamedKey::Backspace) => self.palette.pop(),
+            _ => {
+                if let Some(text) = &key_event.text {
+                    self.palette.push(text);
+                }
+            }
+        }
+
+        None
+    }
+
     #[inline]
     pub fn quit(&mut self) {
         std::process::exit(0);
@@ -263,6 +325,7 @@ impl Router<'_> {
             "Rio Settings",
             None,
             None,
+            None,
             self.clipboard.clone(),
         );
         let id = window.winit_window.id();
@@ -308,7 +371,8 @@ impl Router<'_> {
         event_proxy: EventProxy,
         config: &'a rio_backend::config::Config,
         open_url: Option<String>,
-    ) {
+        layout: Option<String>,
+    ) -> WindowId {
         let tab_id = if config.navigation.is_native() {
             Some(self.routes.len().to_string())
         } else {
@@ -323,6 +387,7 @@ impl Router<'_> {
             RIO_TITLE,
             tab_id.as_deref(),
             open_url,
+            layout,
             self.clipboard.clone(),
         );
         let id = window.winit_window.id();
@@ -331,6 +396,7 @@ impl Router<'_> {
             window,
             path: RoutePath::Terminal,
             assistant: Assistant::new(),
+            palette: Palette::default(),
         };
 
         if let Some(err) = &self.propagated_report {
@@ -339,6 +405,7 @@ impl Router<'_> {
         }
 
         self.routes.insert(id, route);
+        id
     }
 
     #[cfg(target_os = "macos")]
@@ -359,6 +426,7 @@ impl Router<'_> {
             RIO_TITLE,
             tab_id,
             open_url,
+            None,
             self.clipboard.clone(),
         );
         self.routes.insert(
@@ -367,6 +435,7 @@ impl Router<'_> {
                 window,
                 path: RoutePath::Terminal,
                 assistant: Assistant::new(),
+                palette: Palette::default(),
             },
         );
     }
@@ -433,6 +502,7 @@ impl<'a> RouteWindow<'a> {
         window_name: &str,
         tab_id: Option<&str>,
         open_url: Option<String>,
+        layout: Option<String>,
         clipboard: Rc<RefCell<Clipboard>>,
     ) -> RouteWindow<'a> {
         #[allow(unused_mut)]
@@ -464,6 +534,7 @@ impl<'a> RouteWindow<'a> {
             event_proxy,
             font_library,
             open_url,
+            layout,
             clipboard,
         )
         .expect("Screen not created");
@@ -1,5 +1,5 @@
 use crate::context::grid::ContextDimension;
-use rio_backend::error::{RioError, RioErrorLevel};
+use rio_backend::error::{RioError, RioErrorLevel, RioErrorType};
 use rio_backend::sugarloaf::{Object, Rect, Sugarloaf, Text};
 
 pub struct Assistant {
@@ -89,12 +89,42 @@ pub fn screen(
             )));
         }
 
-        objects.push(Object::Text(Text::multi_line(
-            (70., context_dimension.margin.top_y + 170.),
-            report.report.to_string(),
-            14.,
-            [1., 1., 1., 1.],
-        )));
+        if let RioErrorType::ConfigurationDiagnostics(diagnostics, fallback) =
+            &report.report
+        {
+            // One line per diagnostic, colored by severity
+            let mut position_y = context_dimension.margin.top_y + 170.;
+            objects.push(Object::Text(Text::single_line(
+                (70., position_y),
+                String::from("Found issues in the configuration file:"),
+                14.,
+                [1., 1., 1., 1.],
+            )));
+            for diagnostic in diagnostics {
+                position_y += 22.;
+                objects.push(Object::Text(Text::single_line(
+                    (70., position_y),
+                    diagnostic.to_string(),
+                    14.,
+                    if diagnostic.is_error() { red } else { yellow },
+                )));
+            }
+            if let Some(fallback) = fallback {
+                objects.push(Object::Text(Text::single_line(
+                    (70., position_y + 36.),
+                    fallback.message().to_string(),
+                    14.,
+                    [1., 1., 1., 1.],
+                )));
+            }
+        } else {
+            objects.push(Object::Text(Text::multi_line(
+                (70., context_dimension.margin.top_y + 170.),
+                report.report.to_string(),
+                14.,
+                [1., 1., 1., 1.],
+            )));
+        }
 
         sugarloaf.set_objects(objects);
     }
@@ -0,0 +1,293 @@
+use crate::bindings::Action;
+use rio_backend::config::colors::Colors;
+use rio_backend::sugarloaf::{Object, Rect, Text};
+
+const MAX_VISIBLE_ENTRIES: usize = 12;
+const ENTRY_HEIGHT: f32 = 22.;
+const PANEL_MAX_WIDTH: f32 = 600.;
+
+/// What runs once an entry of the palette is picked.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteCommand {
+    Action(Action),
+    Theme(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteEntry {
+    pub label: String,
+    /// Key binding (or kind) shown on the right side of the entry.
+    pub hint: String,
+    pub command: PaletteCommand,
+}
+
+impl PaletteEntry {
+    pub fn new(label: String, hint: String, command: PaletteCommand) -> PaletteEntry {
+        PaletteEntry {
+            label,
+            hint,
+            command,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Palette {
+    pub query: String,
+    entries: Vec<PaletteEntry>,
+    /// Indexes of the entries matching the query, best match first.
+    matches: Vec<usize>,
+    selected: usize,
+}
+
+impl Palette {
+    pub fn open(&mut self, entries: Vec<PaletteEntry>) {
+        self.entries = entries;
+        self.query.clear();
+        self.filter();
+    }
+
+    pub fn close(&mut self) {
+        self.entries.clear();
+        self.matches.clear();
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    pub fn push(&mut self, text: &str) {
+        let before = self.query.len();
+        self.query
+            .extend(text.chars().filter(|character| !character.is_control()));
+        if self.query.len() != before {
+            self.filter();
+        }
+    }
+
+    pub fn pop(&mut self) {
+        if self.query.pop().is_some() {
+            self.filter();
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.matches.is_empty() {
+            self.selected = (self.selected + 1) % self.matches.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.matches.is_empty() {
+            self.selected = self
+                .selected
+                .checked_sub(1)
+                .unwrap_or(self.matches.len() - 1);
+        }
+    }
+
+    #[inline]
+    pub fn selected(&self) -> Option<&PaletteEntry> {
+        self.matches
+            .get(self.selected)
+            .map(|index| &self.entries[*index])
+    }
+
+    /// Closes the palette, returning the command of the selected entry.
+    pub fn take_selected(&mut self) -> Option<PaletteCommand> {
+        let command = self.selected().map(|entry| entry.command.clone());
+        self.close();
+        command
+    }
+
+    /// Entries that fit in the palette, scrolled to keep the selected one
+    /// visible. The flag tells which one is selected.
+    pub fn visible(&self) -> impl Iterator<Item = (bool, &PaletteEntry)> {
+        let start = (self.selected + 1).saturating_sub(MAX_VISIBLE_ENTRIES);
+        self.matches
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(MAX_VISIBLE_ENTRIES)
+            .map(|(position, index)| (position == self.selected, &self.entries[*index]))
+    }
+
+    fn filter(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                fuzzy_score(&self.query, &entry.label).map(|score| (score, index))
+            })
+            .collect();
+        // Stable, so entries with the same score keep their order
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.selected = 0;
+    }
+}
+
+/// Scores `text` against `query` when every character of the query
+/// (spaces aside) shows up in order. Consecutive characters and the ones
+/// starting a word are worth more, so `tsz` ranks `ToggleSplitZoom` first.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for query_char in query.chars().filter(|character| !character.is_whitespace()) {
+        let query_char = query_char.to_ascii_lowercase();
+        let found = (position..text.len())
+            .find(|index| text[*index].to_ascii_lowercase() == query_char)?;
+
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == found) {
+            score += 5;
+        }
+        let word_start = found == 0
+            || !text[found - 1].is_alphanumeric()
+            || (text[found].is_uppercase() && text[found - 1].is_lowercase());
+        if word_start {
+            score += 8;
+        }
+        // Gaps between matches make it worse
+        score -= (found - position) as i64 / 4;
+
+        previous_match = Some(found);
+        position = found + 1;
+    }
+
+    Some(score)
+}
+
+#[inline]
+pub fn screen(
+    objects: &mut Vec<Object>,
+    colors: &Colors,
+    dimensions: (f32, f32, f32),
+    palette: &Palette,
+) {
+    let (width, height, scale) = dimensions;
+    let (width, height) = (width / scale, height / scale);
+    let panel_width = PANEL_MAX_WIDTH.min(width - 40.).max(100.);
+    let position_x = (width - panel_width) / 2.;
+    let position_y = 40.;
+    let visible_entries = palette.matches.len().min(MAX_VISIBLE_ENTRIES);
+    let panel_height =
+        (ENTRY_HEIGHT * (visible_entries + 1) as f32 + 10.).min(height - position_y);
+
+    objects.push(Object::Rect(Rect {
+        position: [position_x, position_y],
+        color: colors.bar,
+        size: [panel_width, panel_height],
+    }));
+
+    let dim_foreground = [
+        colors.foreground[0],
+        colors.foreground[1],
+        colors.foreground[2],
+        colors.foreground[3] - 0.3,
+    ];
+
+    let (query, query_color) = if palette.query.is_empty() {
+        (
+            String::from("> type to search actions, tabs and themes..."),
+            dim_foreground,
+        )
+    } else {
+        (format!("> {}", palette.query), colors.foreground)
+    };
+    objects.push(Object::Text(Text::single_line(
+        (position_x + 8., position_y + 8.),
+        query,
+        14.,
+        query_color,
+    )));
+
+    let mut entry_y = position_y + ENTRY_HEIGHT + 5.;
+    for (selected, entry) in palette.visible() {
+        let foreground = if selected {
+            objects.push(Object::Rect(Rect {
+                position: [position_x, entry_y],
+                color: colors.tabs_active,
+                size: [panel_width, ENTRY_HEIGHT],
+            }));
+            colors.tabs_active_foreground
+        } else {
+            colors.foreground
+        };
+
+        objects.push(Object::Text(Text::single_line(
+            (position_x + 8., entry_y + 4.),
+            entry.label.to_owned(),
+            14.,
+            foreground,
+        )));
+        if !entry.hint.is_empty() {
+            objects.push(Object::Text(Text::single_line(
+                (position_x + panel_width - 160., entry_y + 4.),
+                entry.hint.to_owned(),
+                12.,
+                dim_foreground,
+            )));
+        }
+
+        entry_y += ENTRY_HEIGHT;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(label: &str) -> PaletteEntry {
+        PaletteEntry::new(
+            label.to_string(),
+            String::new(),
+            PaletteCommand::Theme(label.to_string()),
+        )
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("", "Quit").is_some());
+        assert!(fuzzy_score("tsz", "ToggleSplitZoom").is_some());
+        assert!(fuzzy_score("split zoom", "ToggleSplitZoom").is_some());
+        assert!(fuzzy_score("zst", "ToggleSplitZoom").is_none());
+
+        // Word starts beat scattered characters
+        assert!(
+            fuzzy_score("tsz", "ToggleSplitZoom").unwrap()
+                > fuzzy_score("tsz", "TabsizeZ").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_palette_selection() {
+        let mut palette = Palette::default();
+        palette.open(vec![entry("SplitRight"), entry("SplitDown"), entry("Quit")]);
+        assert_eq!(palette.visible().count(), 3);
+        assert_eq!(palette.selected().unwrap().label, "SplitRight");
+
+        palette.select_prev();
+        assert_eq!(palette.selected().unwrap().label, "Quit");
+        palette.select_next();
+        assert_eq!(palette.selected().unwrap().label, "SplitRight");
+
+        palette.push("sd");
+        assert_eq!(palette.visible().count(), 1);
+        assert_eq!(palette.selected().unwrap().label, "SplitDown");
+        palette.pop();
+        assert_eq!(palette.visible().count(), 2);
+
+        palette.push("xyz");
+        assert_eq!(palette.take_selected(), None);
+
+        palette.open(vec![entry("Quit")]);
+        assert_eq!(
+            palette.take_selected(),
+            Some(PaletteCommand::Theme(String::from("Quit")))
+        );
+        assert_eq!(palette.visible().count(), 0);
+    }
+}
@@ -27,6 +27,9 @@ pub enum Topic {
     RenderRoute,
     UpdateConfig,
     CursorBlinking,
+    MonitorSilence,
+    VisualBell,
+    KeyTables,
 }
 
 /// Event scheduled to be emitted at a specific time.
@@ -9,14 +9,16 @@
 pub mod hint;
 pub mod touch;
 
-use crate::bindings::kitty_keyboard::build_key_sequence;
+use crate::bindings::conditions::ConditionState;
+use crate::bindings::key_input::{key_input, key_modifiers};
+use crate::bindings::key_tables::{Chord, KeyTables, SequenceMatch};
 use crate::bindings::{
-    Action as Act, BindingKey, BindingMode, FontSizeAction, MouseBinding, SearchAction,
-    ViAction,
+    Action as Act, BindingKey, BindingMode, FontSizeAction, MouseAction, MouseBinding,
+    SearchAction, ViAction, NAMED_ACTIONS,
 };
 #[cfg(target_os = "macos")]
 use crate::constants::{DEADZONE_END_Y, DEADZONE_START_Y};
-use crate::context::grid::{ContextDimension, Delta};
+use crate::context::grid::{ContextDimension, Delta, SplitDirection};
 use crate::context::renderable::{Cursor, RenderableContent};
 use crate::context::{self, process_open_url, ContextManager};
 use crate::crosswords::{
@@ -26,11 +28,12 @@ use crate::crosswords::{
     vi_mode::ViMotion,
     Mode,
 };
-use crate::mouse::{calculate_mouse_position, Mouse};
+use crate::mouse::{calculate_mouse_position, Mouse, SplitBorderDrag};
 use crate::renderer::{
     utils::{padding_bottom_from_config, padding_top_from_config},
     Renderer,
 };
+use crate::router::routes::palette::{self, Palette, PaletteCommand, PaletteEntry};
 use crate::screen::hint::HintMatches;
 use crate::selection::{Selection, SelectionType};
 use core::fmt::Debug;
@@ -40,10 +43,14 @@ use rio_backend::clipboard::ClipboardType;
 use rio_backend::config::{
     colors::term::List,
     renderer::{Backend as RendererBackend, Performance as RendererPerformance},
+    scrollback_editor::ScrollbackEditorTarget,
 };
+use rio_backend::crosswords::export::ExportStyle;
 use rio_backend::crosswords::pos::{Boundary, CursorState, Direction, Line};
 use rio_backend::crosswords::search::RegexSearch;
 use rio_backend::event::{ClickState, EventProxy, SearchState};
+use rio_backend::keyboard::{build_key_sequence, encode_key};
+use rio_backend::performer::player::PlayerMsg;
 use rio_backend::sugarloaf::{
     layout::RootStyle, Sugarloaf, SugarloafErrors, SugarloafRenderer, SugarloafWindow,
     SugarloafWindowSize,
@@ -53,13 +60,15 @@ use rio_window::event::Modifiers;
 use rio_window::event::MouseButton;
 #[cfg(target_os = "macos")]
 use rio_window::keyboard::ModifiersKeyState;
-use rio_window::keyboard::{Key, KeyLocation, ModifiersState, NamedKey};
+use rio_window::keyboard::{Key, ModifiersState, NamedKey};
 use rio_window::platform::modifier_supplement::KeyEventExtModifierSupplement;
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::cmp::{max, min};
 use std::error::Error;
 use std::ffi::OsStr;
 use std::rc::Rc;
+use std::time::Instant;
 use touch::TouchPurpose;
 
 /// Minimum number of pixels at the bottom/top where selection scrolling is performed.
@@ -77,6 +86,7 @@ const MAX_SEARCH_HISTORY_SIZE: usize = 255;
 pub struct Screen<'screen> {
     bindings: crate::bindings::KeyBindings,
     mouse_bindings: Vec<MouseBinding>,
+    key_tables: KeyTables,
     pub modifiers: Modifiers,
     pub mouse: Mouse,
     pub touchpurpose: TouchPurpose,
@@ -102,6 +112,7 @@ impl Screen<'_> {
         event_proxy: EventProxy,
         font_library: &rio_backend::sugarloaf::font::FontLibrary,
         open_url: Option<String>,
+        layout: Option<String>,
         clipboard: Rc<RefCell<Clipboard>>,
     ) -> Result<Screen<'screen>, Box<dyn Error>> {
         let size = window_properties.size;
@@ -197,6 +208,8 @@ impl Screen<'_> {
             use_current_path: config.navigation.use_current_path,
             shell,
             working_dir,
+            #[cfg(not(target_os = "windows"))]
+            env: crate::ipc::shell_env(),
             spawn_performer: true,
             #[cfg(not(target_os = "windows"))]
             use_fork: config.use_fork,
@@ -205,10 +218,33 @@ impl Screen<'_> {
             // does not make sense fetch for foreground process names/path
             should_update_title_extra: !config.navigation.color_automation.is_empty(),
             split_color: config.colors.split,
+            broadcast_color: config.colors.broadcast,
             title: config.title.clone(),
+            layouts: config.layouts.clone(),
+            record: config.record.clone(),
+            export: config.export.clone(),
+            export_style: ExportStyle::from(config),
+            triggers: config.triggers.clone(),
+            highlight: config.highlight.enabled,
+            copy_timestamps: config.timestamps.copy,
+            monitor: config.monitor.clone(),
+            monitor_output: std::sync::Arc::default(),
+            bell: config.bell.clone(),
+            editor: config.editor.clone(),
+            scrollback_editor: config.scrollback_editor.clone(),
         };
 
-        let rich_text_id = sugarloaf.create_rich_text();
+        let layout = layout.and_then(|name| match config.layouts.get(&name) {
+            Some(layout) if !layout.panes.is_empty() && !is_native => Some(layout),
+            _ => {
+                tracing::warn!("layout {name} not found");
+                None
+            }
+        });
+        let panes = layout.map_or(1, |layout| layout.panes.len());
+        let rich_text_ids: Vec<usize> =
+            (0..panes).map(|_| sugarloaf.create_rich_text()).collect();
+        let rich_text_id = rich_text_ids[0];
 
         let margin = Delta {
             x: config.padding_x,
@@ -236,7 +272,8 @@ impl Screen<'_> {
             event_proxy,
             window_id,
             0,
-            rich_text_id,
+            &rich_text_ids,
+            layout,
             context_manager_config,
             context_dimension,
             margin,
@@ -256,7 +293,9 @@ impl Screen<'_> {
 
         Ok(Screen {
             search_state: SearchState::default(),
-            mouse_bindings: crate::bindings::default_mouse_bindings(),
+            mouse_bindings: crate::bindings::default_mouse_bindings(
+                config.bindings.mouse.to_owned(),
+            ),
             modifiers: Modifiers::default(),
             context_manager,
             sugarloaf,
@@ -264,6 +303,7 @@ impl Screen<'_> {
             touchpurpose: TouchPurpose::default(),
             renderer,
             bindings,
+            key_tables: KeyTables::new(config.bindings.timeout),
             clipboard,
         })
     }
@@ -304,18 +344,86 @@ impl Screen<'_> {
         }
     }
 
+    /// Returns the border between splits under the mouse (if any).
+    #[inline]
+    pub fn split_border_under_mouse(&self) -> Option<SplitDirection> {
+        self.context_manager
+            .current_grid()
+            .border_at(self.mouse.x as f32, self.mouse.y as f32)
+            .map(|(_, edge)| edge)
+    }
+
+    /// Starts dragging the border under the mouse, returns false if
+    /// the mouse isn't placed over a border between splits.
+    pub fn start_split_border_drag(&mut self) -> bool {
+        match self
+            .context_manager
+            .current_grid()
+            .border_at(self.mouse.x as f32, self.mouse.y as f32)
+        {
+            Some((index, edge)) => {
+                self.mouse.split_border_drag = Some(SplitBorderDrag {
+                    index,
+                    edge,
+                    x: self.mouse.x,
+                    y: self.mouse.y,
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn update_split_border_drag(&mut self) -> bool {
+        let mut drag = match self.mouse.split_border_drag {
+            Some(drag) => drag,
+            None => return false,
+        };
+
+        let delta = match drag.edge {
+            SplitDirection::Left | SplitDirection::Right => {
+                self.mouse.x as f32 - drag.x as f32
+            }
+            SplitDirection::Up | SplitDirection::Down => {
+                self.mouse.y as f32 - drag.y as f32
+            }
+        };
+
+        if self
+            .context_manager
+            .current_grid_mut()
+            .move_border(drag.index, drag.edge, delta)
+        {
+            drag.x = self.mouse.x;
+            drag.y = self.mouse.y;
+            self.mouse.split_border_drag = Some(drag);
+            self.render();
+        }
+
+        true
+    }
+
+    #[inline]
+    pub fn stop_split_border_drag(&mut self) -> bool {
+        self.mouse.split_border_drag.take().is_some()
+    }
+
     #[inline]
     pub fn mouse_position(&self, display_offset: usize) -> Pos {
         let current_grid = self.context_manager.current_grid();
         let (context, margin) = current_grid.current_context_with_computed_dimension();
         let context_dimension = context.dimension;
         let style = self.sugarloaf.style();
+        // The timestamps gutter sits between the margin and the grid
+        let gutter_width = context_dimension.gutter as f32
+            * context_dimension.dimension.width
+            / context_dimension.dimension.scale;
         calculate_mouse_position(
             &self.mouse,
             display_offset,
             style.scale_factor,
             (context_dimension.columns, context_dimension.lines),
-            margin.x,
+            margin.x + gutter_width,
             margin.top_y,
             (
                 context_dimension.dimension.width,
@@ -371,6 +479,7 @@ impl Screen<'_> {
         self.sugarloaf
             .update_filters(config.renderer.filters.as_slice());
         self.renderer = Renderer::new(config, font_library);
+        self.key_tables.set_timeout(config.bindings.timeout);
 
         for context_grid in self.context_manager.contexts_mut() {
             context_grid.update_line_height(config.line_height);
@@ -393,10 +502,12 @@ impl Screen<'_> {
                 let mut terminal = current_context.terminal.lock();
                 current_context.renderable_content =
                     RenderableContent::from_cursor_config(&config.cursor);
+                current_context.highlight.clear();
                 let shape = config.cursor.shape;
                 terminal.cursor_shape = shape;
                 terminal.default_cursor_shape = shape;
                 terminal.blinking_cursor = config.cursor.blinking;
+                terminal.set_triggers(&config.triggers);
                 drop(terminal);
             }
         }
@@ -404,6 +515,19 @@ impl Screen<'_> {
         self.mouse
             .set_multiplier_and_divider(config.scroll.multiplier, config.scroll.divider);
 
+        self.context_manager.config.layouts = config.layouts.clone();
+        self.context_manager.config.broadcast_color = config.colors.broadcast;
+        self.context_manager.config.record = config.record.clone();
+        self.context_manager.config.export = config.export.clone();
+        self.context_manager.config.export_style = ExportStyle::from(config);
+        self.context_manager.config.triggers = config.triggers.clone();
+        self.context_manager.config.copy_timestamps = config.timestamps.copy;
+        self.context_manager.config.monitor = config.monitor.clone();
+        self.context_manager.update_monitor_output();
+        self.context_manager.config.bell = config.bell.clone();
+        self.context_manager.config.editor = config.editor.clone();
+        self.context_manager.config.scrollback_editor = config.scrollback_editor.clone();
+
         if cfg!(target_os = "macos") {
             self.sugarloaf.set_background_color(None);
         } else {
@@ -488,9 +612,11 @@ impl Screen<'_> {
         for context_grid in self.context_manager.contexts_mut() {
             for context in context_grid.contexts_mut() {
                 let ctx = context.context_mut();
-                let mut terminal = ctx.terminal.lock();
-                terminal.resize::<ContextDimension>(ctx.dimension);
-                drop(terminal);
+                if !ctx.playback_sized {
+                    let mut terminal = ctx.terminal.lock();
+                    terminal.resize::<ContextDimension>(ctx.dimension);
+                    drop(terminal);
+                }
                 let winsize = crate::renderer::utils::terminal_dimensions(&ctx.dimension);
                 let _ = ctx.messenger.send_resize(winsize);
             }
@@ -547,10 +673,7 @@ impl Screen<'_> {
         let mods = self.modifiers.state();
 
         if key.state == ElementState::Released {
-            if !mode.contains(Mode::REPORT_EVENT_TYPES)
-                || mode.contains(Mode::VI)
-                || self.search_active()
-            {
+            if self.search_active() {
                 return;
             }
 
@@ -562,18 +685,27 @@ impl Screen<'_> {
                 mods & !ModifiersState::ALT
             };
 
-            let bytes = match key.logical_key.as_ref() {
-                Key::Named(NamedKey::Enter)
-                | Key::Named(NamedKey::Tab)
-                | Key::Named(NamedKey::Backspace)
-                    if !mode.contains(Mode::REPORT_ALL_KEYS_AS_ESC) =>
-                {
-                    return
+            // Only splits reporting event types get releases
+            self.context_manager.send_encoded_input(|mode| {
+                if !mode.contains(Mode::REPORT_EVENT_TYPES) || mode.contains(Mode::VI) {
+                    return Cow::Borrowed(&[]);
                 }
-                _ => build_key_sequence(key, mods, mode),
-            };
 
-            self.ctx_mut().current_mut().messenger.send_write(bytes);
+                match key.logical_key.as_ref() {
+                    Key::Named(NamedKey::Enter)
+                    | Key::Named(NamedKey::Tab)
+                    | Key::Named(NamedKey::Backspace)
+                        if !mode.contains(Mode::REPORT_ALL_KEYS_AS_ESC) =>
+                    {
+                        Cow::Borrowed(&[])
+                    }
+                    _ => Cow::Owned(build_key_sequence(
+                        &key_input(key),
+                        key_modifiers(mods),
+                        mode,
+                    )),
+                }
+            });
 
             return;
         }
@@ -599,58 +731,40 @@ impl Screen<'_> {
             return;
         }
 
-        let build_key_sequence = Self::should_build_sequence(key, text, mode, mods);
-
-        let bytes = if build_key_sequence {
-            crate::bindings::kitty_keyboard::build_key_sequence(key, mods, mode)
-        } else {
-            let mut bytes = Vec::with_capacity(text.len() + 1);
-            if mods.alt_key() {
-                bytes.push(b'\x1b');
-            }
-
-            bytes.extend_from_slice(text.as_bytes());
-            bytes
+        // Encoded with the mode of each split the input is written to
+        let input = key_input(key);
+        let input_mods = key_modifiers(mods);
+        let encode = |mode: Mode| -> Cow<'static, [u8]> {
+            Cow::Owned(encode_key(&input, input_mods, mode))
         };
 
-        if !bytes.is_empty() {
+        if !encode(mode).is_empty() {
             self.scroll_bottom_when_cursor_not_visible();
             self.clear_selection();
 
-            self.ctx_mut().current_mut().messenger.send_bytes(bytes);
-        }
-    }
-
-    /// Check whether we should try to build escape sequence for the [`KeyEvent`].
-    fn should_build_sequence(
-        key: &rio_window::event::KeyEvent,
-        text: &str,
-        mode: Mode,
-        mods: ModifiersState,
-    ) -> bool {
-        if mode.contains(Mode::REPORT_ALL_KEYS_AS_ESC) {
-            return true;
-        }
-
-        let disambiguate = mode.contains(Mode::DISAMBIGUATE_ESC_CODES)
-            && (key.logical_key == Key::Named(NamedKey::Escape)
-                || (!mods.is_empty() && mods != ModifiersState::SHIFT)
-                || key.location == KeyLocation::Numpad);
-
-        match key.logical_key {
-            _ if disambiguate => true,
-            // Exclude all the named keys unless they have textual representation.
-            Key::Named(named) => named.to_text().is_none(),
-            _ => text.is_empty(),
+            self.context_manager.send_encoded_input(encode);
         }
     }
 
+    /// Runs the mouse bindings of the button, returns true if any of them
+    /// was triggered.
     #[inline]
-    pub fn process_mouse_bindings(&mut self, button: MouseButton) {
+    pub fn process_mouse_bindings(&mut self, button: MouseButton) -> bool {
         let mode = self.get_mode();
-        let binding_mode = BindingMode::new(&mode, self.search_active());
+        let binding_mode = BindingMode::new(
+            &mode,
+            self.search_active(),
+            self.context_manager.current().playback.is_some(),
+        );
         let mouse_mode = self.mouse_mode();
         let mods = self.modifiers.state();
+        let clicks = match self.mouse.click_state {
+            ClickState::DoubleClick => 2,
+            ClickState::TripleClick => 3,
+            ClickState::Click | ClickState::None => 1,
+        };
+        let conditions = self.binding_conditions(&mode);
+        let mut triggered = false;
 
         for i in 0..self.mouse_bindings.len() {
             let mut binding = self.mouse_bindings[i].clone();
@@ -660,13 +774,15 @@ impl Screen<'_> {
                 binding.mods |= ModifiersState::SHIFT;
             }
 
-            if binding.is_triggered_by(binding_mode.to_owned(), mods, &button)
-                && binding.action == Act::PasteSelection
+            if binding.is_triggered_by_click(&binding_mode, mods, button, clicks)
+                && conditions.matches(&binding.when)
             {
-                let content = self.clipboard.borrow_mut().get(ClipboardType::Selection);
-                self.paste(&content, true);
+                triggered |= binding.action != Act::ReceiveChar;
+                self.run_action(&binding.action);
             }
         }
+
+        triggered
     }
 
     pub fn process_key_bindings(
@@ -676,37 +792,48 @@ impl Screen<'_> {
         mods: ModifiersState,
     ) -> bool {
         let search_active = self.search_active();
-        let binding_mode = BindingMode::new(mode, search_active);
+        let playback = self.context_manager.current().playback.is_some();
+        let binding_mode = BindingMode::new(mode, search_active, playback);
+        let conditions = self.binding_conditions(mode);
         let mut ignore_chars = None;
 
+        // We don't want the key without modifier, because it means something else most of
+        // the time. However what we want is to manually lowercase the character to account
+        // for both small and capital letters on regular characters at the same time.
+        let logical_key = if let Key::Character(ch) = key.logical_key.as_ref() {
+            // Match `Alt` bindings without `Alt` being applied, otherwise they use the
+            // composed chars, which are not intuitive to bind.
+            //
+            // On Windows, the `Ctrl + Alt` mangles `logical_key` to unidentified values, thus
+            // preventing them from being used in bindings
+            //
+            // For more see https://github.com/rust-windowing/winit/issues/2945.
+            // if (cfg!(target_os = "macos") || (cfg!(windows) && mods.control_key()))
+            //     && mods.alt_key()
+            if (mods.shift_key() || mods.alt_key())
+                || mods.alt_key() && (cfg!(windows) && mods.control_key())
+            {
+                key.key_without_modifiers()
+            } else {
+                Key::Character(ch.to_lowercase().into())
+            }
+        } else {
+            key.logical_key.clone()
+        };
+
+        if self.process_key_tables(key, &logical_key, mods, &binding_mode, &conditions) {
+            return true;
+        }
+
         for i in 0..self.bindings.len() {
             let binding = &self.bindings[i];
 
-            // We don't want the key without modifier, because it means something else most of
-            // the time. However what we want is to manually lowercase the character to account
-            // for both small and capital letters on regular characters at the same time.
-            let logical_key = if let Key::Character(ch) = key.logical_key.as_ref() {
-                // Match `Alt` bindings without `Alt` being applied, otherwise they use the
-                // composed chars, which are not intuitive to bind.
-                //
-                // On Windows, the `Ctrl + Alt` mangles `logical_key` to unidentified values, thus
-                // preventing them from being used in bindings
-                //
-                // For more see https://github.com/rust-windowing/winit/issues/2945.
-                // if (cfg!(target_os = "macos") || (cfg!(windows) && mods.control_key()))
-                //     && mods.alt_key()
-                if (mods.shift_key() || mods.alt_key())
-                    || mods.alt_key() && (cfg!(windows) && mods.control_key())
-                {
-                    key.key_without_modifiers()
-                } else {
-                    Key::Character(ch.to_lowercase().into())
-                }
-            } else {
-                key.logical_key.clone()
-            };
+            // Sequences and key tables were handled above
+            if !binding.prefix.is_empty() || binding.table.is_some() {
+                continue;
+            }
 
-            let key_match = match (&binding.trigger, logical_key) {
+            let key_match = match (&binding.trigger, logical_key.clone()) {
                 (BindingKey::Scancode(_), _) => BindingKey::Scancode(key.physical_key),
                 (_, code) => BindingKey::Keycode {
                     key: code,
@@ -714,328 +841,14 @@ impl Screen<'_> {
                 },
             };
 
-            if binding.is_triggered_by(binding_mode.to_owned(), mods, &key_match) {
+            if binding.is_triggered_by(binding_mode.to_owned(), mods, &key_match)
+                && conditions.matches(&binding.when)
+            {
                 *ignore_chars.get_or_insert(true) &= binding.action != Act::ReceiveChar;
 
-                match &binding.action {
-                    Act::Run(program) => self.exec(program.program(), program.args()),
-                    Act::Esc(s) => {
-                        let current_context = self.context_manager.current_mut();
-                        current_context.set_selection(None);
-                        let mut terminal = current_context.terminal.lock();
-                        terminal.selection.take();
-                        terminal.scroll_display(Scroll::Bottom);
-                        drop(terminal);
-                        current_context
-                            .messenger
-                            .send_bytes(s.to_owned().into_bytes());
-                    }
-                    Act::Paste => {
-                        let content =
-                            self.clipboard.borrow_mut().get(ClipboardType::Clipboard);
-                        self.paste(&content, true);
-                    }
-                    Act::ClearSelection => {
-                        self.clear_selection();
-                    }
-                    Act::PasteSelection => {
-                        let content =
-                            self.clipboard.borrow_mut().get(ClipboardType::Selection);
-                        self.paste(&content, true);
-                    }
-                    Act::Copy => {
-                        self.copy_selection(ClipboardType::Clipboard);
-                    }
-                    Act::SearchForward => {
-                        self.start_search(Direction::Right);
-                        self.resize_top_or_bottom_line(self.ctx().len());
-                        self.render();
-                    }
-                    Act::SearchBackward => {
-                        self.start_search(Direction::Left);
-                        self.resize_top_or_bottom_line(self.ctx().len());
-                        self.render();
-                    }
-                    Act::Search(SearchAction::SearchConfirm) => {
-                        self.confirm_search();
-                        self.resize_top_or_bottom_line(self.ctx().len());
-                        self.render();
-                    }
-                    Act::Search(SearchAction::SearchCancel) => {
-                        self.cancel_search();
-                        self.resize_top_or_bottom_line(self.ctx().len());
-                        self.render();
-                    }
-                    Act::Search(SearchAction::SearchClear) => {
-                        let direction = self.search_state.direction;
-                        self.cancel_search();
-                        self.start_search(direction);
-                        self.resize_top_or_bottom_line(self.ctx().len());
-                        self.render();
-                    }
-                    Act::Search(SearchAction::SearchFocusNext) => {
-                        self.advance_search_origin(self.search_state.direction);
-                        self.resize_top_or_bottom_line(self.ctx().len());
-                        self.render();
-                    }
-                    Act::Search(SearchAction::SearchFocusPrevious) => {
-                        let direction = self.search_state.direction.opposite();
-                        self.advance_search_origin(direction);
-                        self.resize_top_or_bottom_line(self.ctx().len());
-                        self.render();
-                    }
-                    Act::Search(SearchAction::SearchDeleteWord) => {
-                        self.search_pop_word();
-                        self.render();
-                    }
-                    Act::Search(SearchAction::SearchHistoryPrevious) => {
-                        self.search_history_previous();
-                        self.render();
-                    }
-                    Act::Search(SearchAction::SearchHistoryNext) => {
-                        self.search_history_next();
-                        self.render();
-                    }
-                    Act::ToggleViMode => {
-                        let mut terminal =
-                            self.context_manager.current_mut().terminal.lock();
-                        terminal.toggle_vi_mode();
-                        let has_vi_mode_enabled = terminal.mode().contains(Mode::VI);
-                        drop(terminal);
-                        self.renderer.set_vi_mode(has_vi_mode_enabled);
-                        self.render();
-                    }
-                    Act::ViMotion(motion) => {
-                        let current_context = self.context_manager.current_mut();
-                        let mut terminal = current_context.terminal.lock();
-                        if terminal.mode().contains(Mode::VI) {
-                            terminal.vi_motion(*motion);
-                        }
-
-                        if let Some(selection) = &terminal.selection {
-                            current_context.renderable_content.selection_range =
-                                selection.to_range(&terminal);
-                        };
-                        drop(terminal);
-                        self.render();
-                    }
-                    Act::Vi(ViAction::CenterAroundViCursor) => {
-                        let mut terminal =
-                            self.context_manager.current_mut().terminal.lock();
-                        let display_offset = terminal.display_offset() as i32;
-                        let target =
-                            -display_offset + terminal.grid.screen_lines() as i32 / 2 - 1;
-                        let line = terminal.vi_mode_cursor.pos.row;
-                        let scroll_lines = target - line.0;
-
-                        terminal.scroll_display(Scroll::Delta(scroll_lines));
-                        drop(terminal);
-                    }
-                    Act::Vi(ViAction::ToggleNormalSelection) => {
-                        self.toggle_selection(SelectionType::Simple, Side::Left);
-                        self.render();
-                    }
-                    Act::Vi(ViAction::ToggleLineSelection) => {
-                        self.toggle_selection(SelectionType::Lines, Side::Left);
-                        self.render();
-                    }
-                    Act::Vi(ViAction::ToggleBlockSelection) => {
-                        self.toggle_selection(SelectionType::Block, Side::Left);
-                        self.render();
-                    }
-                    Act::Vi(ViAction::ToggleSemanticSelection) => {
-                        self.toggle_selection(SelectionType::Semantic, Side::Left);
-                        self.render();
-                    }
-                    Act::SplitRight => {
-                        self.split_right();
-                    }
-                    Act::SplitDown => {
-                        self.split_down();
-                    }
-                    Act::ConfigEditor => {
-                        self.context_manager.switch_to_settings();
-                    }
-                    Act::WindowCreateNew => {
-                        self.context_manager.create_new_window();
-                    }
-                    Act::CloseCurrentSplitOrTab => {
-                        self.close_split_or_tab();
-                    }
-                    Act::TabCreateNew => {
-                        self.create_tab();
-                    }
-                    Act::TabCloseCurrent => {
-                        self.close_tab();
-                    }
-                    Act::TabCloseUnfocused => {
-                        self.clear_selection();
-                        self.cancel_search();
-                        if self.ctx().len() <= 1 {
-                            return true;
-                        }
-                        self.context_manager.close_unfocused_tabs();
-                        self.resize_top_or_bottom_line(1);
-                        self.render();
-                    }
-                    Act::Quit => {
-                        self.context_manager.quit();
-                    }
-                    Act::IncreaseFontSize => {
-                        self.change_font_size(FontSizeAction::Increase);
-                    }
-                    Act::DecreaseFontSize => {
-                        self.change_font_size(FontSizeAction::Decrease);
-                    }
-                    Act::ResetFontSize => {
-                        self.change_font_size(FontSizeAction::Reset);
-                    }
-                    Act::ScrollPageUp => {
-                        // Move vi mode cursor.
-                        let mut terminal =
-                            self.context_manager.current_mut().terminal.lock();
-                        let scroll_lines = terminal.grid.screen_lines() as i32;
-                        terminal.vi_mode_cursor =
-                            terminal.vi_mode_cursor.scroll(&terminal, scroll_lines);
-                        terminal.scroll_display(Scroll::PageUp);
-                        drop(terminal);
-                        self.render();
-                    }
-                    Act::ScrollPageDown => {
-                        // Move vi mode cursor.
-                        let mut terminal =
-                            self.context_manager.current_mut().terminal.lock();
-                        let scroll_lines = -(terminal.grid.screen_lines() as i32);
-
-                        terminal.vi_mode_cursor =
-                            terminal.vi_mode_cursor.scroll(&terminal, scroll_lines);
-
-                        terminal.scroll_display(Scroll::PageDown);
-                        drop(terminal);
-                        self.render();
-                    }
-                    Act::ScrollHalfPageUp => {
-                        // Move vi mode cursor.
-                        let mut terminal =
-                            self.context_manager.current_mut().terminal.lock();
-                        let scroll_lines = terminal.grid.screen_lines() as i32 / 2;
-
-                        terminal.vi_mode_cursor =
-                            terminal.vi_mode_cursor.scroll(&terminal, scroll_lines);
-
-                        terminal.scroll_display(Scroll::Delta(scroll_lines));
-                        drop(terminal);
-                        self.render();
-                    }
-                    Act::ScrollHalfPageDown => {
-                        // Move vi mode cursor.
-                        let mut terminal =
-                            self.context_manager.current_mut().terminal.lock();
-                        let scroll_lines = -(terminal.grid.screen_lines() as i32 / 2);
-
-                        terminal.vi_mode_cursor =
-                            terminal.vi_mode_cursor.scroll(&terminal, scroll_lines);
-
-                        terminal.scroll_display(Scroll::Delta(scroll_lines));
-                        drop(terminal);
-                        self.render();
-                    }
-                    Act::ScrollToTop => {
-                        let mut terminal =
-                            self.context_manager.current_mut().terminal.lock();
-                        terminal.scroll_display(Scroll::Top);
-
-                        let topmost_line = terminal.grid.topmost_line();
-                        terminal.vi_mode_cursor.pos.row = topmost_line;
-                        terminal.vi_motion(ViMotion::FirstOccupied);
-                        drop(terminal);
-                        self.render();
-                    }
-                    Act::ScrollToBottom => {
-                        let mut terminal =
-                            self.context_manager.current_mut().terminal.lock();
-                        terminal.scroll_display(Scroll::Bottom);
-
-                        // Move vi mode cursor.
-                        terminal.vi_mode_cursor.pos.row = terminal.grid.bottommost_line();
-
-                        // Move to beginning twice, to always jump across linewraps.
-                        terminal.vi_motion(ViMotion::FirstOccupied);
-                        terminal.vi_motion(ViMotion::FirstOccupied);
-                        drop(terminal);
-                        self.render();
-                    }
-                    Act::Scroll(delta) => {
-                        let mut terminal =
-                            self.context_manager.current_mut().terminal.lock();
-                        terminal.scroll_display(Scroll::Delta(*delta));
-                        drop(terminal);
-                        self.render();
-                    }
-                    Act::ClearHistory => {
-                        let mut terminal =
-                            self.context_manager.current_mut().terminal.lock();
-                        terminal.clear_saved_history();
-                        drop(terminal);
-                        self.render();
-                    }
-                    Act::ToggleFullscreen => self.context_manager.toggle_full_screen(),
-                    Act::Minimize => {
-                        self.context_manager.minimize();
-                    }
-                    Act::Hide => {
-                        self.context_manager.hide();
-                    }
-                    #[cfg(target_os = "macos")]
-                    Act::HideOtherApplications => {
-                        self.context_manager.hide_other_apps();
-                    }
-                    Act::SelectNextSplit => {
-                        self.cancel_search();
-                        self.context_manager.select_next_split();
-                        self.render();
-                    }
-                    Act::SelectPrevSplit => {
-                        self.cancel_search();
-                        self.context_manager.select_prev_split();
-                        self.render();
-                    }
-                    Act::SelectTab(tab_index) => {
-                        self.context_manager.select_tab(*tab_index);
-                        self.cancel_search();
-                        self.render();
-                    }
-                    Act::SelectLastTab => {
-                        self.cancel_search();
-                        self.context_manager.select_last_tab();
-                        self.render();
-                    }
-                    Act::SelectNextTab => {
-                        self.cancel_search();
-                        self.clear_selection();
-                        self.context_manager.switch_to_next();
-                        self.render();
-                    }
-                    Act::MoveCurrentTabToPrev => {
-                        self.cancel_search();
-                        self.clear_selection();
-                        self.context_manager.move_current_to_prev();
-                        self.render();
-                    }
-                    Act::MoveCurrentTabToNext => {
-                        self.cancel_search();
-                        self.clear_selection();
-                        self.context_manager.move_current_to_next();
-                        self.render();
-                    }
-                    Act::SelectPrevTab => {
-                        self.cancel_search();
-                        self.clear_selection();
-                        self.context_manager.switch_to_prev();
-                        self.render();
-                    }
-                    Act::ReceiveChar | Act::None => (),
-                    _ => (),
+                let action = binding.action.clone();
+                if self.run_action(&action) {
+                    return true;
                 }
             }
         }
@@ -1043,6 +856,570 @@ impl Screen<'_> {
         ignore_chars.unwrap_or(false)
     }
 
+    /// State of the focused split the `when` conditions of the bindings
+    /// are checked against.
+    fn binding_conditions(&self, mode: &Mode) -> ConditionState {
+        let grid = self.context_manager.current_grid();
+
+        #[cfg(unix)]
+        let lookup: Box<dyn Fn() -> String> = {
+            let (main_fd, shell_pid) =
+                (*grid.current().main_fd, grid.current().shell_pid);
+            Box::new(move || teletypewriter::foreground_process_name(main_fd, shell_pid))
+        };
+
+        #[cfg(not(unix))]
+        let lookup: Box<dyn Fn() -> String> = Box::new(String::default);
+
+        ConditionState::new(*mode, grid.current_index(), lookup)
+    }
+
+    /// Handles the key press for multi-key sequences and the bindings of
+    /// the active key table, returns true if it was consumed.
+    fn process_key_tables(
+        &mut self,
+        key: &rio_window::event::KeyEvent,
+        logical_key: &Key,
+        mods: ModifiersState,
+        binding_mode: &BindingMode,
+        conditions: &ConditionState,
+    ) -> bool {
+        self.key_tables.expire(Instant::now());
+
+        let mut inputs: Vec<Chord> = vec![(
+            mods,
+            BindingKey::Keycode {
+                key: logical_key.clone(),
+                location: key.location,
+            },
+        )];
+        // Keys typed with shift, like `|`, also match without it
+        if let Key::Character(ch) = key.logical_key.as_ref() {
+            let shifted = Key::Character(ch.to_lowercase().into());
+            if mods.shift_key() && shifted != *logical_key {
+                inputs.push((
+                    mods & !ModifiersState::SHIFT,
+                    BindingKey::Keycode {
+                        key: shifted,
+                        location: key.location,
+                    },
+                ));
+            }
+        }
+
+        match self
+            .key_tables
+            .matches(&self.bindings, &inputs, binding_mode, conditions)
+        {
+            SequenceMatch::Complete(index) => {
+                self.key_tables.clear_pending();
+                self.key_tables.touch();
+                let action = self.bindings[index].action.clone();
+                self.run_action(&action);
+                self.render();
+                return true;
+            }
+            SequenceMatch::Partial(chord) => {
+                self.key_tables.push_chord(chord);
+                self.render();
+                return true;
+            }
+            // Keys that don't continue the sequence are dropped
+            SequenceMatch::None if self.key_tables.is_pending() => {
+                self.key_tables.clear_pending();
+                self.render();
+                return true;
+            }
+            // Modifiers pressed to type the next chord keep the sequence
+            SequenceMatch::Modifier if self.key_tables.is_pending() => return true,
+            SequenceMatch::Modifier | SequenceMatch::None => {}
+        }
+
+        let Some(table) = self.key_tables.active() else {
+            return false;
+        };
+        let found = self.bindings.iter().find(|binding| {
+            binding.table.as_deref() == Some(table)
+                && binding.prefix.is_empty()
+                && binding.is_active(binding_mode, Some(table))
+                && conditions.matches(&binding.when)
+                && inputs
+                    .iter()
+                    .any(|(mods, key)| binding.mods == *mods && binding.trigger == *key)
+        });
+
+        if let Some(binding) = found {
+            let action = binding.action.clone();
+            self.key_tables.touch();
+            self.run_action(&action);
+            self.render();
+            return action != Act::ReceiveChar;
+        }
+
+        if key.logical_key == Key::Named(NamedKey::Escape) && mods.is_empty() {
+            self.key_tables.pop();
+            self.render();
+            return true;
+        }
+
+        false
+    }
+
+    /// Runs an action, either triggered by a key binding or picked from the
+    /// command palette. Returns true when the other bindings of the key
+    /// press must be skipped.
+    pub fn run_action(&mut self, action: &Act) -> bool {
+        match action {
+            Act::Run(program) => self.exec(program.program(), program.args()),
+            Act::Esc(s) => {
+                let current_context = self.context_manager.current_mut();
+                current_context.set_selection(None);
+                let mut terminal = current_context.terminal.lock();
+                terminal.selection.take();
+                terminal.scroll_display(Scroll::Bottom);
+                drop(terminal);
+                self.context_manager.send_input(s.to_owned().into_bytes());
+            }
+            Act::Paste => {
+                let content = self.clipboard.borrow_mut().get(ClipboardType::Clipboard);
+                self.paste(&content, true);
+            }
+            Act::ClearSelection => {
+                self.clear_selection();
+            }
+            Act::PasteSelection => {
+                let content = self.clipboard.borrow_mut().get(ClipboardType::Selection);
+                self.paste(&content, true);
+            }
+            Act::Copy => {
+                self.copy_selection(ClipboardType::Clipboard);
+            }
+            Act::SearchForward => {
+                self.start_search(Direction::Right);
+                self.resize_top_or_bottom_line(self.ctx().len());
+                self.render();
+            }
+            Act::SearchBackward => {
+                self.start_search(Direction::Left);
+                self.resize_top_or_bottom_line(self.ctx().len());
+                self.render();
+            }
+            Act::Search(SearchAction::SearchConfirm) => {
+                self.confirm_search();
+                self.resize_top_or_bottom_line(self.ctx().len());
+                self.render();
+            }
+            Act::Search(SearchAction::SearchCancel) => {
+                self.cancel_search();
+                self.resize_top_or_bottom_line(self.ctx().len());
+                self.render();
+            }
+            Act::Search(SearchAction::SearchClear) => {
+                let direction = self.search_state.direction;
+                self.cancel_search();
+                self.start_search(direction);
+                self.resize_top_or_bottom_line(self.ctx().len());
+                self.render();
+            }
+            Act::Search(SearchAction::SearchFocusNext) => {
+                self.advance_search_origin(self.search_state.direction);
+                self.resize_top_or_bottom_line(self.ctx().len());
+                self.render();
+            }
+            Act::Search(SearchAction::SearchFocusPrevious) => {
+                let direction = self.search_state.direction.opposite();
+                self.advance_search_origin(direction);
+                self.resize_top_or_bottom_line(self.ctx().len());
+                self.render();
+            }
+            Act::Search(SearchAction::SearchDeleteWord) => {
+                self.search_pop_word();
+                self.render();
+            }
+            Act::Search(SearchAction::SearchHistoryPrevious) => {
+                self.search_history_previous();
+                self.render();
+            }
+            Act::Search(SearchAction::SearchHistoryNext) => {
+                self.search_history_next();
+                self.render();
+            }
+            Act::ToggleViMode => {
+                let mut terminal = self.context_manager.current_mut().terminal.lock();
+                terminal.toggle_vi_mode();
+                let has_vi_mode_enabled = terminal.mode().contains(Mode::VI);
+                drop(terminal);
+                self.renderer.set_vi_mode(has_vi_mode_enabled);
+                self.render();
+            }
+            Act::ViMotion(motion) => {
+                let current_context = self.context_manager.current_mut();
+                let mut terminal = current_context.terminal.lock();
+                if terminal.mode().contains(Mode::VI) {
+                    terminal.vi_motion(*motion);
+                }
+
+                if let Some(selection) = &terminal.selection {
+                    current_context.renderable_content.selection_range =
+                        selection.to_range(&terminal);
+                };
+                drop(terminal);
+                self.render();
+            }
+            Act::Vi(ViAction::CenterAroundViCursor) => {
+                let mut terminal = self.context_manager.current_mut().terminal.lock();
+                let display_offset = terminal.display_offset() as i32;
+                let target =
+                    -display_offset + terminal.grid.screen_lines() as i32 / 2 - 1;
+                let line = terminal.vi_mode_cursor.pos.row;
+                let scroll_lines = target - line.0;
+
+                terminal.scroll_display(Scroll::Delta(scroll_lines));
+                drop(terminal);
+            }
+            Act::Vi(ViAction::ToggleNormalSelection) => {
+                self.toggle_selection(SelectionType::Simple, Side::Left);
+                self.render();
+            }
+            Act::Vi(ViAction::ToggleLineSelection) => {
+                self.toggle_selection(SelectionType::Lines, Side::Left);
+                self.render();
+            }
+            Act::Vi(ViAction::ToggleBlockSelection) => {
+                self.toggle_selection(SelectionType::Block, Side::Left);
+                self.render();
+            }
+            Act::Vi(ViAction::ToggleSemanticSelection) => {
+                self.toggle_selection(SelectionType::Semantic, Side::Left);
+                self.render();
+            }
+            Act::SplitRight => {
+                self.split_right();
+            }
+            Act::SplitDown => {
+                self.split_down();
+            }
+            Act::ConfigEditor => {
+                self.context_manager.switch_to_settings();
+            }
+            Act::WindowCreateNew => {
+                self.context_manager.create_new_window();
+            }
+            Act::CloseCurrentSplitOrTab => {
+                self.close_split_or_tab();
+            }
+            Act::TabCreateNew => {
+                self.create_tab();
+            }
+            Act::TabCreateNewWithLayout(layout) => {
+                let layout = layout.to_owned();
+                self.create_tab_from_layout(&layout);
+            }
+            Act::TabCloseCurrent => {
+                self.close_tab();
+            }
+            Act::TabCloseUnfocused => {
+                self.clear_selection();
+                self.cancel_search();
+                if self.ctx().len() <= 1 {
+                    return true;
+                }
+                self.context_manager.close_unfocused_tabs();
+                self.resize_top_or_bottom_line(1);
+                self.render();
+            }
+            Act::Quit => {
+                self.context_manager.quit();
+            }
+            Act::IncreaseFontSize => {
+                self.change_font_size(FontSizeAction::Increase);
+            }
+            Act::DecreaseFontSize => {
+                self.change_font_size(FontSizeAction::Decrease);
+            }
+            Act::ResetFontSize => {
+                self.change_font_size(FontSizeAction::Reset);
+            }
+            Act::ScrollPageUp => {
+                // Move vi mode cursor.
+                let mut terminal = self.context_manager.current_mut().terminal.lock();
+                let scroll_lines = terminal.grid.screen_lines() as i32;
+                terminal.vi_mode_cursor =
+                    terminal.vi_mode_cursor.scroll(&terminal, scroll_lines);
+                terminal.scroll_display(Scroll::PageUp);
+                drop(terminal);
+                self.render();
+            }
+            Act::ScrollPageDown => {
+                // Move vi mode cursor.
+                let mut terminal = self.context_manager.current_mut().terminal.lock();
+                let scroll_lines = -(terminal.grid.screen_lines() as i32);
+
+                terminal.vi_mode_cursor =
+                    terminal.vi_mode_cursor.scroll(&terminal, scroll_lines);
+
+                terminal.scroll_display(Scroll::PageDown);
+                drop(terminal);
+                self.render();
+            }
+            Act::ScrollHalfPageUp => {
+                // Move vi mode cursor.
+                let mut terminal = self.context_manager.current_mut().terminal.lock();
+                let scroll_lines = terminal.grid.screen_lines() as i32 / 2;
+
+                terminal.vi_mode_cursor =
+                    terminal.vi_mode_cursor.scroll(&terminal, scroll_lines);
+
+                terminal.scroll_display(Scroll::Delta(scroll_lines));
+                drop(terminal);
+                self.render();
+            }
+            Act::ScrollHalfPageDown => {
+                // Move vi mode cursor.
+                let mut terminal = self.context_manager.current_mut().terminal.lock();
+                let scroll_lines = -(terminal.grid.screen_lines() as i32 / 2);
+
+                terminal.vi_mode_cursor =
+                    terminal.vi_mode_cursor.scroll(&terminal, scroll_lines);
+
+                terminal.scroll_display(Scroll::Delta(scroll_lines));
+                drop(terminal);
+                self.render();
+            }
+            Act::ScrollToTop => {
+                let mut terminal = self.context_manager.current_mut().terminal.lock();
+                terminal.scroll_display(Scroll::Top);
+
+                let topmost_line = terminal.grid.topmost_line();
+                terminal.vi_mode_cursor.pos.row = topmost_line;
+                terminal.vi_motion(ViMotion::FirstOccupied);
+                drop(terminal);
+                self.render();
+            }
+            Act::ScrollToBottom => {
+                let mut terminal = self.context_manager.current_mut().terminal.lock();
+                terminal.scroll_display(Scroll::Bottom);
+
+                // Move vi mode cursor.
+                terminal.vi_mode_cursor.pos.row = terminal.grid.bottommost_line();
+
+                // Move to beginning twice, to always jump across linewraps.
+                terminal.vi_motion(ViMotion::FirstOccupied);
+                terminal.vi_motion(ViMotion::FirstOccupied);
+                drop(terminal);
+                self.render();
+            }
+            Act::Scroll(delta) => {
+                let mut terminal = self.context_manager.current_mut().terminal.lock();
+                terminal.scroll_display(Scroll::Delta(*delta));
+                drop(terminal);
+                self.render();
+            }
+            Act::ClearHistory => {
+                let mut terminal = self.context_manager.current_mut().terminal.lock();
+                terminal.clear_saved_history();
+                drop(terminal);
+                self.render();
+            }
+            Act::ToggleFullscreen => self.context_manager.toggle_full_screen(),
+            Act::Minimize => {
+                self.context_manager.minimize();
+            }
+            Act::Hide => {
+                self.context_manager.hide();
+            }
+            #[cfg(target_os = "macos")]
+            Act::HideOtherApplications => {
+                self.context_manager.hide_other_apps();
+            }
+            Act::SelectNextSplit => {
+                self.cancel_search();
+                self.context_manager.select_next_split();
+                self.render();
+            }
+            Act::SelectPrevSplit => {
+                self.cancel_search();
+                self.context_manager.select_prev_split();
+                self.render();
+            }
+            Act::SelectSplit(direction) => {
+                let direction = *direction;
+                self.cancel_search();
+                self.context_manager.select_split(direction);
+                self.render();
+            }
+            Act::ResizeSplit(direction, size) => {
+                self.context_manager.resize_split(*direction, *size);
+                self.render();
+            }
+            Act::EqualizeSplits => {
+                self.context_manager.equalize_splits();
+                self.render();
+            }
+            Act::ToggleSplitZoom => {
+                self.context_manager.toggle_split_zoom();
+                self.render();
+            }
+            Act::SwapSplitNext => {
+                self.clear_selection();
+                self.context_manager.swap_split_next();
+                self.render();
+            }
+            Act::SwapSplitPrev => {
+                self.clear_selection();
+                self.context_manager.swap_split_prev();
+                self.render();
+            }
+            Act::MoveSplitToNewTab => {
+                self.move_split_to_tab(None);
+            }
+            Act::MoveSplitToTab(tab_index) => {
+                let tab_index = *tab_index;
+                self.move_split_to_tab(Some(tab_index));
+            }
+            Act::ToggleBroadcast(scope) => {
+                self.context_manager.toggle_broadcast(*scope);
+                self.context_manager.update_titles();
+                self.render();
+            }
+            Act::ToggleRecording => {
+                self.context_manager.toggle_recording();
+                self.context_manager.update_titles();
+                self.render();
+            }
+            Act::ToggleTimestamps => {
+                self.context_manager.toggle_timestamps();
+                self.render();
+            }
+            Act::OpenCommandPalette => {
+                self.clear_selection();
+                self.cancel_search();
+                self.context_manager.open_command_palette();
+            }
+            Act::ToggleMonitorActivity => {
+                let enabled = self.context_manager.toggle_monitor_activity();
+                tracing::info!("activity monitor enabled: {enabled}");
+            }
+            Act::ToggleMonitorSilence => {
+                let enabled = self.context_manager.toggle_monitor_silence();
+                tracing::info!("silence monitor enabled: {enabled}");
+            }
+            Act::ToggleHighlight => {
+                let context = self.context_manager.current_mut();
+                context.highlight.toggle();
+                context.renderable_content.mark_pending_updates();
+                self.render();
+            }
+            Act::SaveScreen(format) => {
+                self.context_manager.save_screen(*format, false);
+            }
+            Act::SaveScrollback(format) => {
+                self.context_manager.save_screen(*format, true);
+            }
+            Act::OpenScrollbackInEditor => {
+                let target = self.context_manager.config.scrollback_editor.open_in;
+                self.clear_selection();
+                self.cancel_search();
+                let sugarloaf = &mut self.sugarloaf;
+                if self
+                    .context_manager
+                    .open_scrollback_in_editor(|| sugarloaf.create_rich_text())
+                {
+                    if target == ScrollbackEditorTarget::Tab {
+                        let num_tabs = self.ctx().len();
+                        self.resize_top_or_bottom_line(num_tabs);
+                    }
+                    self.render();
+                }
+            }
+            Act::PlaybackTogglePause => {
+                self.context_manager
+                    .current()
+                    .send_playback(PlayerMsg::TogglePause);
+            }
+            Act::PlaybackSeek(seconds) => {
+                let seconds = *seconds as f64;
+                self.context_manager
+                    .current()
+                    .send_playback(PlayerMsg::Seek(seconds));
+            }
+            Act::PlaybackStep => {
+                self.context_manager
+                    .current()
+                    .send_playback(PlayerMsg::Step);
+            }
+            Act::PlaybackFaster => {
+                self.context_manager
+                    .current()
+                    .send_playback(PlayerMsg::Speed(2.));
+            }
+            Act::PlaybackSlower => {
+                self.context_manager
+                    .current()
+                    .send_playback(PlayerMsg::Speed(0.5));
+            }
+            Act::MarkSplitForBroadcast => {
+                self.context_manager.toggle_broadcast_mark();
+                self.context_manager.update_titles();
+                self.render();
+            }
+            Act::SelectTab(tab_index) => {
+                self.context_manager.select_tab(*tab_index);
+                self.cancel_search();
+                self.render();
+            }
+            Act::SelectLastTab => {
+                self.cancel_search();
+                self.context_manager.select_last_tab();
+                self.render();
+            }
+            Act::SelectNextTab => {
+                self.cancel_search();
+                self.clear_selection();
+                self.context_manager.switch_to_next();
+                self.render();
+            }
+            Act::MoveCurrentTabToPrev => {
+                self.cancel_search();
+                self.clear_selection();
+                self.context_manager.move_current_to_prev();
+                self.render();
+            }
+            Act::MoveCurrentTabToNext => {
+                self.cancel_search();
+                self.clear_selection();
+                self.context_manager.move_current_to_next();
+                self.render();
+            }
+            Act::SelectPrevTab => {
+                self.cancel_search();
+                self.clear_selection();
+                self.context_manager.switch_to_prev();
+                self.render();
+            }
+            Act::ActivateKeyTable(table) => {
+                self.key_tables.activate(table.to_owned());
+                self.render();
+            }
+            Act::PopKeyTable => {
+                self.key_tables.pop();
+                self.render();
+            }
+            Act::OpenHyperlink => {
+                self.open_hyperlink_under_mouse();
+            }
+            Act::Mouse(MouseAction::ExpandSelection) if !self.selection_is_empty() => {
+                let pos = self.mouse_position(self.display_offset());
+                self.update_selection(pos, self.mouse.square_side);
+                self.render();
+            }
+            Act::ReceiveChar | Act::None => (),
+            _ => (),
+        }
+
+        false
+    }
+
     pub fn split_right_with_config(&mut self, config: rio_backend::config::Config) {
         let rich_text_id = self.sugarloaf.create_rich_text();
         self.context_manager
@@ -1078,7 +1455,11 @@ impl Screen<'_> {
     }
 
     pub fn close_split_or_tab(&mut self) {
-        if self.context_manager.current_grid_len() > 1 {
+        // Closing an editor opened over a split brings the split back
+        if self.context_manager.restore_current_overlaid() {
+            self.clear_selection();
+            self.render();
+        } else if self.context_manager.current_grid_len() > 1 {
             self.clear_selection();
             self.context_manager.remove_current_grid();
             self.render();
@@ -1087,6 +1468,40 @@ impl Screen<'_> {
         }
     }
 
+    pub fn create_tab_from_layout(&mut self, layout: &str) {
+        // Rich texts can't be removed, so only created for layouts that fit
+        let panes = match self.context_manager.layout_panes(layout) {
+            Some(panes) => panes,
+            None => return,
+        };
+
+        let rich_text_ids: Vec<usize> = (0..panes)
+            .map(|_| self.sugarloaf.create_rich_text())
+            .collect();
+        if self.context_manager.add_layout(layout, &rich_text_ids) {
+            let num_tabs = self.ctx().len();
+            self.cancel_search();
+            self.resize_top_or_bottom_line(num_tabs);
+            self.render();
+        }
+    }
+
+    pub fn move_split_to_tab(&mut self, tab_index: Option<usize>) {
+        self.clear_selection();
+        self.cancel_search();
+
+        let has_moved = match tab_index {
+            Some(tab_index) => self.context_manager.move_split_to_tab(tab_index),
+            None => self.context_manager.move_split_to_new_tab(),
+        };
+
+        if has_moved {
+            let num_tabs = self.ctx().len();
+            self.resize_top_or_bottom_line(num_tabs);
+            self.render();
+        }
+    }
+
     pub fn close_tab(&mut self) {
         self.clear_selection();
         self.context_manager.close_current_context();
@@ -1252,8 +1667,14 @@ impl Screen<'_> {
     }
 
     pub fn copy_selection(&mut self, ty: ClipboardType) {
+        let copy_timestamps = self.context_manager.config.copy_timestamps;
         let terminal = self.context_manager.current_mut().terminal.lock();
-        let text = match terminal.selection_to_string().filter(|s| !s.is_empty()) {
+        let text = if copy_timestamps {
+            terminal.selection_to_string_with_timestamps()
+        } else {
+            terminal.selection_to_string()
+        };
+        let text = match text.filter(|s| !s.is_empty()) {
             Some(text) => text,
             None => return,
         };
@@ -1407,6 +1828,20 @@ impl Screen<'_> {
         false
     }
 
+    /// Opens the hyperlink under the mouse cursor, also detecting URLs
+    /// written without hyperlink escape sequences.
+    fn open_hyperlink_under_mouse(&mut self) {
+        let mut terminal = self.context_manager.current().terminal.lock();
+        let pos = self.mouse_position(terminal.display_offset());
+        terminal.search_nearest_hyperlink_from_pos(pos);
+        let hyperlink = terminal.grid[pos].hyperlink();
+        drop(terminal);
+
+        if let Some(hyperlink) = hyperlink {
+            self.open_hyperlink(hyperlink);
+        }
+    }
+
     fn open_hyperlink(&self, hyperlink: Hyperlink) {
         #[cfg(not(any(target_os = "macos", windows)))]
         self.exec("xdg-open", [hyperlink.uri()]);
@@ -1897,6 +2332,12 @@ impl Screen<'_> {
 
     #[inline]
     pub fn on_focus_change(&mut self, is_focused: bool) {
+        // Alerts of the current tab fired while the window was unfocused
+        if is_focused {
+            let index = self.context_manager.current_index();
+            self.context_manager.set_current(index);
+        }
+
         if self.get_mode().contains(Mode::FOCUS_IN_OUT) {
             let chr = if is_focused { "I" } else { "O" };
 
@@ -2005,32 +2446,27 @@ impl Screen<'_> {
             for c in text.chars() {
                 self.search_input(c);
             }
-        } else if bracketed && self.get_mode().contains(Mode::BRACKETED_PASTE) {
-            self.ctx_mut()
-                .current_mut()
-                .messenger
-                .send_bytes(b"\x1b[200~"[..].to_vec());
-
+        } else if bracketed {
             // Write filtered escape sequences.
             //
             // We remove `\x1b` to ensure it's impossible for the pasted text to write the bracketed
             // paste end escape `\x1b[201~` and `\x03` since some shells incorrectly terminate
             // bracketed paste on its receival.
             let filtered = text.replace(['\x1b', '\x03'], "");
-            self.ctx_mut()
-                .current_mut()
-                .messenger
-                .send_bytes(filtered.into_bytes());
+            let bracketed_text = format!("\x1b[200~{filtered}\x1b[201~").into_bytes();
+            let text = text.replace("\r\n", "\r").replace('\n', "\r").into_bytes();
 
-            self.ctx_mut()
-                .current_mut()
-                .messenger
-                .send_bytes(b"\x1b[201~"[..].to_vec());
+            // Broadcast targets may not have bracketed paste enabled
+            self.context_manager.send_encoded_input(|mode| {
+                if mode.contains(Mode::BRACKETED_PASTE) {
+                    Cow::Owned(bracketed_text.clone())
+                } else {
+                    Cow::Owned(text.clone())
+                }
+            });
         } else {
-            self.ctx_mut()
-                .current_mut()
-                .messenger
-                .send_bytes(text.replace("\r\n", "\r").replace('\n', "\r").into_bytes());
+            self.context_manager
+                .send_input(text.replace("\r\n", "\r").replace('\n', "\r").into_bytes());
         }
     }
 
@@ -2056,6 +2492,82 @@ impl Screen<'_> {
         self.sugarloaf.render();
     }
 
+    /// Entries of the command palette: open tabs, every action, layouts and
+    /// themes, along with the key bound to each of them.
+    pub fn palette_entries(&self) -> Vec<PaletteEntry> {
+        let hint = |action: &Act| {
+            self.bindings
+                .iter()
+                .find(|binding| binding.action == *action)
+                .map(|binding| binding.label())
+                .unwrap_or_default()
+        };
+        let mut entries = Vec::with_capacity(NAMED_ACTIONS.len() + self.ctx().len());
+
+        for index in 0..self.ctx().len() {
+            let title = self
+                .context_manager
+                .titles
+                .titles
+                .get(&index)
+                .map(|title| title.content.as_str())
+                .unwrap_or_default();
+            let action = Act::SelectTab(index);
+            entries.push(PaletteEntry::new(
+                format!("Tab {}: {}", index + 1, title),
+                hint(&action),
+                PaletteCommand::Action(action),
+            ));
+        }
+
+        for (name, action) in NAMED_ACTIONS {
+            let action = action();
+            entries.push(PaletteEntry::new(
+                name.to_string(),
+                hint(&action),
+                PaletteCommand::Action(action),
+            ));
+        }
+
+        let mut layouts: Vec<&String> =
+            self.context_manager.config.layouts.keys().collect();
+        layouts.sort();
+        for layout in layouts {
+            entries.push(PaletteEntry::new(
+                format!("Layout: {layout}"),
+                String::new(),
+                PaletteCommand::Action(Act::TabCreateNewWithLayout(layout.to_owned())),
+            ));
+        }
+
+        for theme in rio_backend::config::theme_names() {
+            entries.push(PaletteEntry::new(
+                format!("Theme: {theme}"),
+                String::new(),
+                PaletteCommand::Theme(theme),
+            ));
+        }
+
+        entries
+    }
+
+    pub fn render_palette(&mut self, palette: &Palette) {
+        let window_size = self.sugarloaf.window_size();
+        let mut objects = Vec::new();
+        palette::screen(
+            &mut objects,
+            &self.renderer.named_colors,
+            (
+                window_size.width,
+                window_size.height,
+                self.sugarloaf.scale_factor(),
+            ),
+            palette,
+        );
+        self.renderer.set_overlay(objects);
+        self.render();
+    }
+
     pub fn render_dialog(&mut self, content: &str) {
         self.sugarloaf.clear();
         crate::router::routes::dialog::screen(
@@ -2066,6 +2578,12 @@ impl Screen<'_> {
         self.sugarloaf.render();
     }
 
+    /// When the active key table (or the typed sequence) times out.
+    #[inline]
+    pub fn key_tables_deadline(&self) -> Option<Instant> {
+        self.key_tables.deadline()
+    }
+
     pub fn render(&mut self) {
         // let start_total = std::time::Instant::now();
         // println!("_____________________________\nrender time elapsed");
@@ -2078,6 +2596,9 @@ impl Screen<'_> {
             }
         }
 
+        self.key_tables.expire(Instant::now());
+        self.renderer.set_key_table(self.key_tables.indicator());
+
         let mut search_hints = if is_search_active {
             let terminal = self.context_manager.current().terminal.lock();
             let hints = self
@@ -1,17 +1,74 @@
 use crate::event::{EventListener, RioEvent};
 use notify::{Config, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
-use std::path::Path;
+use rio_backend::config::ConfigError;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 const POLLING_TIMEOUT: Duration = Duration::from_secs(2);
 
+/// Watches the config directory along with the files imported by the
+/// config, which may live anywhere.
+pub struct ConfigWatcher {
+    watcher: RecommendedWatcher,
+    config_dir: PathBuf,
+    /// Directories watched for the imported files.
+    directories: HashSet<PathBuf>,
+    imported: Arc<Mutex<HashSet<PathBuf>>>,
+}
+
+impl ConfigWatcher {
+    /// Watches the directory of every imported file, events of other files
+    /// in those directories are ignored.
+    pub fn watch_imported(&mut self, imported: &[PathBuf]) {
+        let directories: HashSet<PathBuf> = imported
+            .iter()
+            .filter_map(|file| file.parent().map(Path::to_path_buf))
+            .filter(|directory| *directory != self.config_dir)
+            .collect();
+
+        for directory in self.directories.difference(&directories) {
+            let _ = self.watcher.unwatch(directory);
+        }
+        for directory in directories.difference(&self.directories) {
+            if let Err(err_message) =
+                self.watcher.watch(directory, RecursiveMode::NonRecursive)
+            {
+                tracing::warn!("unable to watch {directory:?}: {err_message:?}");
+            }
+        }
+
+        self.directories = directories;
+        if let Ok(mut watched) = self.imported.lock() {
+            *watched = imported.iter().cloned().collect();
+        }
+    }
+}
+
+/// Files to watch for a config, along with the imports of the config file
+/// when it failed to load, even the ones that don't exist yet.
+pub fn imports_to_watch(
+    imported: &[PathBuf],
+    config_error: Option<&ConfigError>,
+) -> Vec<PathBuf> {
+    let mut files = imported.to_vec();
+    for file in config_error.map(ConfigError::imported).unwrap_or_default() {
+        if !files.contains(file) {
+            files.push(file.clone());
+        }
+    }
+    files
+}
+
 pub fn configuration_file_updates<
     P: AsRef<Path> + std::marker::Send + 'static,
     T: EventListener + std::marker::Send + 'static,
 >(
     path: P,
+    imported: &[PathBuf],
     event_proxy: T,
-) -> notify::Result<()> {
+) -> notify::Result<ConfigWatcher> {
     let (tx, rx) = std::sync::mpsc::channel();
 
     // Automatically select the best implementation for your platform.
@@ -21,15 +78,16 @@ pub fn configuration_file_updates<
         Config::default().with_poll_interval(POLLING_TIMEOUT),
     )?;
 
-    std::thread::spawn(move || {
-        // Add a path to be watched. All files and directories at that path and
-        // below will be monitored for changes.
-        if let Err(err_message) =
-            watcher.watch(path.as_ref(), RecursiveMode::NonRecursive)
-        {
-            tracing::warn!("unable to watch config directory {err_message:?}");
-        };
+    // Add a path to be watched. All files and directories at that path and
+    // below will be monitored for changes.
+    if let Err(err_message) = watcher.watch(path.as_ref(), RecursiveMode::NonRecursive) {
+        tracing::warn!("unable to watch config directory {err_message:?}");
+    };
 
+    let config_dir = path.as_ref().to_path_buf();
+    let watched_imports = Arc::new(Mutex::new(HashSet::new()));
+    let watched = watched_imports.clone();
+    std::thread::spawn(move || {
         for res in rx {
             match res {
                 Ok(event) => match event.kind {
@@ -37,6 +95,18 @@ pub fn configuration_file_updates<
                     | EventKind::Create(_)
                     | EventKind::Modify(_)
                     | EventKind::Other => {
+                        // Directories of imported files may hold anything
+                        let is_config_file = event.paths.is_empty()
+                            || event.paths.iter().any(|path| {
+                                path.parent() == Some(config_dir.as_path())
+                                    || watched
+                                        .lock()
+                                        .is_ok_and(|watched| watched.contains(path))
+                            });
+                        if !is_config_file {
+                            continue;
+                        }
+
                         tracing::info!(
                             "config directory has dispatched an event {event:?}"
                         );
@@ -54,5 +124,13 @@ pub fn configuration_file_updates<
         }
     });
 
-    Ok(())
+    let mut config_watcher = ConfigWatcher {
+        watcher,
+        config_dir: path.as_ref().to_path_buf(),
+        directories: HashSet::new(),
+        imported: watched_imports,
+    };
+    config_watcher.watch_imported(imported);
+
+    Ok(config_watcher)
 }
@@ -4,7 +4,6 @@
 
 use crate::ansi::sixel;
 use crate::config::colors::ColorRgb;
-use crate::crosswords::grid::Dimensions;
 use crate::sugarloaf::{GraphicData, GraphicId};
 use parking_lot::Mutex;
 use smallvec::SmallVec;
@@ -139,25 +138,11 @@ pub struct Graphics {
     /// Shared palette for Sixel graphics.
     pub sixel_shared_palette: Option<Vec<ColorRgb>>,
 
-    /// Cell height in pixels.
-    pub cell_height: f32,
-
-    /// Cell width in pixels.
-    pub cell_width: f32,
-
     /// Current Sixel parser.
     pub sixel_parser: Option<Box<sixel::Parser>>,
 }
 
 impl Graphics {
-    /// Create a new instance, and initialize it with the dimensions of the
-    /// window.
-    pub fn new<S: Dimensions>(size: &S) -> Self {
-        let mut graphics = Graphics::default();
-        graphics.resize(size);
-        graphics
-    }
-
     /// Generate a new graphic identifier.
     pub fn next_id(&mut self) -> GraphicId {
         self.last_id += 1;
@@ -197,12 +182,6 @@ impl Graphics {
             clear_subregions,
         })
     }
-
-    /// Update cell dimensions.
-    pub fn resize<S: Dimensions>(&mut self, size: &S) {
-        self.cell_height = size.square_height();
-        self.cell_width = size.square_width();
-    }
 }
 
 #[test]
@@ -3,12 +3,16 @@ use serde::{Deserialize, Serialize};
 
 pub mod charset;
 pub mod control;
+#[cfg(feature = "ui")]
 pub mod graphics;
+#[cfg(feature = "ui")]
 pub mod iterm2_image_protocol;
 pub mod mode;
+#[cfg(feature = "ui")]
 pub mod sixel;
 
 #[derive(Default, Clone, Serialize, Deserialize, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum CursorShape {
     /// Cursor is a block like `▒`.
     #[default]
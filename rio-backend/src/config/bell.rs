@@ -0,0 +1,66 @@
+use crate::config::colors::{deserialize_to_arr, serialize_arr, ColorArray};
+use crate::config::defaults::default_bool_true;
+use crate::config::Shell;
+use serde::{Deserialize, Serialize};
+
+/// What happens when a program rings the bell (BEL).
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Bell {
+    /// Flashes the split that rang the bell.
+    #[serde(default)]
+    pub visual: bool,
+    /// Duration of the flash in milliseconds.
+    #[serde(default = "default_visual_duration", rename = "visual-duration")]
+    pub visual_duration: u64,
+    /// Color of the flash, the alpha fades out to zero.
+    #[serde(
+        default = "default_visual_color",
+        rename = "visual-color",
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr"
+    )]
+    pub visual_color: ColorArray,
+    /// Requests the user attention when the window isn't focused.
+    #[serde(default = "default_bool_true")]
+    pub urgent: bool,
+    /// Marks background tabs that rang the bell until they're selected.
+    #[serde(default = "default_bool_true")]
+    pub indicator: bool,
+    /// Program executed on every bell.
+    #[serde(default)]
+    pub command: Option<Shell>,
+    /// Minimum interval in milliseconds between bells of a split that
+    /// isn't focused, the ones in between are dropped.
+    #[serde(default = "default_unfocused_interval", rename = "unfocused-interval")]
+    pub unfocused_interval: u64,
+}
+
+#[inline]
+fn default_visual_duration() -> u64 {
+    150
+}
+
+#[inline]
+fn default_visual_color() -> ColorArray {
+    [1.0, 1.0, 1.0, 0.4]
+}
+
+#[inline]
+fn default_unfocused_interval() -> u64 {
+    1000
+}
+
+impl Default for Bell {
+    fn default() -> Bell {
+        Bell {
+            visual: false,
+            visual_duration: default_visual_duration(),
+            visual_color: default_visual_color(),
+            urgent: true,
+            indicator: true,
+            command: None,
+            unfocused_interval: default_unfocused_interval(),
+        }
+    }
+}
@@ -4,10 +4,20 @@ use serde::{Deserialize, Serialize};
 // { key = "w", mods: "super", action = "quit" }
 // Bytes[27, 91, 53, 126] is equivalent to "\x1b[5~"
 // { key = "Home", mods: "super | shift", bytes = [27, 91, 53, 126] }
+// { keys = "control+a |", action = "splitright" }
+// { key = "h", table = "resize", action = "resizesplitleft" }
+// { button = "middle", action = "paste" }
+// { button = "left", clicks = 2, with = "control", action = "openhyperlink" }
+// { key = "h", with = "control", when = "~process=nvim", action = "selectprevsplit" }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct KeyBinding {
+    #[serde(default = "String::default")]
     pub key: String,
+    /// Sequence of chords like `control+a |`, used instead of `key` and `with`.
+    #[serde(default = "String::default")]
+    pub keys: String,
     #[serde(default = "String::default")]
     pub with: String,
     #[serde(default = "String::default")]
@@ -15,16 +25,65 @@ pub struct KeyBinding {
     #[serde(default = "String::default")]
     pub text: String,
     #[serde(default = "Vec::default")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub bytes: Vec<u8>,
     #[serde(default = "String::default")]
     pub mode: String,
+    /// Key table the binding belongs to, only active while the table is.
+    #[serde(default = "String::default")]
+    pub table: String,
+    /// Conditions on the terminal state like `~process=nvim | altscreen`.
+    #[serde(default = "String::default")]
+    pub when: String,
 }
 
 pub type KeyBindings = Vec<KeyBinding>;
 
-#[derive(Default, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MouseBinding {
+    /// `left`, `middle`, `right`, `back` or `forward`.
+    pub button: String,
+    /// Number of consecutive clicks (1 to 3), any when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clicks: Option<u8>,
+    #[serde(default = "String::default")]
+    pub with: String,
+    #[serde(default = "String::default")]
+    pub action: String,
+    #[serde(default = "String::default")]
+    pub mode: String,
+    #[serde(default = "String::default")]
+    pub when: String,
+}
+
+pub type MouseBindings = Vec<MouseBinding>;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Bindings {
+    #[serde(default)]
     pub keys: KeyBindings,
+    #[serde(default)]
+    pub mouse: MouseBindings,
+    /// Milliseconds waiting for the next key of a sequence, key tables are
+    /// left after the same time without a key press (0 waits forever).
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+}
+
+fn default_timeout() -> u64 {
+    1000
+}
+
+impl Default for Bindings {
+    fn default() -> Bindings {
+        Bindings {
+            keys: KeyBindings::default(),
+            mouse: MouseBindings::default(),
+            timeout: default_timeout(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -53,6 +112,65 @@ mod tests {
         assert_eq!(decoded.bindings.keys[0].with.to_owned(), "super");
         assert_eq!(decoded.bindings.keys[0].action.to_owned(), "quit");
         assert!(decoded.bindings.keys[0].text.to_owned().is_empty());
+        assert_eq!(decoded.bindings.timeout, 1000);
+    }
+
+    #[test]
+    fn test_key_sequences_and_tables() {
+        let content = r#"
+            [bindings]
+            timeout = 2000
+            keys = [
+                { keys = 'control+a |', action = 'splitright' },
+                { key = 'h', table = 'resize', action = 'resizesplitleft' },
+            ]
+        "#;
+
+        let decoded = toml::from_str::<Root>(content).unwrap();
+        assert_eq!(decoded.bindings.timeout, 2000);
+        assert_eq!(decoded.bindings.keys[0].keys, "control+a |");
+        assert!(decoded.bindings.keys[0].key.is_empty());
+        assert!(decoded.bindings.keys[0].table.is_empty());
+        assert_eq!(decoded.bindings.keys[1].key, "h");
+        assert_eq!(decoded.bindings.keys[1].table, "resize");
+    }
+
+    #[test]
+    fn test_when_conditions() {
+        let content = r#"
+            [bindings]
+            keys = [
+                { key = 'h', with = 'control', when = '~process=nvim', action = 'selectprevsplit' },
+            ]
+            mouse = [
+                { button = 'middle', when = '~mouse', action = 'paste' },
+            ]
+        "#;
+
+        let decoded = toml::from_str::<Root>(content).unwrap();
+        assert_eq!(decoded.bindings.keys[0].when, "~process=nvim");
+        assert_eq!(decoded.bindings.mouse[0].when, "~mouse");
+    }
+
+    #[test]
+    fn test_mouse_bindings() {
+        let content = r#"
+            [bindings]
+            mouse = [
+                { button = 'middle', action = 'paste' },
+                { button = 'left', clicks = 2, with = 'control', action = 'openhyperlink', mode = '~vi' },
+            ]
+        "#;
+
+        let decoded = toml::from_str::<Root>(content).unwrap();
+        assert!(decoded.bindings.keys.is_empty());
+        assert_eq!(decoded.bindings.mouse[0].button, "middle");
+        assert_eq!(decoded.bindings.mouse[0].clicks, None);
+        assert!(decoded.bindings.mouse[0].with.is_empty());
+        assert_eq!(decoded.bindings.mouse[1].clicks, Some(2));
+        assert_eq!(decoded.bindings.mouse[1].with, "control");
+        assert_eq!(decoded.bindings.mouse[1].action, "openhyperlink");
+        assert_eq!(decoded.bindings.mouse[1].mode, "~vi");
     }
 
     #[test]
@@ -72,6 +72,13 @@ pub fn tabs_active_highlight() -> ColorArray {
         .to_arr()
 }
 
+#[inline]
+pub fn tabs_alert() -> ColorArray {
+    ColorBuilder::from_hex(String::from("#F4E409"), Format::SRGB0_1)
+        .unwrap()
+        .to_arr()
+}
+
 #[inline]
 pub fn foreground() -> ColorArray {
     [1., 1., 1., 1.]
@@ -147,6 +154,13 @@ pub fn split() -> ColorArray {
         .to_arr()
 }
 
+#[inline]
+pub fn broadcast() -> ColorArray {
+    ColorBuilder::from_hex(String::from("#ED203D"), Format::SRGB0_1)
+        .unwrap()
+        .to_arr()
+}
+
 #[inline]
 pub fn dim_blue() -> ColorArray {
     ColorBuilder::from_hex(String::from("#0E91B7"), Format::SRGB0_1)
@@ -4,7 +4,7 @@ pub mod term;
 
 use regex::Regex;
 use serde::Serialize;
-use serde::{de, Deserialize};
+use serde::{de, ser, Deserialize};
 use std::num::ParseIntError;
 
 pub type ColorWGPU = wgpu::Color;
@@ -53,216 +53,310 @@ pub enum AnsiColor {
     Indexed(u8),
 }
 
-#[derive(Debug, Copy, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Copy, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Colors {
     #[serde(
         deserialize_with = "deserialize_to_composition",
+        serialize_with = "serialize_composition",
         default = "defaults::background"
     )]
     /// Background is a special color type called ColorComposition
     /// ColorComposition type is (ColorArray, ColorWGPU)
     /// See more in colors definition
+    #[cfg_attr(feature = "schemars", schemars(with = "ColorArray"))]
     pub background: ColorComposition,
     #[serde(
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         default = "defaults::foreground"
     )]
     pub foreground: ColorArray,
-    #[serde(deserialize_with = "deserialize_to_arr", default = "defaults::blue")]
+    #[serde(
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
+        default = "defaults::blue"
+    )]
     pub blue: ColorArray,
-    #[serde(deserialize_with = "deserialize_to_arr", default = "defaults::green")]
+    #[serde(
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
+        default = "defaults::green"
+    )]
     pub green: ColorArray,
-    #[serde(deserialize_with = "deserialize_to_arr", default = "defaults::red")]
+    #[serde(
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
+        default = "defaults::red"
+    )]
     pub red: ColorArray,
-    #[serde(deserialize_with = "deserialize_to_arr", default = "defaults::yellow")]
+    #[serde(
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
+        default = "defaults::yellow"
+    )]
     pub yellow: ColorArray,
     #[serde(
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         default = "defaults::tabs_active",
         rename = "tabs-active"
     )]
     pub tabs_active: ColorArray,
     #[serde(
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         default = "defaults::tabs_active_foreground",
         rename = "tabs-active-foreground"
     )]
     pub tabs_active_foreground: ColorArray,
     #[serde(
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         default = "defaults::tabs_foreground",
         rename = "tabs-foreground"
     )]
     pub tabs_foreground: ColorArray,
-    #[serde(default = "defaults::cursor", deserialize_with = "deserialize_to_arr")]
+    #[serde(
+        default = "defaults::cursor",
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr"
+    )]
     pub cursor: ColorArray,
     #[serde(
         default = "defaults::vi_cursor",
         rename = "vi-cursor",
-        deserialize_with = "deserialize_to_arr"
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr"
     )]
     pub vi_cursor: ColorArray,
-    #[serde(default = "defaults::black", deserialize_with = "deserialize_to_arr")]
+    #[serde(
+        default = "defaults::black",
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr"
+    )]
     pub black: ColorArray,
-    #[serde(default = "defaults::cyan", deserialize_with = "deserialize_to_arr")]
+    #[serde(
+        default = "defaults::cyan",
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr"
+    )]
     pub cyan: ColorArray,
-    #[serde(default = "defaults::magenta", deserialize_with = "deserialize_to_arr")]
+    #[serde(
+        default = "defaults::magenta",
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr"
+    )]
     pub magenta: ColorArray,
-    #[serde(default = "defaults::tabs", deserialize_with = "deserialize_to_arr")]
+    #[serde(
+        default = "defaults::tabs",
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr"
+    )]
     pub tabs: ColorArray,
-    #[serde(default = "defaults::bar", deserialize_with = "deserialize_to_arr")]
+    #[serde(
+        default = "defaults::bar",
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr"
+    )]
     pub bar: ColorArray,
     #[serde(
         default = "defaults::tabs_active_highlight",
         rename = "tabs-active-highlight",
-        deserialize_with = "deserialize_to_arr"
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr"
     )]
     pub tabs_active_highlight: ColorArray,
-    #[serde(default = "defaults::white", deserialize_with = "deserialize_to_arr")]
+    #[serde(
+        default = "defaults::tabs_alert",
+        rename = "tabs-alert",
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr"
+    )]
+    pub tabs_alert: ColorArray,
+    #[serde(
+        default = "defaults::white",
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr"
+    )]
     pub white: ColorArray,
     #[serde(
         default = "defaults::dim_black",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "dim-black"
     )]
     pub dim_black: ColorArray,
     #[serde(
         default = "defaults::dim_blue",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "dim-blue"
     )]
     pub dim_blue: ColorArray,
     #[serde(
         default = "defaults::dim_cyan",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "dim-cyan"
     )]
     pub dim_cyan: ColorArray,
     #[serde(
         default = "defaults::dim_foreground",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "dim-foreground"
     )]
     pub dim_foreground: ColorArray,
     #[serde(
         default = "defaults::dim_green",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "dim-green"
     )]
     pub dim_green: ColorArray,
     #[serde(
         default = "defaults::dim_magenta",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "dim-magenta"
     )]
     pub dim_magenta: ColorArray,
     #[serde(
         default = "defaults::dim_red",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "dim-red"
     )]
     pub dim_red: ColorArray,
     #[serde(
         default = "defaults::dim_white",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "dim-white"
     )]
     pub dim_white: ColorArray,
     #[serde(
         default = "defaults::dim_yellow",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "dim-yellow"
     )]
     pub dim_yellow: ColorArray,
     #[serde(
         default = "defaults::light_black",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "light-black"
     )]
     pub light_black: ColorArray,
     #[serde(
         default = "defaults::light_blue",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "light-blue"
     )]
     pub light_blue: ColorArray,
     #[serde(
         default = "defaults::light_cyan",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "light-cyan"
     )]
     pub light_cyan: ColorArray,
     #[serde(
         default = "defaults::light_foreground",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "light-foreground"
     )]
     pub light_foreground: ColorArray,
     #[serde(
         default = "defaults::light_green",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "light-green"
     )]
     pub light_green: ColorArray,
     #[serde(
         default = "defaults::light_magenta",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "light-magenta"
     )]
     pub light_magenta: ColorArray,
     #[serde(
         default = "defaults::light_red",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "light-red"
     )]
     pub light_red: ColorArray,
     #[serde(
         default = "defaults::light_white",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "light-white"
     )]
     pub light_white: ColorArray,
     #[serde(
         default = "defaults::light_yellow",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "light-yellow"
     )]
     pub light_yellow: ColorArray,
     #[serde(
         default = "defaults::selection_background",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "selection-background"
     )]
     pub selection_background: ColorArray,
     #[serde(
         default = "defaults::selection_foreground",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "selection-foreground"
     )]
     pub selection_foreground: ColorArray,
-    #[serde(default = "defaults::split", deserialize_with = "deserialize_to_arr")]
+    #[serde(
+        default = "defaults::split",
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr"
+    )]
     pub split: ColorArray,
+    #[serde(
+        default = "defaults::broadcast",
+        deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr"
+    )]
+    pub broadcast: ColorArray,
     #[serde(
         default = "defaults::search_match_background",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "search-match-background"
     )]
     pub search_match_background: ColorArray,
     #[serde(
         default = "defaults::search_match_foreground",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "search-match-foreground"
     )]
     pub search_match_foreground: ColorArray,
     #[serde(
         default = "defaults::search_focused_match_background",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "search-focused-match-background"
     )]
     pub search_focused_match_background: ColorArray,
     #[serde(
         default = "defaults::search_focused_match_foreground",
         deserialize_with = "deserialize_to_arr",
+        serialize_with = "serialize_arr",
         rename = "search-focused-match-foreground"
     )]
     pub search_focused_match_foreground: ColorArray,
@@ -280,11 +374,13 @@ impl Default for Colors {
             bar: defaults::bar(),
             tabs: defaults::tabs(),
             tabs_active_highlight: defaults::tabs_active_highlight(),
+            tabs_alert: defaults::tabs_alert(),
             tabs_active: defaults::tabs_active(),
             tabs_active_foreground: defaults::tabs_active_foreground(),
             tabs_foreground: defaults::tabs_foreground(),
             cursor: defaults::cursor(),
             split: defaults::split(),
+            broadcast: defaults::broadcast(),
             vi_cursor: defaults::vi_cursor(),
             black: defaults::black(),
             cyan: defaults::cyan(),
@@ -633,6 +729,56 @@ where
     }
 }
 
+pub fn deserialize_to_optional_arr<'de, D>(
+    deserializer: D,
+) -> Result<Option<ColorArray>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    deserialize_to_arr(deserializer).map(Some)
+}
+
+/// Writes the color as a hex string, the alpha is only written when the
+/// color is not opaque.
+pub fn to_hex(color: &ColorArray) -> String {
+    let [red, green, blue, alpha] = color.map(|value| (value * 255.0).round() as u8);
+    if alpha == u8::MAX {
+        format!("#{red:02x}{green:02x}{blue:02x}")
+    } else {
+        format!("#{red:02x}{green:02x}{blue:02x}{alpha:02x}")
+    }
+}
+
+pub fn serialize_arr<S>(color: &ColorArray, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    serializer.serialize_str(&to_hex(color))
+}
+
+pub fn serialize_composition<S>(
+    color: &ColorComposition,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    serialize_arr(&color.0, serializer)
+}
+
+pub fn serialize_optional_arr<S>(
+    color: &Option<ColorArray>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    match color {
+        Some(color) => serialize_arr(color, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -787,4 +933,10 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_conversion_to_hex() {
+        assert_eq!(to_hex(&hex_to_color_arr("#06A49B")), "#06a49b");
+        assert_eq!(to_hex(&hex_to_color_arr("#06a49b99")), "#06a49b99");
+    }
 }
@@ -136,6 +136,16 @@ pub fn default_disable_ctlseqs_alt() -> bool {
 
 pub fn default_config_file_content() -> String {
     r#"
+# Import
+#
+# Config files merged before this one, in order. Later files win
+# and key bindings are concatenated. Paths starting with `~` are
+# relative to the home directory, other relative paths to the
+# directory of the importing file.
+#
+# Example:
+# import = ["~/.config/rio/team.toml", "themes/work.toml"]
+
 # Hide the cursor while typing
 #
 # Default is `false`
@@ -466,11 +476,148 @@ pub fn default_config_file_content() -> String {
 #   { key = "home", with = "super | shift", bytes = [27, 91, 53, 126] }
 # ]
 
+# Layouts
+#
+# Declare split layouts that can be opened with `rio --layout dev`
+# or by a binding with the action `CreateTab:dev`.
+# Each pane (besides the first one) splits the previous pane
+# (or the pane in `from`) to `right` or `down`, `size` is the
+# percentage of the area used by the new pane.
+#
+# Example:
+# [layouts.dev]
+# panes = [
+#   { shell = { program = "nvim" }, working-dir = "/var/www" },
+#   { split = "right", size = 40, shell = { program = "cargo", args = ["watch", "-x", "test"] } },
+#   { split = "down" },
+# ]
+
+# Record
+#
+# Sessions can be recorded in asciicast v2 format with the
+# action `ToggleRecording` or by starting with `rio --record <file>`.
+# • directory - where `ToggleRecording` writes the recordings (default: home)
+# • input - also record the input written to the terminal
+#
+# Example:
+# [record]
+# directory = "/tmp"
+# input = false
+
+# Export
+#
+# The actions `SaveScreen` and `SaveScrollback` write the content
+# of the current split as plain text, ANSI escaped text, HTML or SVG.
+# • directory - where the files are written (default: home)
+# • format - format used when the action doesn't specify one:
+#   "text", "ansi", "html" or "svg" (default: "text")
+#
+# Example:
+# [export]
+# directory = "/tmp"
+# format = "html"
+
+# Triggers
+#
+# Actions executed when a regex matches the output of a program,
+# text properties can use the capture groups ($0, $1, ${name}).
+# • highlight - paints the match with `foreground` and `background`
+# • notify - desktop notification with `title` and `message`
+# • bell - rings the bell
+# • send-text - writes `text` to the program
+# • run - runs `program` with `args` in the background
+#
+# Example:
+# [[triggers]]
+# regex = "build (\\w+) finished"
+# action = "notify"
+# message = "$1 is ready"
+
+# Highlight
+#
+# Rules that style the text matching a regex, on top of the colors
+# used by the program. The action `ToggleHighlight` enables or
+# disables them in the current split, layout panes can override
+# `enabled` with `highlight`.
+# • enabled - whether the rules are applied to new splits (default: true)
+# • rules - list of rules with `regex` and any of `foreground`,
+#   `background`, `underline` and `bold`
+#
+# Example:
+# [highlight]
+# rules = [
+#   { regex = "ERROR", foreground = '#ff5555', bold = true },
+#   { regex = "WARN", foreground = '#f1fa8c' },
+# ]
+
+# Timestamps
+#
+# Rio records the time each line was first written, the action
+# `ToggleTimestamps` shows it in a gutter on the left of the split.
+# • copy - prefix copied lines with their time (default: false)
+# • export - prefix exported lines with their time (default: false)
+#
+# Example:
+# [timestamps]
+# copy = true
+# export = true
+
+# Monitor
+#
+# Watch background tabs and alert when they write new output
+# (activity) or stop writing for a while (silence). The actions
+# `ToggleMonitorActivity` and `ToggleMonitorSilence` change it
+# for the current tab.
+# • activity - watch new output of every tab (default: false)
+# • silence - watch every tab for silence (default: false)
+# • silence-seconds - seconds without output (default: 10)
+# • actions - any of "mark", "bell" and "notify" (default: ["mark"])
+#
+# Example:
+# [monitor]
+# silence = true
+# silence-seconds = 30
+# actions = ["mark", "notify"]
+
+# Bell
+#
+# What happens when a program rings the bell.
+# • visual - flash the split that rang the bell (default: false)
+# • visual-duration - flash duration in milliseconds (default: 150)
+# • visual-color - flash color, alpha fades out (default: '#FFFFFF66')
+# • urgent - request attention when the window is unfocused (default: true)
+# • indicator - mark background tabs that rang the bell (default: true)
+# • command - program executed on every bell
+# • unfocused-interval - minimum milliseconds between bells of
+#   unfocused splits (default: 1000)
+#
+# Example:
+# [bell]
+# visual = true
+# visual-duration = 200
+# command = { program = "paplay", args = ["/usr/share/sounds/bell.oga"] }
+
+# Scrollback editor
+#
+# The action `OpenScrollbackInEditor` writes the scrollback of the
+# current split to a temporary file and opens it with `editor`.
+# • command - program used instead of `editor`, like a pager
+# • ansi - keep colors as ANSI escape sequences (default: false)
+# • open-in - "overlay" takes the place of the current split until
+#   the editor exits, "split" or "tab" (default: "overlay")
+#
+# Example:
+# [scrollback-editor]
+# command = { program = "less", args = ["-R"] }
+# ansi = true
+# open-in = "tab"
+
 # Platform
 #
 # Rio now allows you to have different configurations per OS
-# You can write ovewrite properties like `Shell`, `Navigation`
-# and `Window`.
+# The table of the current OS is merged over the configuration,
+# so it can overwrite any property like `shell`, `fonts`,
+# `padding-x`, `colors`, `bindings` or `env-vars`.
 #
 # Example:
 # [shell]
@@ -486,6 +633,7 @@ pub fn default_config_file_content() -> String {
 # # Linux overwrite
 # linux.shell.program = "tmux"
 # linux.shell.args = ["new-session", "-c", "/var/www"]
+# linux.fonts.size = 14
 
 # Log level
 #
@@ -0,0 +1,726 @@
+// Diagnostics of the config file. Syntax errors stop the config from
+// loading, while keys with invalid values are dropped (so they fall back
+// to their defaults) and reported as warnings, along with unknown and
+// deprecated keys. Values using undefined environment variables are
+// dropped as well, but reported as errors. Every diagnostic points to the
+// file, line and column of the key and may come with a suggestion.
+
+use crate::config::expand;
+use crate::config::overrides::ConfigOverrides;
+use crate::config::platform;
+use crate::config::Config;
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// Keys that still work but were renamed, with their new name.
+const DEPRECATED_KEYS: &[(&str, &str)] =
+    &[("hide-cursor-when-typing", "hide-mouse-cursor-when-typing")];
+
+/// Keys renamed or moved by previous releases that are no longer read,
+/// reported with their new name instead of as unknown keys.
+const RENAMED_KEYS: &[(&str, &str)] = &[
+    ("developer.log-file", "developer.enable-log-file"),
+    ("window.background-opacity", "window.opacity"),
+    (
+        "ignore-selection-fg-color",
+        "ignore-selection-foreground-color",
+    ),
+    ("performance", "renderer.performance"),
+    (
+        "disable-unfocused-render",
+        "renderer.disable-unfocused-render",
+    ),
+];
+
+/// Invalid values reported before giving up on the rest of the config.
+const MAX_DROPPED_VALUES: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub file: PathBuf,
+    /// One-based position in the file, zero when unknown.
+    pub line: usize,
+    pub column: usize,
+    /// Path of the key like `window.mode` or `bindings.keys[0].action`,
+    /// empty for syntax errors.
+    pub key: String,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn error(file: &Path, message: String) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            file: file.to_path_buf(),
+            line: 0,
+            column: 0,
+            key: String::new(),
+            message,
+            suggestion: None,
+        }
+    }
+
+    /// Syntax error of the TOML document `content` read from `file`.
+    pub fn syntax(file: &Path, content: &str, error: &toml::de::Error) -> Diagnostic {
+        let mut diagnostic = Diagnostic::error(file, error.message().to_string());
+        if let Some(span) = error.span() {
+            (diagnostic.line, diagnostic.column) = position(content, span.start);
+        }
+        diagnostic
+    }
+
+    #[inline]
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.file.display())?;
+        if self.line > 0 {
+            write!(f, ":{}:{}", self.line, self.column)?;
+        }
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, ": {severity}: ")?;
+        if !self.key.is_empty() {
+            write!(f, "`{}` ", self.key)?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (did you mean `{suggestion}`?)")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+fn key_path(segments: &[Segment]) -> String {
+    let mut path = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Key(key) => {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(key);
+            }
+            Segment::Index(index) => path.push_str(&format!("[{index}]")),
+        }
+    }
+    path
+}
+
+/// Files the config was read from, used to locate keys. The config file
+/// comes first since its values win over the imported ones.
+pub struct Sources {
+    main_file: PathBuf,
+    documents: Vec<(PathBuf, String, Option<toml_edit::ImDocument<String>>)>,
+}
+
+impl Sources {
+    pub fn new(path: &Path, content: &str, imported: &[PathBuf]) -> Sources {
+        let mut documents = vec![(path.to_path_buf(), content.to_string())];
+        for file in imported.iter().rev() {
+            if let Ok(content) = std::fs::read_to_string(file) {
+                documents.push((file.to_path_buf(), content));
+            }
+        }
+
+        Sources {
+            main_file: path.to_path_buf(),
+            documents: documents
+                .into_iter()
+                .map(|(file, content)| {
+                    let document = toml_edit::ImDocument::parse(content.clone()).ok();
+                    (file, content, document)
+                })
+                .collect(),
+        }
+    }
+
+    /// Adds the command line overrides, which win over every file.
+    pub fn with_overrides(mut self, overrides: &ConfigOverrides) -> Sources {
+        if !overrides.is_empty() {
+            let (file, content) = overrides.source();
+            let document = toml_edit::ImDocument::parse(content.clone()).ok();
+            self.documents.insert(0, (file, content, document));
+        }
+        self
+    }
+
+    fn main_file(&self) -> &Path {
+        &self.main_file
+    }
+
+    /// Builds a diagnostic pointing to the key at `segments`, in the first
+    /// file that has it.
+    fn diagnostic(
+        &self,
+        severity: Severity,
+        segments: &[Segment],
+        message: String,
+        suggestion: Option<String>,
+    ) -> Diagnostic {
+        let mut diagnostic = Diagnostic {
+            severity,
+            file: self.main_file().to_path_buf(),
+            line: 0,
+            column: 0,
+            key: key_path(segments),
+            message,
+            suggestion,
+        };
+
+        // Values under the `[platform]` of the current OS win over the others
+        let platform_segments: Option<Vec<Segment>> = platform::current().map(|os| {
+            [
+                Segment::Key(String::from("platform")),
+                Segment::Key(os.to_string()),
+            ]
+            .into_iter()
+            .chain(segments.iter().cloned())
+            .collect()
+        });
+
+        for (file, content, document) in &self.documents {
+            let Some(table) = document.as_ref().map(|document| document.as_table())
+            else {
+                continue;
+            };
+            let platform_span = platform_segments
+                .as_ref()
+                .and_then(|platform_segments| key_span(table, platform_segments));
+            if let Some(span) = platform_span {
+                diagnostic.key =
+                    key_path(platform_segments.as_deref().unwrap_or_default());
+                diagnostic.file = file.to_path_buf();
+                (diagnostic.line, diagnostic.column) = position(content, span.start);
+                break;
+            }
+            if let Some(span) = key_span(table, segments) {
+                diagnostic.file = file.to_path_buf();
+                (diagnostic.line, diagnostic.column) = position(content, span.start);
+                break;
+            }
+        }
+
+        diagnostic
+    }
+}
+
+enum Node<'a> {
+    Table(&'a dyn toml_edit::TableLike),
+    Array(&'a toml_edit::Array),
+    Tables(&'a toml_edit::ArrayOfTables),
+    Other,
+}
+
+impl<'a> Node<'a> {
+    fn from_item(item: &'a toml_edit::Item) -> Node<'a> {
+        match item {
+            toml_edit::Item::Table(table) => Node::Table(table),
+            toml_edit::Item::ArrayOfTables(tables) => Node::Tables(tables),
+            toml_edit::Item::Value(value) => Node::from_value(value),
+            toml_edit::Item::None => Node::Other,
+        }
+    }
+
+    fn from_value(value: &'a toml_edit::Value) -> Node<'a> {
+        match value {
+            toml_edit::Value::InlineTable(table) => Node::Table(table),
+            toml_edit::Value::Array(array) => Node::Array(array),
+            _ => Node::Other,
+        }
+    }
+}
+
+/// Span of the last key (or array item) of `segments`.
+fn key_span(table: &toml_edit::Table, segments: &[Segment]) -> Option<Range<usize>> {
+    let mut node = Node::Table(table);
+    let mut span = None;
+    for segment in segments {
+        node = match (node, segment) {
+            (Node::Table(table), Segment::Key(key)) => {
+                let (key, item) = table.get_key_value(key)?;
+                span = key.span();
+                Node::from_item(item)
+            }
+            (Node::Array(array), Segment::Index(index)) => {
+                let value = array.get(*index)?;
+                span = value.span();
+                Node::from_value(value)
+            }
+            (Node::Tables(tables), Segment::Index(index)) => {
+                let table = tables.get(*index)?;
+                span = table.span();
+                Node::Table(table)
+            }
+            _ => return None,
+        };
+    }
+    span
+}
+
+/// One-based line and column of a byte offset.
+fn position(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+/// Closest candidate to `value`, if any is close enough.
+pub fn did_you_mean<'a>(
+    value: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<String> {
+    candidates
+        .into_iter()
+        .map(|candidate| (strsim::jaro_winkler(value, candidate), candidate))
+        .filter(|(confidence, _)| *confidence > 0.7)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// Names between backticks in a serde message, like the variants of
+/// "unknown variant `foo`, expected one of `bar`, `baz`".
+fn quoted(message: &str) -> Vec<&str> {
+    message.split('`').skip(1).step_by(2).collect()
+}
+
+/// Suggestion for a serde error, the closest expected variant or field.
+fn suggestion_for(message: &str) -> Option<String> {
+    let (found, expected) = message.split_once("expected")?;
+    let found = quoted(found);
+    did_you_mean(found.first()?, quoted(expected))
+}
+
+fn segments_of_serde_path(path: &serde_path_to_error::Path) -> Vec<Segment> {
+    path.iter()
+        .filter_map(|segment| match segment {
+            serde_path_to_error::Segment::Map { key } => Some(Segment::Key(key.clone())),
+            serde_path_to_error::Segment::Seq { index } => Some(Segment::Index(*index)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn segments_of_ignored_path(path: &serde_ignored::Path, segments: &mut Vec<Segment>) {
+    match path {
+        serde_ignored::Path::Root => {}
+        serde_ignored::Path::Seq { parent, index } => {
+            segments_of_ignored_path(parent, segments);
+            segments.push(Segment::Index(*index));
+        }
+        serde_ignored::Path::Map { parent, key } => {
+            segments_of_ignored_path(parent, segments);
+            segments.push(Segment::Key(key.clone()));
+        }
+        serde_ignored::Path::Some { parent }
+        | serde_ignored::Path::NewtypeStruct { parent }
+        | serde_ignored::Path::NewtypeVariant { parent } => {
+            segments_of_ignored_path(parent, segments);
+        }
+    }
+}
+
+/// Expands `~` and the environment variables of every string, collecting
+/// the values with undefined variables.
+fn expand_table(
+    table: &mut Table,
+    segments: &mut Vec<Segment>,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    undefined: &mut Vec<(Vec<Segment>, String)>,
+) {
+    for (key, value) in table.iter_mut() {
+        segments.push(Segment::Key(key.clone()));
+        expand_value(value, segments, lookup, undefined);
+        segments.pop();
+    }
+}
+
+fn expand_value(
+    value: &mut Value,
+    segments: &mut Vec<Segment>,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    undefined: &mut Vec<(Vec<Segment>, String)>,
+) {
+    let path = segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Key(key) => key.as_str(),
+            Segment::Index(_) => "[]",
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+        .replace(".[]", "[]");
+    if expand::is_skipped(&path) {
+        return;
+    }
+
+    match value {
+        Value::String(string) => match expand::expand_with(string, lookup) {
+            Ok(Cow::Owned(expanded)) => *string = expanded,
+            Ok(Cow::Borrowed(_)) => {}
+            Err(name) => undefined.push((segments.clone(), name)),
+        },
+        Value::Table(table) => expand_table(table, segments, lookup, undefined),
+        Value::Array(array) => {
+            for (index, value) in array.iter_mut().enumerate() {
+                segments.push(Segment::Index(index));
+                expand_value(value, segments, lookup, undefined);
+                segments.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Removes the value at `segments`, returns false if it isn't there.
+fn remove(table: &mut Table, segments: &[Segment]) -> bool {
+    match segments {
+        [Segment::Key(key)] => table.remove(key).is_some(),
+        [Segment::Key(key), rest @ ..] => table
+            .get_mut(key)
+            .is_some_and(|value| remove_from_value(value, rest)),
+        _ => false,
+    }
+}
+
+fn remove_from_value(value: &mut Value, segments: &[Segment]) -> bool {
+    match (value, segments) {
+        (Value::Table(table), _) => remove(table, segments),
+        (Value::Array(array), [Segment::Index(index)]) if *index < array.len() => {
+            array.remove(*index);
+            true
+        }
+        (Value::Array(array), [Segment::Index(index), rest @ ..]) => array
+            .get_mut(*index)
+            .is_some_and(|value| remove_from_value(value, rest)),
+        _ => false,
+    }
+}
+
+/// Keys known at `segments`, taken from the default config.
+fn known_keys(defaults: &Value, segments: &[Segment]) -> Vec<String> {
+    let mut value = defaults;
+    for segment in segments {
+        value = match (value, segment) {
+            (Value::Table(table), Segment::Key(key)) => match table.get(key) {
+                Some(value) => value,
+                None => return Vec::new(),
+            },
+            _ => return Vec::new(),
+        };
+    }
+
+    match value {
+        Value::Table(table) => table.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Deserializes the config, dropping invalid values to report every one
+/// of them. Returns the config along with warnings, or the errors (and
+/// warnings) that stopped it from loading.
+pub fn decode(
+    table: Table,
+    sources: &Sources,
+) -> Result<(Config, Vec<Diagnostic>), Vec<Diagnostic>> {
+    decode_with(table, sources, &|name| std::env::var(name).ok())
+}
+
+/// Like `decode`, looking the variables of the values up with `lookup`.
+fn decode_with(
+    mut table: Table,
+    sources: &Sources,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<(Config, Vec<Diagnostic>), Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+
+    let mut undefined = Vec::new();
+    expand_table(&mut table, &mut Vec::new(), lookup, &mut undefined);
+    // Removed last to first so the indexes of arrays stay valid
+    for (segments, name) in undefined.into_iter().rev() {
+        remove(&mut table, &segments);
+        diagnostics.push(sources.diagnostic(
+            Severity::Error,
+            &segments,
+            format!("uses the undefined variable `{name}`"),
+            None,
+        ));
+    }
+    diagnostics.reverse();
+
+    for (deprecated, renamed) in DEPRECATED_KEYS {
+        if table.contains_key(*deprecated) {
+            diagnostics.push(sources.diagnostic(
+                Severity::Warning,
+                &[Segment::Key(deprecated.to_string())],
+                String::from("is deprecated"),
+                Some(renamed.to_string()),
+            ));
+        }
+    }
+
+    for _ in 0..MAX_DROPPED_VALUES {
+        let mut ignored = Vec::new();
+        let mut on_ignored = |path: serde_ignored::Path| {
+            let mut segments = Vec::new();
+            segments_of_ignored_path(&path, &mut segments);
+            ignored.push(segments);
+        };
+        let deserializer = serde_ignored::Deserializer::new(
+            Value::Table(table.clone()),
+            &mut on_ignored,
+        );
+
+        match serde_path_to_error::deserialize::<_, Config>(deserializer) {
+            Ok(config) => {
+                let defaults = Value::try_from(Config::default())
+                    .unwrap_or_else(|_| Value::Table(Table::new()));
+                for segments in ignored {
+                    let path = key_path(&segments);
+                    if let Some((_, renamed)) =
+                        RENAMED_KEYS.iter().find(|(old, _)| *old == path)
+                    {
+                        diagnostics.push(sources.diagnostic(
+                            Severity::Warning,
+                            &segments,
+                            String::from("was renamed and is ignored"),
+                            Some(renamed.to_string()),
+                        ));
+                        continue;
+                    }
+
+                    let (key, parent) = match segments.split_last() {
+                        Some((Segment::Key(key), parent)) => (key, parent),
+                        _ => continue,
+                    };
+                    let known = known_keys(&defaults, parent);
+                    let suggestion = did_you_mean(key, known.iter().map(String::as_str));
+                    diagnostics.push(sources.diagnostic(
+                        Severity::Warning,
+                        &segments,
+                        String::from("is not a known key and was ignored"),
+                        suggestion,
+                    ));
+                }
+
+                if diagnostics.iter().any(Diagnostic::is_error) {
+                    return Err(diagnostics);
+                }
+                return Ok((config, diagnostics));
+            }
+            Err(err) => {
+                let segments = segments_of_serde_path(err.path());
+                let message = err.inner().to_string();
+                let suggestion = suggestion_for(&message);
+                // Items of arrays (like key bindings) are dropped as a whole
+                let item = segments
+                    .iter()
+                    .rposition(|segment| matches!(segment, Segment::Index(_)));
+                let removed = match item {
+                    Some(index) => &segments[..=index],
+                    None => &segments[..],
+                };
+                if !remove(&mut table, removed) {
+                    diagnostics.push(sources.diagnostic(
+                        Severity::Error,
+                        &segments,
+                        message,
+                        suggestion,
+                    ));
+                    return Err(diagnostics);
+                }
+
+                diagnostics.push(sources.diagnostic(
+                    Severity::Error,
+                    &segments,
+                    message,
+                    suggestion,
+                ));
+            }
+        }
+    }
+
+    diagnostics.push(Diagnostic::error(
+        sources.main_file(),
+        String::from("too many invalid values"),
+    ));
+    Err(diagnostics)
+}
+
+/// Parses `content` read from `path` and deserializes it, used by the
+/// tests of the config with documents that don't import anything.
+#[cfg(test)]
+pub fn decode_str(
+    content: &str,
+    path: &Path,
+) -> Result<(Config, Vec<Diagnostic>), Vec<Diagnostic>> {
+    let table = toml::from_str::<Table>(content)
+        .map_err(|err| vec![Diagnostic::syntax(path, content, &err)])?;
+    decode(table, &Sources::new(path, content, &[]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_test(content: &str) -> Result<(Config, Vec<Diagnostic>), Vec<Diagnostic>> {
+        decode_str(content, Path::new("config.toml"))
+    }
+
+    #[test]
+    fn test_syntax_error_position() {
+        let errors = decode_test("padding-x = 1\n[window\nwidth = 2").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].is_error());
+        assert_eq!((errors[0].line, errors[0].column), (2, 8));
+        assert!(errors[0]
+            .to_string()
+            .starts_with("config.toml:2:8: error: "));
+    }
+
+    #[test]
+    fn test_invalid_enum_value() {
+        let errors = decode_test(
+            "padding-x = 3\n\n[navigation]\nmode = \"TopTabs\"\nclickable = true\n",
+        )
+        .unwrap_err();
+        assert_eq!(errors.len(), 1);
+        let error = &errors[0];
+        assert_eq!(error.severity, Severity::Error);
+        assert_eq!(error.key, "navigation.mode");
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.suggestion.as_deref(), Some("TopTab"));
+    }
+
+    #[test]
+    fn test_undefined_variables() {
+        let content = "working-dir = \"${DIR}/work\"\n\
+             env-vars = [\"A=$UNDEFINED\", \"B=${UNDEFINED:-2}\"]\n\
+             [shell]\nprogram = \"$UNDEFINED/fish\"\n\
+             [editor]\nprogram = \"awk\"\nargs = [\"{print $NF}\"]\n\
+             [bindings]\nkeys = [{ key = \"a\", with = \"super\", text = \"$UNDEFINED\" }]\n";
+        let table = toml::from_str::<Table>(content).unwrap();
+        let lookup = |name: &str| (name == "DIR").then(|| String::from("/tmp/rio"));
+        let diagnostics = decode_with(
+            table.clone(),
+            &Sources::new(Path::new("config.toml"), content, &[]),
+            &lookup,
+        )
+        .unwrap_err();
+
+        let keys: Vec<(&str, usize)> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.key.as_str(), diagnostic.line))
+            .collect();
+        // Without its program the shell is invalid as well
+        assert_eq!(
+            keys,
+            vec![("env-vars[0]", 2), ("shell.program", 4), ("shell", 3)]
+        );
+        assert!(diagnostics.iter().all(Diagnostic::is_error));
+        assert!(diagnostics[0].message.contains("`UNDEFINED`"));
+
+        let lookup = |name: &str| match name {
+            "DIR" => Some(String::from("/tmp/rio")),
+            "UNDEFINED" => Some(String::from("/usr/bin")),
+            _ => None,
+        };
+        let (config, diagnostics) = decode_with(
+            table,
+            &Sources::new(Path::new("config.toml"), content, &[]),
+            &lookup,
+        )
+        .unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(config.working_dir.as_deref(), Some("/tmp/rio/work"));
+        assert_eq!(config.env_vars, vec!["A=/usr/bin", "B=/usr/bin"]);
+        assert_eq!(config.shell.program, "/usr/bin/fish");
+        assert_eq!(config.editor.args, vec![String::from("{print $NF}")]);
+        assert_eq!(config.bindings.keys[0].text, "$UNDEFINED");
+    }
+
+    #[test]
+    fn test_unknown_and_deprecated_keys() {
+        let (config, warnings) = decode_test(
+            "hide-cursor-when-typing = true\npading-x = 3\n[window]\nwidht = 300\n\
+             background-opacity = 0.5\n",
+        )
+        .unwrap();
+        assert!(config.hide_cursor_when_typing);
+
+        let keys: Vec<(&str, Option<&str>, usize)> = warnings
+            .iter()
+            .map(|warning| {
+                (
+                    warning.key.as_str(),
+                    warning.suggestion.as_deref(),
+                    warning.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (
+                    "hide-cursor-when-typing",
+                    Some("hide-mouse-cursor-when-typing"),
+                    1
+                ),
+                ("pading-x", Some("padding-x"), 2),
+                ("window.background-opacity", Some("window.opacity"), 5),
+                ("window.widht", Some("width"), 4),
+            ]
+        );
+        assert!(warnings[2].message.contains("renamed"));
+    }
+
+    #[test]
+    fn test_invalid_values_are_all_reported() {
+        let errors = decode_test(
+            "padding-x = \"3\"\n[bindings]\nkeys = [\n  { key = \"q\", action = \"Quit\" },\n  { key = 1 },\n]\n",
+        )
+        .unwrap_err();
+        let keys: Vec<(&str, usize, usize)> = errors
+            .iter()
+            .map(|error| (error.key.as_str(), error.line, error.column))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![("bindings.keys[1].key", 5, 5), ("padding-x", 1, 1)]
+        );
+        assert!(errors.iter().all(Diagnostic::is_error));
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        assert_eq!(
+            did_you_mean("Bokmark", ["Plain", "TopTab", "Bookmark"]),
+            Some(String::from("Bookmark"))
+        );
+        assert_eq!(did_you_mean("xyz", ["Plain", "TopTab"]), None);
+    }
+}
@@ -0,0 +1,163 @@
+// Expansion of `~` and environment variables in the strings of the
+// config, so paths and commands can be shared between machines.
+
+use std::borrow::Cow;
+
+/// Keys left untouched since their `$` means something else, like the
+/// text sent to the terminal, the programs run by actions or the capture
+/// groups of a regex. The `[platform]` of the current OS is expanded once
+/// merged, the others may use variables that only exist in their OS.
+pub const SKIPPED_KEYS: &[&str] = &[
+    "bindings.keys[].text",
+    "bindings.keys[].action",
+    "bindings.mouse[].action",
+    "highlight.rules[].regex",
+    "triggers",
+    "platform",
+];
+
+/// Whether the value at the dotted `path` is left untouched, which is also
+/// the case of the `args` of every program since they're passed as they
+/// are, like `awk '{print $NF}'`, and shells expand them on their own.
+pub fn is_skipped(path: &str) -> bool {
+    SKIPPED_KEYS.contains(&path) || path == "args" || path.ends_with(".args")
+}
+
+/// Expands a leading `~` to the home directory along with `$VAR`,
+/// `${VAR}` and `${VAR:-default}`, `$$` is a literal `$`. Returns the
+/// name of the first undefined variable on failure.
+pub fn expand(value: &str) -> Result<Cow<'_, str>, String> {
+    expand_with(value, &|name| std::env::var(name).ok())
+}
+
+/// Like `expand`, looking the variables up with `lookup`.
+pub fn expand_with<'a>(
+    value: &'a str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<Cow<'a, str>, String> {
+    let home = if value == "~" || value.starts_with("~/") || value.starts_with("~\\") {
+        dirs::home_dir()
+    } else {
+        None
+    };
+    if home.is_none() && !value.contains('$') {
+        return Ok(Cow::Borrowed(value));
+    }
+
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    if let Some(home) = home {
+        expanded.push_str(&home.to_string_lossy());
+        rest = &value[1..];
+    }
+
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        if let Some(after) = after.strip_prefix('$') {
+            expanded.push('$');
+            rest = after;
+        } else if let Some(braced) = after.strip_prefix('{') {
+            let Some(end) = braced.find('}') else {
+                expanded.push('$');
+                rest = after;
+                continue;
+            };
+            let (name, default) = match braced[..end].split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (&braced[..end], None),
+            };
+            // Like shells, the default is also used for empty variables
+            match (lookup(name), default) {
+                (Some(value), None) => expanded.push_str(&value),
+                (Some(value), Some(_)) if !value.is_empty() => expanded.push_str(&value),
+                (_, Some(default)) => expanded.push_str(&expand_with(default, lookup)?),
+                (None, None) => return Err(name.to_string()),
+            }
+            rest = &braced[end + 1..];
+        } else {
+            let length = after
+                .char_indices()
+                .take_while(|(index, c)| {
+                    c.is_ascii_alphabetic()
+                        || *c == '_'
+                        || (*index > 0 && c.is_ascii_digit())
+                })
+                .count();
+            if length == 0 {
+                expanded.push('$');
+                rest = after;
+                continue;
+            }
+            let name = &after[..length];
+            expanded.push_str(&lookup(name).ok_or_else(|| name.to_string())?);
+            rest = &after[length..];
+        }
+    }
+    expanded.push_str(rest);
+
+    Ok(Cow::Owned(expanded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "HOME" => Some(String::from("/home/rio")),
+            "EMPTY" => Some(String::new()),
+            "SHELL_2" => Some(String::from("fish")),
+            _ => None,
+        }
+    }
+
+    fn expand_test(value: &str) -> Result<String, String> {
+        expand_with(value, &lookup).map(Cow::into_owned)
+    }
+
+    #[test]
+    fn test_expand_variables() {
+        assert_eq!(expand_test("/bin/fish").unwrap(), "/bin/fish");
+        assert_eq!(expand_test("$HOME/bin").unwrap(), "/home/rio/bin");
+        assert_eq!(expand_test("${HOME}/bin").unwrap(), "/home/rio/bin");
+        assert_eq!(expand_test("/usr/bin/$SHELL_2").unwrap(), "/usr/bin/fish");
+        assert_eq!(expand_test("${EDITOR:-vim}").unwrap(), "vim");
+        assert_eq!(expand_test("${EMPTY:-vim}").unwrap(), "vim");
+        assert_eq!(expand_test("a${EMPTY}b").unwrap(), "ab");
+        assert_eq!(
+            expand_test("${EDITOR:-$HOME/vim}").unwrap(),
+            "/home/rio/vim"
+        );
+        assert_eq!(
+            expand_test("cost: $$5, $1 $ ${").unwrap(),
+            "cost: $5, $1 $ ${"
+        );
+
+        assert_eq!(expand_test("$UNDEFINED/bin").unwrap_err(), "UNDEFINED");
+        assert_eq!(expand_test("${UNDEFINED}").unwrap_err(), "UNDEFINED");
+    }
+
+    #[test]
+    fn test_skipped_keys() {
+        assert!(is_skipped("shell.args"));
+        assert!(is_skipped("editor.args"));
+        assert!(is_skipped("layouts.dev.panes[].shell.args"));
+        assert!(is_skipped("bindings.keys[].text"));
+        assert!(!is_skipped("shell.program"));
+        assert!(!is_skipped("env-vars"));
+    }
+
+    #[test]
+    fn test_expand_home() {
+        let Some(home) = dirs::home_dir() else {
+            return;
+        };
+        let home = home.to_string_lossy();
+        assert_eq!(expand_test("~").unwrap(), home);
+        assert_eq!(expand_test("~/bg.png").unwrap(), format!("{home}/bg.png"));
+        assert_eq!(expand_test("a/~/b").unwrap(), "a/~/b");
+        assert_eq!(expand_test("~rio").unwrap(), "~rio");
+    }
+}
@@ -0,0 +1,39 @@
+use crate::crosswords::export::ExportFormat;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Screen and scrollback exports (`SaveScreen` and `SaveScrollback`).
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Export {
+    /// Directory where the exported files are written.
+    #[serde(default)]
+    pub directory: Option<String>,
+    /// Format used when the action doesn't specify one.
+    #[serde(default = "default_export_format")]
+    pub format: ExportFormat,
+}
+
+fn default_export_format() -> ExportFormat {
+    ExportFormat::Text
+}
+
+impl Default for Export {
+    fn default() -> Export {
+        Export {
+            directory: None,
+            format: default_export_format(),
+        }
+    }
+}
+
+impl Export {
+    /// Configured directory, falls back to the home directory.
+    pub fn directory(&self) -> PathBuf {
+        self.directory
+            .as_ref()
+            .map(PathBuf::from)
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(std::env::temp_dir)
+    }
+}
@@ -0,0 +1,47 @@
+use crate::config::colors::{
+    deserialize_to_optional_arr, serialize_optional_arr, ColorArray,
+};
+use crate::config::defaults::default_bool_true;
+use serde::{Deserialize, Serialize};
+
+/// Styles always applied to the text matched by the rules, on top of the
+/// colors used by the program.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Highlight {
+    #[serde(default = "default_bool_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub rules: Vec<HighlightRule>,
+}
+
+impl Default for Highlight {
+    fn default() -> Highlight {
+        Highlight {
+            enabled: true,
+            rules: Vec::default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct HighlightRule {
+    pub regex: String,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_to_optional_arr",
+        serialize_with = "serialize_optional_arr"
+    )]
+    pub foreground: Option<ColorArray>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_to_optional_arr",
+        serialize_with = "serialize_optional_arr"
+    )]
+    pub background: Option<ColorArray>,
+    #[serde(default)]
+    pub underline: bool,
+    #[serde(default)]
+    pub bold: bool,
+}
@@ -0,0 +1,257 @@
+// Config files can import other config files with `import = [...]`. The
+// imported files are deep merged in order and the importing file is merged
+// last, so later files win. Key bindings are concatenated instead.
+
+use crate::config::diagnostics::Diagnostic;
+use crate::config::expand;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// Nested imports deeper than this are considered a cycle.
+const MAX_DEPTH: usize = 8;
+
+/// Arrays that are concatenated instead of replaced when merging.
+const CONCATENATED_ARRAYS: &[&str] = &["bindings.keys", "bindings.mouse"];
+
+/// Config with its imports merged in.
+#[derive(Debug)]
+pub struct Imported {
+    pub table: Table,
+    /// Every imported file, nested ones included.
+    pub files: Vec<PathBuf>,
+}
+
+/// Config that failed to import.
+#[derive(Debug)]
+pub struct ImportError {
+    pub diagnostic: Diagnostic,
+    /// Files imported until the error, the one that couldn't be read
+    /// included, so they are watched until the config is fixed.
+    pub files: Vec<PathBuf>,
+}
+
+/// Parses `content` (read from `path`) and merges the files it imports.
+pub fn load(content: &str, path: &Path) -> Result<Imported, Box<ImportError>> {
+    let mut files = Vec::new();
+    match load_table(content, path, 0, &mut files) {
+        Ok(table) => Ok(Imported { table, files }),
+        Err(diagnostic) => Err(Box::new(ImportError { diagnostic, files })),
+    }
+}
+
+/// Resolves an imported path, `~` is the home directory and relative
+/// paths start from the directory of the importing file.
+pub fn resolve(path: &str, relative_to: &Path) -> PathBuf {
+    if let Some(home) = dirs::home_dir() {
+        if path == "~" {
+            return home;
+        }
+        if let Some(rest) = path.strip_prefix("~/") {
+            return home.join(rest);
+        }
+    }
+
+    let path = PathBuf::from(path);
+    if path.is_absolute() {
+        path
+    } else {
+        relative_to.join(path)
+    }
+}
+
+fn load_table(
+    content: &str,
+    path: &Path,
+    depth: usize,
+    files: &mut Vec<PathBuf>,
+) -> Result<Table, Diagnostic> {
+    let mut table = content
+        .parse::<Table>()
+        .map_err(|err| Diagnostic::syntax(path, content, &err))?;
+
+    let imports = match table.remove("import") {
+        Some(Value::Array(imports)) => imports,
+        Some(_) => {
+            return Err(Diagnostic::error(
+                path,
+                String::from("import must be an array of paths"),
+            ))
+        }
+        None => return Ok(table),
+    };
+
+    if depth >= MAX_DEPTH {
+        return Err(Diagnostic::error(
+            path,
+            String::from("too many nested imports, is a file importing itself?"),
+        ));
+    }
+
+    let directory = path.parent().unwrap_or(Path::new("."));
+    let mut merged = Table::new();
+    for import in imports {
+        let import = match import {
+            Value::String(import) => {
+                let import = expand::expand(&import).map_err(|name| {
+                    Diagnostic::error(
+                        path,
+                        format!("import uses the undefined variable `{name}`"),
+                    )
+                })?;
+                resolve(&import, directory)
+            }
+            _ => {
+                return Err(Diagnostic::error(
+                    path,
+                    String::from("import must be an array of paths"),
+                ))
+            }
+        };
+
+        if !files.contains(&import) {
+            files.push(import.clone());
+        }
+        let content = std::fs::read_to_string(&import).map_err(|err| {
+            Diagnostic::error(
+                path,
+                format!("unable to import {}: {err}", import.display()),
+            )
+        })?;
+        let imported = load_table(&content, &import, depth + 1, files)?;
+        merge(&mut merged, imported, "");
+    }
+    merge(&mut merged, table, "");
+
+    Ok(merged)
+}
+
+/// Merges `overlay` into `base`, tables are merged key by key and any
+/// other value of `overlay` replaces the one of `base`.
+pub(crate) fn merge(base: &mut Table, overlay: Table, prefix: &str) {
+    for (key, value) in overlay {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base_table)), Value::Table(table)) => {
+                merge(base_table, table, &path);
+            }
+            (Some(Value::Array(base_array)), Value::Array(array))
+                if CONCATENATED_ARRAYS.contains(&path.as_str()) =>
+            {
+                base_array.extend(array);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(directory: &Path, name: &str, content: &str) -> PathBuf {
+        let path = directory.join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_import_merge_order() {
+        let directory = std::env::temp_dir().join("rio-import-merge-order");
+        std::fs::create_dir_all(directory.join("themes")).unwrap();
+        write(
+            &directory,
+            "team.toml",
+            r#"
+            padding-x = 10
+            [window]
+            width = 800
+            height = 600
+            [bindings]
+            keys = [{ key = "q", with = "super", action = "Quit" }]
+            "#,
+        );
+        write(
+            &directory.join("themes"),
+            "work.toml",
+            r#"
+            [window]
+            height = 700
+            "#,
+        );
+        let config = write(
+            &directory,
+            "config.toml",
+            r#"
+            import = ["team.toml", "themes/work.toml"]
+            padding-x = 20
+            [bindings]
+            keys = [{ key = "n", with = "super", action = "CreateWindow" }]
+            "#,
+        );
+
+        let content = std::fs::read_to_string(&config).unwrap();
+        let imported = load(&content, &config).unwrap();
+        assert_eq!(
+            imported.files,
+            vec![
+                directory.join("team.toml"),
+                directory.join("themes").join("work.toml")
+            ]
+        );
+
+        let table = imported.table;
+        assert!(table.get("import").is_none());
+        assert_eq!(table["padding-x"].as_integer(), Some(20));
+        assert_eq!(table["window"]["width"].as_integer(), Some(800));
+        assert_eq!(table["window"]["height"].as_integer(), Some(700));
+        let keys = table["bindings"]["keys"].as_array().unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0]["action"].as_str(), Some("Quit"));
+        assert_eq!(keys[1]["action"].as_str(), Some("CreateWindow"));
+    }
+
+    #[test]
+    fn test_import_errors() {
+        let directory = std::env::temp_dir().join("rio-import-errors");
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let config = write(&directory, "missing.toml", r#"import = ["nope.toml"]"#);
+        let content = std::fs::read_to_string(&config).unwrap();
+        let error = load(&content, &config).unwrap_err();
+        assert!(error.diagnostic.message.contains("nope.toml"));
+        assert_eq!(error.files, vec![directory.join("nope.toml")]);
+
+        let config = write(&directory, "cycle.toml", r#"import = ["cycle.toml"]"#);
+        let content = std::fs::read_to_string(&config).unwrap();
+        let error = load(&content, &config).unwrap_err();
+        assert!(error.diagnostic.message.contains("too many nested imports"));
+        assert_eq!(error.files, vec![config]);
+
+        let config = write(&directory, "invalid.toml", r#"import = "a.toml""#);
+        let content = std::fs::read_to_string(&config).unwrap();
+        assert!(load(&content, &config).is_err());
+    }
+
+    #[test]
+    fn test_resolve_import_path() {
+        let directory = Path::new("/etc/rio");
+        assert_eq!(
+            resolve("team.toml", directory),
+            PathBuf::from("/etc/rio/team.toml")
+        );
+        assert_eq!(
+            resolve("/tmp/a.toml", directory),
+            PathBuf::from("/tmp/a.toml")
+        );
+        if let Some(home) = dirs::home_dir() {
+            assert_eq!(resolve("~/a.toml", directory), home.join("a.toml"));
+        }
+    }
+}
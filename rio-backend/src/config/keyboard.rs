@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use super::defaults::default_disable_ctlseqs_alt;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Keyboard {
     // Disable ctlseqs with ALT keys
     // For example: Terminal.app does not deal with ctlseqs with ALT keys
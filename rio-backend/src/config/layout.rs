@@ -0,0 +1,102 @@
+use crate::config::Shell;
+use serde::{Deserialize, Serialize};
+
+/// Split layout that can be opened as a tab, declared under `[layouts.<name>]`.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Layout {
+    #[serde(default)]
+    pub panes: Vec<LayoutPane>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutSplit {
+    #[default]
+    Right,
+    Down,
+}
+
+/// Every pane (except the first one) is created by splitting the pane
+/// in `from` (or the previous pane) towards `split`.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LayoutPane {
+    #[serde(default)]
+    pub split: LayoutSplit,
+    #[serde(default)]
+    pub from: Option<usize>,
+    /// Percentage of the split pane area used by this pane.
+    #[serde(default)]
+    pub size: Option<u8>,
+    #[serde(default)]
+    pub shell: Option<Shell>,
+    #[serde(default, rename = "working-dir")]
+    pub working_dir: Option<String>,
+    /// Overrides `[highlight] enabled` for this pane.
+    #[serde(default)]
+    pub highlight: Option<bool>,
+}
+
+impl LayoutPane {
+    /// Index of the pane that should be split to create the pane in `index`.
+    #[inline]
+    pub fn parent(&self, index: usize) -> usize {
+        let previous = index.saturating_sub(1);
+        match self.from {
+            Some(from) if from < index => from,
+            _ => previous,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Root {
+        layouts: std::collections::HashMap<String, Layout>,
+    }
+
+    #[test]
+    fn test_layout_panes() {
+        let root: Root = toml::from_str(
+            r#"
+            [layouts.dev]
+            panes = [
+                { shell = { program = "vim" }, working-dir = "/tmp" },
+                { split = "right", size = 40, shell = { program = "cargo", args = ["watch"] } },
+                { split = "down", from = 1, highlight = true },
+            ]
+        "#,
+        )
+        .unwrap();
+
+        let layout = &root.layouts["dev"];
+        assert_eq!(layout.panes.len(), 3);
+        assert_eq!(layout.panes[0].working_dir, Some(String::from("/tmp")));
+        assert_eq!(layout.panes[1].split, LayoutSplit::Right);
+        assert_eq!(layout.panes[1].size, Some(40));
+        assert_eq!(
+            layout.panes[1].shell,
+            Some(Shell {
+                program: String::from("cargo"),
+                args: vec![String::from("watch")],
+            })
+        );
+        assert_eq!(layout.panes[2].split, LayoutSplit::Down);
+        assert_eq!(layout.panes[2].highlight, Some(true));
+        assert_eq!(layout.panes[0].highlight, None);
+        assert_eq!(layout.panes[2].parent(2), 1);
+        assert_eq!(layout.panes[1].parent(1), 0);
+
+        // Panes can only be created from previous panes
+        let pane = LayoutPane {
+            from: Some(5),
+            ..LayoutPane::default()
+        };
+        assert_eq!(pane.parent(2), 1);
+    }
+}
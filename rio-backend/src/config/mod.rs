@@ -1,26 +1,58 @@
+pub mod bell;
 pub mod bindings;
 pub mod colors;
 pub mod defaults;
+pub mod diagnostics;
+pub mod expand;
+pub mod export;
+pub mod highlight;
+pub mod import;
 pub mod keyboard;
+pub mod layout;
+pub mod monitor;
 pub mod navigation;
+pub mod overrides;
+pub mod platform;
+pub mod record;
 pub mod renderer;
+#[cfg(feature = "schemars")]
+pub mod schema;
+pub mod scrollback_editor;
 pub mod theme;
+pub mod theme_formats;
+pub mod timestamps;
 pub mod title;
+pub mod triggers;
 pub mod window;
 
 use crate::ansi::CursorShape;
+use crate::config::bell::Bell;
 use crate::config::bindings::Bindings;
 use crate::config::defaults::*;
+use crate::config::diagnostics::Diagnostic;
+use crate::config::export::Export;
+use crate::config::highlight::Highlight;
 use crate::config::keyboard::Keyboard;
+use crate::config::layout::Layout;
+use crate::config::monitor::Monitor;
 use crate::config::navigation::Navigation;
+use crate::config::overrides::ConfigOverrides;
+use crate::config::platform::Platform;
+use crate::config::record::Record;
 use crate::config::renderer::Renderer;
+use crate::config::scrollback_editor::ScrollbackEditor;
+use crate::config::timestamps::Timestamps;
 use crate::config::title::Title;
+use crate::config::triggers::Trigger;
 use crate::config::window::Window;
 use colors::Colors;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use std::{default::Default, fs::File};
+#[cfg(feature = "ui")]
 use sugarloaf::font::fonts::SugarloafFonts;
 use theme::{AdaptiveColors, AdaptiveTheme, Theme};
 use tracing::warn;
@@ -28,34 +60,99 @@ use tracing::warn;
 #[derive(Clone, Debug)]
 pub enum ConfigError {
     ErrLoadingConfig(String),
+    /// Errors (and warnings) that stopped the config from loading, with
+    /// the files it imports, the ones that couldn't be read included.
+    Invalid(Vec<Diagnostic>, Vec<PathBuf>),
     ErrLoadingTheme(String),
     PathNotFound,
 }
 
-#[derive(Default, Debug, Serialize, Deserialize, PartialEq, Clone)]
-pub struct Shell {
-    pub program: String,
-    #[serde(default)]
-    pub args: Vec<String>,
+impl ConfigError {
+    /// Files imported by the config that failed to load, which should be
+    /// watched so fixing them reloads the config.
+    pub fn imported(&self) -> &[PathBuf] {
+        match self {
+            ConfigError::Invalid(_, imported) => imported,
+            _ => &[],
+        }
+    }
 }
 
-#[derive(Default, Debug, Serialize, Deserialize, PartialEq, Clone)]
-pub struct Platform {
-    pub linux: Option<PlatformConfig>,
-    pub windows: Option<PlatformConfig>,
-    pub macos: Option<PlatformConfig>,
+/// Config file merged with its imports and the `[platform]` of the
+/// current OS, it can be decoded with different overrides (like the ones
+/// of each window) without being read again.
+#[derive(Debug)]
+pub struct ConfigFile {
+    path: PathBuf,
+    content: String,
+    imported: import::Imported,
+}
+
+impl ConfigFile {
+    /// Reads the config file, see `config_file_path`.
+    pub fn read() -> Result<Self, ConfigError> {
+        let path = config_file_path();
+        if !path.exists() {
+            return Err(ConfigError::PathNotFound);
+        }
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => ConfigFile::parse(path, content),
+            Err(err_message) => {
+                Err(ConfigError::ErrLoadingConfig(err_message.to_string()))
+            }
+        }
+    }
+
+    fn parse(path: PathBuf, content: String) -> Result<Self, ConfigError> {
+        let mut imported = import::load(&content, &path)
+            .map_err(|error| ConfigError::Invalid(vec![error.diagnostic], error.files))?;
+        platform::apply(&mut imported.table);
+        Ok(ConfigFile {
+            path,
+            content,
+            imported,
+        })
+    }
+
+    /// Decodes the config with the overrides merged over it.
+    fn decode(&self, overrides: &ConfigOverrides) -> Result<Config, ConfigError> {
+        let mut table = self.imported.table.clone();
+        overrides.apply(&mut table);
+        let sources =
+            diagnostics::Sources::new(&self.path, &self.content, &self.imported.files)
+                .with_overrides(overrides);
+        let (mut decoded, warnings) =
+            diagnostics::decode(table, &sources).map_err(|errors| {
+                ConfigError::Invalid(errors, self.imported.files.clone())
+            })?;
+        for warning in &warnings {
+            warn!("{warning}");
+        }
+        decoded.imported = self.imported.files.clone();
+        decoded.diagnostics = warnings;
+        Ok(decoded)
+    }
+
+    /// Decodes the config with the overrides merged over it and loads its
+    /// themes from the themes directory.
+    pub fn load(&self, overrides: &ConfigOverrides) -> Result<Config, ConfigError> {
+        let mut decoded = self.decode(overrides)?;
+        decoded.load_themes()?;
+        Ok(decoded)
+    }
 }
 
-/// Other platform specific configuration options can be added here.
 #[derive(Default, Debug, Serialize, Deserialize, PartialEq, Clone)]
-pub struct PlatformConfig {
-    shell: Option<Shell>,
-    navigation: Option<Navigation>,
-    window: Option<Window>,
-    renderer: Option<Renderer>,
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Shell {
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Scroll {
     pub multiplier: f64,
     pub divider: f64,
@@ -71,6 +168,7 @@ impl Default for Scroll {
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Developer {
     #[serde(default = "bool::default", rename = "enable-fps-counter")]
     pub enable_fps_counter: bool,
@@ -91,6 +189,7 @@ impl Default for Developer {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Config {
     #[serde(default)]
     pub cursor: CursorConfig,
@@ -122,6 +221,7 @@ pub struct Config {
         rename = "adaptive-theme"
     )]
     pub adaptive_theme: Option<AdaptiveTheme>,
+    #[cfg(feature = "ui")]
     #[serde(default = "SugarloafFonts::default")]
     pub fonts: SugarloafFonts,
     #[serde(default = "default_editor")]
@@ -131,12 +231,14 @@ pub struct Config {
     #[serde(rename = "padding-y", default = "default_padding_y")]
     pub padding_y: [f32; 2],
     #[serde(default = "Vec::default", rename = "env-vars")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub env_vars: Vec<String>,
     #[serde(default = "default_option_as_alt", rename = "option-as-alt")]
     pub option_as_alt: String,
-    #[serde(default = "Colors::default", skip_serializing)]
+    #[serde(default = "Colors::default")]
     pub colors: Colors,
     #[serde(default = "Option::default", skip_serializing)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
     pub adaptive_colors: Option<AdaptiveColors>,
     #[serde(default = "Developer::default")]
     pub developer: Developer,
@@ -157,9 +259,36 @@ pub struct Config {
     pub hide_cursor_when_typing: bool,
     #[serde(default = "Renderer::default")]
     pub renderer: Renderer,
+    #[serde(default = "HashMap::default")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
+    pub layouts: HashMap<String, Layout>,
+    #[serde(default = "Record::default")]
+    pub record: Record,
+    #[serde(default = "Export::default")]
+    pub export: Export,
+    #[serde(default = "Vec::default")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
+    pub triggers: Vec<Trigger>,
+    #[serde(default = "Highlight::default")]
+    pub highlight: Highlight,
+    #[serde(default = "Timestamps::default")]
+    pub timestamps: Timestamps,
+    #[serde(default = "Monitor::default")]
+    pub monitor: Monitor,
+    #[serde(default = "Bell::default")]
+    pub bell: Bell,
+    #[serde(default = "ScrollbackEditor::default", rename = "scrollback-editor")]
+    pub scrollback_editor: ScrollbackEditor,
+    /// Files merged through `import`, nested imports included.
+    #[serde(skip)]
+    pub imported: Vec<PathBuf>,
+    /// Issues found while loading, the values they point to were ignored.
+    #[serde(skip)]
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CursorConfig {
     #[serde(default = "default_cursor")]
     pub shape: CursorShape,
@@ -171,21 +300,75 @@ pub struct CursorConfig {
 
 #[cfg(not(target_os = "windows"))]
 #[inline]
-pub fn config_dir_path() -> PathBuf {
+fn default_config_dir_path() -> PathBuf {
     let home_dir = dirs::home_dir().unwrap();
     home_dir.join(".config").join("rio")
 }
 
 #[cfg(target_os = "windows")]
 #[inline]
-pub fn config_dir_path() -> PathBuf {
+fn default_config_dir_path() -> PathBuf {
     let home_dir = dirs::home_dir().unwrap();
     home_dir.join("AppData").join("Local").join("rio")
 }
 
+/// Directory of the config file, themes and logs. The directory of the
+/// file selected with `--config-file` when there is one.
+#[inline]
+pub fn config_dir_path() -> PathBuf {
+    match CONFIG_FILE_PATH.get().and_then(|path| path.parent()) {
+        Some(directory) => directory.to_owned(),
+        None => default_config_dir_path(),
+    }
+}
+
+/// Config file selected with `--config-file`.
+static CONFIG_FILE_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Uses `path` as the config file instead of the one in the config
+/// directory, it can only be set once.
+pub fn set_config_file_path(path: PathBuf) {
+    if CONFIG_FILE_PATH.set(path).is_err() {
+        warn!("config file path was already set");
+    }
+}
+
 #[inline]
 pub fn config_file_path() -> PathBuf {
-    config_dir_path().join("config.toml")
+    match CONFIG_FILE_PATH.get() {
+        Some(path) => path.to_owned(),
+        None => default_config_dir_path().join("config.toml"),
+    }
+}
+
+#[inline]
+pub fn themes_dir_path() -> PathBuf {
+    config_dir_path().join("themes")
+}
+
+/// Names of the themes available in the themes directory, sorted.
+pub fn theme_names() -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(themes_dir_path())
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.extension().is_some_and(|ext| {
+                        theme_formats::EXTENSIONS
+                            .contains(&ext.to_string_lossy().as_ref())
+                    })
+                })
+                .filter_map(|path| {
+                    path.file_stem()
+                        .map(|stem| stem.to_string_lossy().to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names.dedup();
+    names
 }
 
 #[inline]
@@ -240,7 +423,18 @@ pub fn create_config_file(path: Option<PathBuf>) {
 }
 
 impl Config {
-    #[cfg(test)]
+    /// Decodes the content of the config file at `path`, merging the
+    /// files listed in `import`, the `[platform]` of the current OS and
+    /// then the overrides.
+    fn decode(
+        content: &str,
+        path: &Path,
+        overrides: &ConfigOverrides,
+    ) -> Result<Self, ConfigError> {
+        ConfigFile::parse(path.to_owned(), content.to_owned())?.decode(overrides)
+    }
+
+    #[cfg(all(test, feature = "ui"))]
     fn load_from_path(path: &PathBuf) -> Self {
         if path.exists() {
             let content = std::fs::read_to_string(path).unwrap();
@@ -251,11 +445,11 @@ impl Config {
             Config::default()
         }
     }
-    #[cfg(test)]
+    #[cfg(all(test, feature = "ui"))]
     fn load_from_path_without_fallback(path: &PathBuf) -> Result<Self, String> {
         if path.exists() {
             let content = std::fs::read_to_string(path).unwrap();
-            match toml::from_str::<Config>(&content) {
+            match Config::decode(&content, path, &ConfigOverrides::default()) {
                 Ok(mut decoded) => {
                     let theme = &decoded.theme;
                     if theme.is_empty() {
@@ -263,7 +457,7 @@ impl Config {
                     }
 
                     let tmp = std::env::temp_dir();
-                    let path = tmp.join(theme).with_extension("toml");
+                    let path = theme_formats::find(&tmp, theme);
                     if let Ok(loaded_theme) = Config::load_theme(&path) {
                         decoded.colors = loaded_theme.colors;
                     } else {
@@ -272,7 +466,7 @@ impl Config {
 
                     if let Some(adaptive_theme) = &decoded.adaptive_theme {
                         let light_theme = &adaptive_theme.light;
-                        let path = tmp.join(light_theme).with_extension("toml");
+                        let path = theme_formats::find(&tmp, light_theme);
                         let mut adaptive_colors = AdaptiveColors {
                             dark: None,
                             light: None,
@@ -285,7 +479,7 @@ impl Config {
                         }
 
                         let dark_theme = &adaptive_theme.dark;
-                        let path = tmp.join(dark_theme).with_extension("toml");
+                        let path = theme_formats::find(&tmp, dark_theme);
                         if let Ok(dark_loaded_theme) = Config::load_theme(&path) {
                             adaptive_colors.dark = Some(dark_loaded_theme.colors);
                         } else {
@@ -308,10 +502,11 @@ impl Config {
         }
     }
 
+    /// Loads a Rio theme, or the color scheme of another terminal.
     fn load_theme(path: &PathBuf) -> Result<Theme, String> {
         if path.exists() {
-            let content = std::fs::read_to_string(path).unwrap();
-            match toml::from_str::<Theme>(&content) {
+            let table = theme_formats::load(path)?;
+            match toml::Value::Table(table).try_into::<Theme>() {
                 Ok(decoded) => Ok(decoded),
                 Err(err_message) => Err(format!("error parsing: {:?}", err_message)),
             }
@@ -320,157 +515,98 @@ impl Config {
         }
     }
 
+    /// Replaces the colors with the ones of a theme from the themes directory.
+    pub fn set_theme(&mut self, theme: &str) -> Result<(), String> {
+        let path = theme_formats::find(&themes_dir_path(), theme);
+        let loaded_theme = Config::load_theme(&path)?;
+        self.theme = theme.to_string();
+        self.colors = loaded_theme.colors;
+        Ok(())
+    }
+
     pub fn to_string(&self) -> Result<String, toml::ser::Error> {
         toml::to_string(self)
     }
 
-    pub fn load() -> Self {
-        let config_path = config_dir_path();
-        let path = config_file_path();
-        if path.exists() {
-            let content = std::fs::read_to_string(path).unwrap();
-            match toml::from_str::<Config>(&content) {
-                Ok(mut decoded) => {
-                    let theme = &decoded.theme;
-                    if theme.is_empty() {
-                        return decoded;
-                    }
-
-                    let path = config_path
-                        .join("themes")
-                        .join(theme)
-                        .with_extension("toml");
-                    if let Ok(loaded_theme) = Config::load_theme(&path) {
-                        decoded.colors = loaded_theme.colors;
-                    } else {
-                        warn!("failed to load theme: {}", theme);
-                    }
-
-                    decoded
-                }
-                Err(err_message) => {
-                    warn!("failure to parse config file, falling back to default...\n{err_message:?}");
-                    Config::default()
+    /// Loads the config file with the overrides merged over it, or the
+    /// defaults with the overrides when it can't be loaded.
+    pub fn load(overrides: &ConfigOverrides) -> Self {
+        match Config::try_load(overrides) {
+            Ok(config) => config,
+            Err(ConfigError::Invalid(diagnostics, _)) => {
+                for diagnostic in diagnostics {
+                    warn!("{diagnostic}");
                 }
+                warn!("failure to parse config file, falling back to default...");
+                Config::default_with_overrides(overrides)
             }
-        } else {
-            Config::default()
+            Err(_) => Config::default_with_overrides(overrides),
         }
     }
 
-    pub fn try_load() -> Result<Self, ConfigError> {
-        let path = config_file_path();
-        if path.exists() {
-            match std::fs::read_to_string(path) {
-                Ok(content) => match toml::from_str::<Config>(&content) {
-                    Ok(mut decoded) => {
-                        let theme = &decoded.theme;
-                        let theme_path = config_dir_path().join("themes");
-                        if !theme.is_empty() {
-                            let path = theme_path.join(theme).with_extension("toml");
-                            match Config::load_theme(&path) {
-                                Ok(loaded_theme) => {
-                                    decoded.colors = loaded_theme.colors;
-                                }
-                                Err(err_message) => {
-                                    return Err(ConfigError::ErrLoadingTheme(
-                                        err_message,
-                                    ));
-                                }
-                            }
-                        }
-
-                        if let Some(adaptive_theme) = &decoded.adaptive_theme {
-                            let mut adaptive_colors = AdaptiveColors {
-                                dark: None,
-                                light: None,
-                            };
-
-                            let light_theme = &adaptive_theme.light;
-                            let path =
-                                theme_path.join(light_theme).with_extension("toml");
-                            match Config::load_theme(&path) {
-                                Ok(light_loaded_theme) => {
-                                    adaptive_colors.light =
-                                        Some(light_loaded_theme.colors)
-                                }
-                                Err(err_message) => {
-                                    warn!("failed to load light theme: {}", light_theme);
-                                    return Err(ConfigError::ErrLoadingTheme(
-                                        err_message,
-                                    ));
-                                }
-                            }
-
-                            let dark_theme = &adaptive_theme.dark;
-                            let path = theme_path.join(dark_theme).with_extension("toml");
-                            match Config::load_theme(&path) {
-                                Ok(dark_loaded_theme) => {
-                                    adaptive_colors.dark = Some(dark_loaded_theme.colors)
-                                }
-                                Err(err_message) => {
-                                    warn!("failed to load dark theme: {}", dark_theme);
-                                    return Err(ConfigError::ErrLoadingTheme(
-                                        err_message,
-                                    ));
-                                }
-                            }
-
-                            if adaptive_colors.light.is_some()
-                                && adaptive_colors.dark.is_some()
-                            {
-                                decoded.adaptive_colors = Some(adaptive_colors);
-                            }
-                        }
+    /// Defaults with the overrides applied, used when there is no config
+    /// file or it can't be loaded.
+    pub fn default_with_overrides(overrides: &ConfigOverrides) -> Self {
+        if overrides.is_empty() {
+            return Config::default();
+        }
 
-                        Ok(decoded)
-                    }
-                    Err(err_message) => {
-                        Err(ConfigError::ErrLoadingConfig(err_message.to_string()))
-                    }
-                },
-                Err(err_message) => {
-                    Err(ConfigError::ErrLoadingConfig(err_message.to_string()))
+        match Config::decode("", &config_file_path(), overrides) {
+            Ok(mut config) => {
+                let theme = config.theme.clone();
+                if !theme.is_empty() && config.set_theme(&theme).is_err() {
+                    warn!("failed to load theme: {}", theme);
                 }
+                config
             }
-        } else {
-            Err(ConfigError::PathNotFound)
+            Err(ConfigError::Invalid(diagnostics, _)) => {
+                for diagnostic in diagnostics {
+                    warn!("{diagnostic}");
+                }
+                Config::default()
+            }
+            Err(_) => Config::default(),
         }
     }
 
-    pub fn overwrite_based_on_platform(&mut self) {
-        #[cfg(windows)]
-        if let Some(windows) = &self.platform.windows {
-            self.overwrite_with_platform_config(windows.clone());
-        }
-
-        #[cfg(target_os = "linux")]
-        if let Some(linux) = &self.platform.linux {
-            self.overwrite_with_platform_config(linux.clone());
-        }
-
-        #[cfg(target_os = "macos")]
-        if let Some(macos) = &self.platform.macos {
-            self.overwrite_with_platform_config(macos.clone());
-        }
+    /// Loads the config file with the overrides merged over it.
+    pub fn try_load(overrides: &ConfigOverrides) -> Result<Self, ConfigError> {
+        ConfigFile::read()?.load(overrides)
     }
 
-    fn overwrite_with_platform_config(&mut self, platform_config: PlatformConfig) {
-        if let Some(shell_overwrite) = &platform_config.shell {
-            self.shell = shell_overwrite.clone();
+    /// Replaces the colors with the ones of the theme and loads the
+    /// colors of the adaptive theme.
+    fn load_themes(&mut self) -> Result<(), ConfigError> {
+        let theme_path = themes_dir_path();
+        if !self.theme.is_empty() {
+            let path = theme_formats::find(&theme_path, &self.theme);
+            let loaded_theme =
+                Config::load_theme(&path).map_err(ConfigError::ErrLoadingTheme)?;
+            self.colors = loaded_theme.colors;
         }
 
-        if let Some(window_overwrite) = &platform_config.window {
-            self.window = window_overwrite.clone();
+        if let Some(adaptive_theme) = &self.adaptive_theme {
+            let light_theme = &adaptive_theme.light;
+            let path = theme_formats::find(&theme_path, light_theme);
+            let light = Config::load_theme(&path).map_err(|err_message| {
+                warn!("failed to load light theme: {}", light_theme);
+                ConfigError::ErrLoadingTheme(err_message)
+            })?;
+
+            let dark_theme = &adaptive_theme.dark;
+            let path = theme_formats::find(&theme_path, dark_theme);
+            let dark = Config::load_theme(&path).map_err(|err_message| {
+                warn!("failed to load dark theme: {}", dark_theme);
+                ConfigError::ErrLoadingTheme(err_message)
+            })?;
+
+            self.adaptive_colors = Some(AdaptiveColors {
+                dark: Some(dark.colors),
+                light: Some(light.colors),
+            });
         }
 
-        if let Some(navigation_overwrite) = &platform_config.navigation {
-            self.navigation = navigation_overwrite.clone();
-        }
-
-        if let Some(renderer_overwrite) = &platform_config.renderer {
-            self.renderer = renderer_overwrite.clone();
-        }
+        Ok(())
     }
 }
 
@@ -488,6 +624,7 @@ impl Default for Config {
             title: Title::default(),
             developer: Developer::default(),
             env_vars: vec![],
+            #[cfg(feature = "ui")]
             fonts: SugarloafFonts::default(),
             line_height: default_line_height(),
             navigation: Navigation::default(),
@@ -504,6 +641,17 @@ impl Default for Config {
             ignore_selection_fg_color: false,
             confirm_before_quit: true,
             hide_cursor_when_typing: false,
+            layouts: HashMap::default(),
+            record: Record::default(),
+            export: Export::default(),
+            triggers: Vec::default(),
+            highlight: Highlight::default(),
+            timestamps: Timestamps::default(),
+            monitor: Monitor::default(),
+            bell: Bell::default(),
+            scrollback_editor: ScrollbackEditor::default(),
+            imported: Vec::new(),
+            diagnostics: Vec::new(),
         }
     }
 }
@@ -518,7 +666,7 @@ impl Default for CursorConfig {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "ui"))]
 mod tests {
     use super::*;
     use colors::{hex_to_color_arr, hex_to_color_wgpu};
@@ -589,6 +737,7 @@ mod tests {
     #[test]
     fn test_if_explicit_defaults_match() {
         let result = create_temporary_config("defaults", &default_config_file_content());
+        assert!(result.diagnostics.is_empty());
 
         assert_eq!(
             result.renderer.performance,
@@ -871,6 +1020,28 @@ mod tests {
         assert_eq!(result.colors.background.0, hex_to_color_arr("#2B3E50"));
     }
 
+    #[test]
+    fn test_change_theme_from_other_terminal() {
+        let path = tmp_dir().join("test-rio-kitty-theme.conf");
+        std::fs::write(
+            path,
+            "foreground #F8F8F2\nbackground #2B3E50\ncolor1 #ff5555\n",
+        )
+        .unwrap();
+
+        let result = create_temporary_config(
+            "change-theme-kitty",
+            r#"
+            theme = "test-rio-kitty-theme"
+        "#,
+        );
+
+        assert_eq!(result.colors.foreground, hex_to_color_arr("#F8F8F2"));
+        assert_eq!(result.colors.background.0, hex_to_color_arr("#2B3E50"));
+        assert_eq!(result.colors.red, hex_to_color_arr("#ff5555"));
+        assert_eq!(result.colors.cursor, colors::defaults::cursor());
+    }
+
     #[test]
     fn test_change_one_color() {
         let result = create_temporary_config(
@@ -1056,4 +1227,295 @@ mod tests {
         assert_eq!(result.colors.tabs_active, colors::defaults::tabs_active());
         assert_eq!(result.colors.cursor, colors::defaults::cursor());
     }
+
+    #[test]
+    fn test_highlight_rules() {
+        let result = create_temporary_config(
+            "highlight-rules",
+            r##"
+            [highlight]
+            rules = [
+              { regex = "ERROR", foreground = "#ff0000", bold = true },
+              { regex = "req-[0-9a-f]+", underline = true },
+            ]
+        "##,
+        );
+
+        assert!(result.highlight.enabled);
+        assert_eq!(result.highlight.rules.len(), 2);
+        let rule = &result.highlight.rules[0];
+        assert_eq!(rule.regex, "ERROR");
+        assert_eq!(rule.foreground, Some(hex_to_color_arr("#ff0000")));
+        assert_eq!(rule.background, None);
+        assert!(rule.bold && !rule.underline);
+        assert!(result.highlight.rules[1].underline);
+    }
+
+    #[test]
+    fn test_monitor() {
+        let result = create_temporary_config(
+            "monitor",
+            r#"
+            [monitor]
+            silence = true
+            silence-seconds = 30
+            actions = ["mark", "notify"]
+        "#,
+        );
+
+        assert!(!result.monitor.activity);
+        assert!(result.monitor.silence);
+        assert_eq!(result.monitor.silence_seconds, 30);
+        assert_eq!(
+            result.monitor.actions,
+            vec![monitor::MonitorAction::Mark, monitor::MonitorAction::Notify]
+        );
+    }
+
+    #[test]
+    fn test_import() {
+        let team = tmp_dir().join("test-rio-import-team.toml");
+        std::fs::write(
+            &team,
+            r#"
+            padding-x = 12
+            [cursor]
+            blinking = true
+            [bindings]
+            keys = [{ key = "q", with = "super", action = "Quit" }]
+        "#,
+        )
+        .unwrap();
+
+        let result = create_temporary_config(
+            "import",
+            r#"
+            import = ["test-rio-import-team.toml"]
+            padding-x = 4
+            [bindings]
+            keys = [{ key = "n", with = "super", action = "CreateWindow" }]
+        "#,
+        );
+
+        assert_eq!(result.imported, vec![team]);
+        assert_eq!(result.padding_x, 4.);
+        assert!(result.cursor.blinking);
+        assert_eq!(result.bindings.keys.len(), 2);
+        assert_eq!(result.bindings.keys[0].action, "Quit");
+
+        let path = tmp_dir().join("test-rio-import-missing.toml");
+        let content = "import = [\"test-rio-import-missing-team.toml\"]";
+        let error =
+            Config::decode(content, &path, &ConfigOverrides::default()).unwrap_err();
+        assert_eq!(
+            error.imported(),
+            [tmp_dir().join("test-rio-import-missing-team.toml")]
+        );
+    }
+
+    #[test]
+    fn test_scrollback_editor() {
+        let result = create_temporary_config(
+            "scrollback-editor",
+            r#"
+            [scrollback-editor]
+            command = { program = "less", args = ["-R"] }
+            ansi = true
+            open-in = "split"
+        "#,
+        );
+
+        assert!(result.scrollback_editor.ansi);
+        assert_eq!(
+            result.scrollback_editor.open_in,
+            scrollback_editor::ScrollbackEditorTarget::Split
+        );
+        let shell = result
+            .scrollback_editor
+            .shell(&result.editor, "/tmp/scrollback.txt");
+        assert_eq!(shell.program, "less");
+        assert_eq!(shell.args, vec!["-R", "/tmp/scrollback.txt"]);
+
+        let shell = ScrollbackEditor::default().shell(&result.editor, "file");
+        assert_eq!(shell.program, result.editor.program);
+        assert_eq!(shell.args.last().unwrap(), "file");
+    }
+
+    #[test]
+    fn test_bell() {
+        let result = create_temporary_config(
+            "bell",
+            r##"
+            [bell]
+            visual = true
+            visual-duration = 300
+            visual-color = "#ff000080"
+            urgent = false
+            command = { program = "paplay", args = ["bell.oga"] }
+        "##,
+        );
+
+        assert!(result.bell.visual);
+        assert_eq!(result.bell.visual_duration, 300);
+        assert_eq!(result.bell.visual_color, hex_to_color_arr("#ff000080"));
+        assert!(!result.bell.urgent);
+        assert!(result.bell.indicator);
+        assert_eq!(result.bell.unfocused_interval, 1000);
+        let command = result.bell.command.unwrap();
+        assert_eq!(command.program, "paplay");
+        assert_eq!(command.args, vec!["bell.oga"]);
+    }
+
+    #[test]
+    fn test_printed_config_loads() {
+        let printed = Config::default().to_string().unwrap();
+        let result = create_temporary_config("printed", &printed);
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+
+        let default = Config::default();
+        assert_eq!(result.fonts, default.fonts);
+        assert_eq!(result.window, default.window);
+        assert_eq!(result.navigation, default.navigation);
+        assert_eq!(result.renderer, default.renderer);
+        assert_eq!(result.bell, default.bell);
+        // The wgpu copy of the default background isn't rounded like a
+        // parsed one, so the colors are compared as they are printed
+        assert_eq!(
+            toml::to_string(&result.colors).unwrap(),
+            toml::to_string(&default.colors).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_printed_config_has_theme_colors() {
+        create_temporary_theme(
+            "test-printed-theme",
+            r##"
+            [colors]
+            background = '#1e1e2e'
+            red = '#f38ba8'
+        "##,
+        );
+        let result = create_temporary_config(
+            "printed-theme",
+            r#"
+            theme = "test-printed-theme"
+        "#,
+        );
+
+        let printed = result.to_string().unwrap();
+        let table: toml::Table = toml::from_str(&printed).unwrap();
+        let colors = table["colors"].as_table().unwrap();
+        assert_eq!(colors["background"].as_str(), Some("#1e1e2e"));
+        assert_eq!(colors["red"].as_str(), Some("#f38ba8"));
+        assert_eq!(
+            colors["blue"].as_str().map(String::from),
+            Some(colors::to_hex(&Colors::default().blue))
+        );
+    }
+
+    #[test]
+    fn test_overrides() {
+        let path = tmp_dir().join("test-rio-overrides.toml");
+        let content = r#"
+            padding-x = 4
+            [fonts]
+            size = 14
+            [window]
+            mode = "maximized"
+        "#;
+        let overrides = ConfigOverrides::parse(&[
+            "fonts.size=18",
+            "window.mode=fullscreen",
+            "window.opacityy=0.5",
+        ])
+        .unwrap();
+
+        let result = Config::decode(content, &path, &overrides).unwrap();
+        assert_eq!(result.padding_x, 4.);
+        assert_eq!(result.fonts.size, 18.);
+        assert_eq!(result.window.mode, window::WindowMode::Fullscreen);
+
+        assert_eq!(result.diagnostics.len(), 1);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.file, PathBuf::from(overrides::OVERRIDES_SOURCE));
+        assert_eq!(diagnostic.key, "window.opacityy");
+        assert!(diagnostic.line > 0);
+
+        let result = Config::default_with_overrides(&overrides);
+        assert_eq!(result.fonts.size, 18.);
+        assert_eq!(result.padding_x, Config::default().padding_x);
+    }
+
+    #[test]
+    fn test_config_file_decoded_per_window() {
+        let path = tmp_dir().join("test-rio-config-file.toml");
+        let content = r#"
+            padding-x = 4
+            [fonts]
+            size = 14
+        "#;
+        let config_file = ConfigFile::parse(path, content.to_owned()).unwrap();
+
+        let global = ConfigOverrides::parse(&["padding-x=8"]).unwrap();
+        let window = ConfigOverrides::parse(&["fonts.size=20"]).unwrap();
+
+        let result = config_file.decode(&global).unwrap();
+        assert_eq!(result.padding_x, 8.);
+        assert_eq!(result.fonts.size, 14.);
+
+        let result = config_file.decode(&global.merged(&window)).unwrap();
+        assert_eq!(result.padding_x, 8.);
+        assert_eq!(result.fonts.size, 20.);
+    }
+
+    #[test]
+    fn test_platform() {
+        let result = create_temporary_config(
+            "platform",
+            r#"
+            padding-x = 10
+            env-vars = ["A=1"]
+            [fonts]
+            size = 14
+            [shell]
+            program = "/bin/fish"
+            args = ["--login"]
+
+            [platform]
+            linux.padding-x = 4
+            linux.fonts.size = 18
+            linux.env-vars = ["B=2"]
+            linux.shell.program = "zsh"
+            macos.padding-x = 4
+            macos.fonts.size = 18
+            macos.env-vars = ["B=2"]
+            macos.shell.program = "zsh"
+            windows.padding-x = 4
+            windows.fonts.size = 18
+            windows.env-vars = ["B=2"]
+            windows.shell.program = "zsh"
+        "#,
+        );
+
+        assert_eq!(result.padding_x, 4.);
+        assert_eq!(result.fonts.size, 18.);
+        assert_eq!(result.env_vars, vec![String::from("B=2")]);
+        assert_eq!(result.shell.program, "zsh");
+        assert!(result.shell.args.is_empty());
+        assert!(result.platform.linux.is_some());
+        assert!(result.diagnostics.is_empty());
+
+        let os = platform::current().unwrap();
+        let content = format!("[platform]\n{os}.window.opacity = \"clear\"\n");
+        let path = tmp_dir().join("test-rio-platform-invalid.toml");
+        match Config::decode(&content, &path, &ConfigOverrides::default()) {
+            Err(ConfigError::Invalid(diagnostics, _)) => {
+                assert_eq!(diagnostics.len(), 1);
+                assert_eq!(diagnostics[0].key, format!("platform.{os}.window.opacity"));
+                assert_eq!(diagnostics[0].line, 2);
+            }
+            _ => panic!("invalid value of the platform table was accepted"),
+        }
+    }
 }
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// Monitors watching the output of background tabs.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Monitor {
+    /// Alert when a background tab writes new output.
+    #[serde(default)]
+    pub activity: bool,
+    /// Alert when a background tab stops writing output for
+    /// `silence-seconds`.
+    #[serde(default)]
+    pub silence: bool,
+    #[serde(default = "default_silence_seconds", rename = "silence-seconds")]
+    pub silence_seconds: u64,
+    /// What happens when a monitor fires.
+    #[serde(default = "default_monitor_actions")]
+    pub actions: Vec<MonitorAction>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum MonitorAction {
+    /// Marks the tab in the tab bar (or bookmark) until it's selected.
+    Mark,
+    Bell,
+    /// Desktop notification.
+    Notify,
+}
+
+#[inline]
+fn default_silence_seconds() -> u64 {
+    10
+}
+
+#[inline]
+fn default_monitor_actions() -> Vec<MonitorAction> {
+    vec![MonitorAction::Mark]
+}
+
+impl Default for Monitor {
+    fn default() -> Monitor {
+        Monitor {
+            activity: false,
+            silence: false,
+            silence_seconds: default_silence_seconds(),
+            actions: default_monitor_actions(),
+        }
+    }
+}
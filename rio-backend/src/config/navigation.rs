@@ -3,6 +3,7 @@ use crate::config::default_bool_true;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum NavigationMode {
     #[serde(alias = "plain")]
     Plain,
@@ -91,6 +92,7 @@ impl std::str::FromStr for NavigationMode {
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ColorAutomation {
     #[serde(default = "String::new")]
     pub program: String,
@@ -104,6 +106,7 @@ pub struct ColorAutomation {
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Navigation {
     #[serde(default = "NavigationMode::default")]
     pub mode: NavigationMode,
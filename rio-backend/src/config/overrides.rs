@@ -0,0 +1,155 @@
+// Config overrides passed in the command line with `-o key=value` (or
+// through IPC when opening a window). Values are parsed as TOML and merged
+// over the config file, like one more imported file that wins over all
+// the others.
+
+use crate::config::import;
+use std::path::PathBuf;
+use toml::{Table, Value};
+
+/// Name shown as the file of diagnostics pointing to an override.
+pub const OVERRIDES_SOURCE: &str = "--option";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigOverrides {
+    table: Table,
+}
+
+impl ConfigOverrides {
+    /// Parses options like `fonts.size=18` or `window.mode=fullscreen`,
+    /// values that aren't valid TOML are taken as strings.
+    pub fn parse<S: AsRef<str>>(options: &[S]) -> Result<ConfigOverrides, String> {
+        let mut overrides = ConfigOverrides::default();
+        for option in options {
+            let option = option.as_ref();
+            let (key, value) = match option.split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => {
+                    (key.trim(), value.trim())
+                }
+                _ => {
+                    return Err(format!("invalid option {option:?}, expected key=value"))
+                }
+            };
+
+            let line = format!("{key} = {value}");
+            let table = match line.parse::<Table>() {
+                Ok(table) => table,
+                Err(_) => {
+                    let line = format!("{key} = {}", Value::String(value.to_string()));
+                    line.parse::<Table>().map_err(|err| {
+                        format!("invalid option {option:?}: {}", err.message())
+                    })?
+                }
+            };
+            import::merge(&mut overrides.table, table, "");
+        }
+        Ok(overrides)
+    }
+
+    /// Sets the value at the dotted `key`, like `title.placeholder`.
+    pub fn insert(&mut self, key: &str, value: Value) {
+        let mut table = Table::new();
+        let mut segments = key.rsplit('.');
+        let last = segments.next().unwrap_or(key);
+        table.insert(last.to_string(), value);
+        for segment in segments {
+            let mut parent = Table::new();
+            parent.insert(segment.to_string(), Value::Table(table));
+            table = parent;
+        }
+        import::merge(&mut self.table, table, "");
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// Overrides of `self` followed by the ones of `other`, which win.
+    pub fn merged(&self, other: &ConfigOverrides) -> ConfigOverrides {
+        let mut merged = self.clone();
+        import::merge(&mut merged.table, other.table.clone(), "");
+        merged
+    }
+
+    /// Merges the overrides over the config `table`.
+    pub fn apply(&self, table: &mut Table) {
+        import::merge(table, self.table.clone(), "");
+    }
+
+    /// Source used by the diagnostics to point to the overrides, written
+    /// with one value per line.
+    pub fn source(&self) -> (PathBuf, String) {
+        let mut source = String::new();
+        for (key, value) in leaves(&self.table, "") {
+            source.push_str(&format!("{key} = {value}\n"));
+        }
+        (PathBuf::from(OVERRIDES_SOURCE), source)
+    }
+}
+
+/// Dotted keys of the values of `table` that aren't tables.
+fn leaves(table: &Table, prefix: &str) -> Vec<(String, Value)> {
+    let mut leaves = Vec::new();
+    for (key, value) in table {
+        let key = toml_edit::Key::new(key.as_str()).display_repr().to_string();
+        let path = if prefix.is_empty() {
+            key
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match value {
+            Value::Table(table) if !table.is_empty() => {
+                leaves.extend(self::leaves(table, &path));
+            }
+            value => leaves.push((path, value.clone())),
+        }
+    }
+    leaves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_overrides() {
+        let overrides = ConfigOverrides::parse(&[
+            "fonts.size=18",
+            "window.mode = fullscreen",
+            "shell = { program = \"/bin/fish\", args = [\"--login\"] }",
+            "fonts.size=20",
+        ])
+        .unwrap();
+
+        let mut table = Table::new();
+        overrides.apply(&mut table);
+        assert_eq!(table["fonts"]["size"].as_integer(), Some(20));
+        assert_eq!(table["window"]["mode"].as_str(), Some("fullscreen"));
+        assert_eq!(table["shell"]["program"].as_str(), Some("/bin/fish"));
+        let (_, source) = overrides.source();
+        assert_eq!(source.lines().count(), 4);
+        assert!(source.contains("shell.program = \"/bin/fish\"\n"));
+        assert!(source.parse::<Table>().is_ok());
+
+        assert!(ConfigOverrides::parse(&["fonts.size"]).is_err());
+        assert!(ConfigOverrides::parse(&["=18"]).is_err());
+        assert!(ConfigOverrides::parse(&["fonts size=18"]).is_err());
+    }
+
+    #[test]
+    fn test_insert_and_merge_overrides() {
+        let mut overrides = ConfigOverrides::parse(&["title.placeholder=rio"]).unwrap();
+        let mut window = ConfigOverrides::default();
+        window.insert("title.placeholder", Value::String(String::from("htop")));
+        window.insert("use-fork", Value::Boolean(false));
+        overrides = overrides.merged(&window);
+
+        let mut table = Table::new();
+        overrides.apply(&mut table);
+        assert_eq!(table["title"]["placeholder"].as_str(), Some("htop"));
+        assert_eq!(table["use-fork"].as_bool(), Some(false));
+        assert!(!overrides.is_empty());
+        assert!(ConfigOverrides::default().is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+// Per OS configuration under `[platform]`. The table of the current OS is
+// deep merged over the config, so any key can be overwritten, like
+// `platform.macos.fonts.size` or `platform.linux.bindings.keys`.
+
+use crate::config::import;
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+
+/// Commands are replaced as a whole, so the arguments of the shell of
+/// another OS aren't kept.
+const REPLACED_KEYS: &[&str] = &["shell", "editor"];
+
+#[derive(Default, Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Platform {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<crate::config::Config>"))]
+    pub linux: Option<Table>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<crate::config::Config>"))]
+    pub windows: Option<Table>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<crate::config::Config>"))]
+    pub macos: Option<Table>,
+}
+
+/// Key of the current OS under `[platform]`.
+pub fn current() -> Option<&'static str> {
+    if cfg!(windows) {
+        Some("windows")
+    } else if cfg!(target_os = "linux") {
+        Some("linux")
+    } else if cfg!(target_os = "macos") {
+        Some("macos")
+    } else {
+        None
+    }
+}
+
+/// Merges the table of the current OS over the config `table`.
+pub fn apply(table: &mut Table) {
+    apply_for(table, current());
+}
+
+fn apply_for(table: &mut Table, platform: Option<&str>) {
+    let overwrite = platform.and_then(|platform| {
+        table
+            .get("platform")
+            .and_then(|platforms| platforms.get(platform))
+            .and_then(Value::as_table)
+            .cloned()
+    });
+
+    if let Some(overwrite) = overwrite {
+        for key in REPLACED_KEYS {
+            if overwrite.contains_key(*key) {
+                table.remove(*key);
+            }
+        }
+        import::merge(table, overwrite, "");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_platform_deep_merge() {
+        let mut table = r#"
+            padding-x = 10
+            env-vars = ["A=1"]
+            [fonts]
+            size = 14
+            family = "cascadiacode"
+            [shell]
+            program = "/bin/fish"
+            args = ["--login"]
+            [bindings]
+            keys = [{ key = "q", with = "super", action = "Quit" }]
+
+            [platform]
+            linux.padding-x = 4
+            linux.env-vars = ["B=2"]
+            linux.fonts.size = 18
+            linux.shell.program = "tmux"
+            linux.bindings.keys = [{ key = "n", with = "super", action = "CreateWindow" }]
+            macos.fonts.size = 20
+        "#
+        .parse::<Table>()
+        .unwrap();
+
+        apply_for(&mut table, Some("linux"));
+        assert_eq!(table["padding-x"].as_integer(), Some(4));
+        assert_eq!(table["env-vars"].as_array().unwrap().len(), 1);
+        assert_eq!(table["env-vars"][0].as_str(), Some("B=2"));
+        assert_eq!(table["fonts"]["size"].as_integer(), Some(18));
+        assert_eq!(table["fonts"]["family"].as_str(), Some("cascadiacode"));
+        assert_eq!(table["shell"]["program"].as_str(), Some("tmux"));
+        assert!(table["shell"].get("args").is_none());
+        assert_eq!(table["bindings"]["keys"].as_array().unwrap().len(), 2);
+
+        let mut unchanged = table.clone();
+        apply_for(&mut unchanged, Some("windows"));
+        assert_eq!(unchanged, table);
+        apply_for(&mut unchanged, None);
+        assert_eq!(unchanged, table);
+    }
+}
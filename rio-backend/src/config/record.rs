@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Session recording, written in asciicast v2 format.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Record {
+    /// Directory of the recordings started by `ToggleRecording`.
+    #[serde(default)]
+    pub directory: Option<String>,
+    /// Also record the input written to the terminal.
+    #[serde(default)]
+    pub input: bool,
+}
+
+impl Record {
+    /// Configured directory, falls back to the home directory.
+    pub fn directory(&self) -> PathBuf {
+        self.directory
+            .as_ref()
+            .map(PathBuf::from)
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(std::env::temp_dir)
+    }
+}
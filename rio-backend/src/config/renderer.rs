@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Renderer {
     #[serde(default = "Performance::default")]
     pub performance: Performance,
@@ -10,14 +11,17 @@ pub struct Renderer {
     #[serde(default = "bool::default", rename = "disable-unfocused-render")]
     pub disable_unfocused_render: bool,
     #[serde(default = "Option::default", rename = "target-fps")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub target_fps: Option<u64>,
     #[serde(default = "Vec::default")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub filters: Vec<String>,
     #[serde(default = "RendererStategy::default")]
     pub strategy: RendererStategy,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum RendererStategy {
     #[default]
     #[serde(alias = "events")]
@@ -53,6 +57,7 @@ impl Default for Renderer {
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Performance {
     #[default]
     #[serde(alias = "high")]
@@ -75,6 +80,7 @@ impl Display for Performance {
 }
 
 #[derive(Debug, Default, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Backend {
     // Leave Sugarloaf/WGPU to decide
     #[default]
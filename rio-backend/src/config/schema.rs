@@ -0,0 +1,129 @@
+// JSON Schema of `config.toml`, generated from the config structs so
+// editors (taplo, VS Code) can validate and autocomplete the config file.
+
+use crate::config::colors::{to_hex, ColorArray};
+use crate::config::Config;
+use schemars::gen::SchemaSettings;
+use schemars::schema::{InstanceType, RootSchema, Schema, SchemaObject, SingleOrVec};
+use schemars::visit::{visit_schema_object, Visitor};
+use serde_json::Value;
+
+/// Generates the JSON Schema of the config file.
+pub fn generate() -> RootSchema {
+    let mut schema = SchemaSettings::draft07()
+        .with_visitor(LowercaseVariants)
+        .with_visitor(HexColors)
+        .into_generator()
+        .into_root_schema_for::<Config>();
+    let metadata = schema.schema.metadata();
+    metadata.title = Some(String::from("Rio"));
+    metadata.description = Some(String::from("Rio terminal configuration file"));
+    schema
+}
+
+/// Unit variants also accept their lowercase name (`#[serde(alias)]`),
+/// which schemars doesn't know about.
+#[derive(Debug, Clone)]
+struct LowercaseVariants;
+
+impl Visitor for LowercaseVariants {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        if let Some(values) = &mut schema.enum_values {
+            let lowercase: Vec<Value> = values
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|value| Value::from(value.to_lowercase()))
+                .filter(|value| !values.contains(value))
+                .collect();
+            values.extend(lowercase);
+        }
+
+        visit_schema_object(self, schema);
+    }
+}
+
+/// Colors are `[f32; 4]` in the config structs but they are written as
+/// hex strings, like `#ffffff`.
+#[derive(Debug, Clone)]
+struct HexColors;
+
+impl Visitor for HexColors {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        if is_color(schema) {
+            schema.array = None;
+            schema.instance_type = Some(match schema.instance_type.take() {
+                // Optional colors also accept null
+                Some(SingleOrVec::Vec(types)) => types
+                    .into_iter()
+                    .map(|kind| match kind {
+                        InstanceType::Array => InstanceType::String,
+                        kind => kind,
+                    })
+                    .collect::<Vec<_>>()
+                    .into(),
+                _ => InstanceType::String.into(),
+            });
+
+            let default = schema.metadata.as_mut().and_then(|m| m.default.as_mut());
+            if let Some(default) = default {
+                if let Ok(color) = serde_json::from_value::<ColorArray>(default.clone()) {
+                    *default = Value::from(to_hex(&color));
+                }
+            }
+        }
+
+        visit_schema_object(self, schema);
+    }
+}
+
+fn is_color(schema: &SchemaObject) -> bool {
+    let Some(array) = &schema.array else {
+        return false;
+    };
+    let is_number = matches!(
+        &array.items,
+        Some(SingleOrVec::Single(item)) if matches!(
+            item.as_ref(),
+            Schema::Object(SchemaObject {
+                instance_type: Some(SingleOrVec::Single(kind)),
+                ..
+            }) if **kind == InstanceType::Number
+        )
+    );
+    is_number && array.min_items == Some(4) && array.max_items == Some(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_describes_config() {
+        let schema = serde_json::to_value(generate()).unwrap();
+        assert_eq!(schema["title"], "Rio");
+
+        let properties = &schema["properties"];
+        assert!(properties.get("fonts").is_some());
+        assert!(properties.get("padding-x").is_some());
+        assert!(properties.get("scrollback-editor").is_some());
+        assert!(properties.get("imported").is_none());
+        assert!(properties.get("adaptive-colors").is_none());
+
+        // Colors are written as hex strings
+        let colors = &schema["definitions"]["Colors"]["properties"];
+        assert_eq!(colors["background"]["type"], "string");
+        assert_eq!(colors["foreground"]["default"], "#ffffff");
+        let rule = &schema["definitions"]["HighlightRule"]["properties"];
+        assert_eq!(
+            rule["foreground"]["type"],
+            serde_json::json!(["string", "null"])
+        );
+
+        // Lowercase aliases are valid values
+        let mode = schema["definitions"]["WindowMode"]["enum"]
+            .as_array()
+            .unwrap();
+        assert!(mode.contains(&Value::from("Fullscreen")));
+        assert!(mode.contains(&Value::from("fullscreen")));
+    }
+}
@@ -0,0 +1,38 @@
+use crate::config::Shell;
+use serde::{Deserialize, Serialize};
+
+/// Scrollback opened in an editor or pager (`OpenScrollbackInEditor`).
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ScrollbackEditor {
+    /// Program that opens the scrollback file, `editor` when unset.
+    #[serde(default)]
+    pub command: Option<Shell>,
+    /// Keeps the colors and styles as ANSI escape sequences, meant for
+    /// pagers like `less -R`.
+    #[serde(default)]
+    pub ansi: bool,
+    /// Where the editor runs.
+    #[serde(default, rename = "open-in")]
+    pub open_in: ScrollbackEditorTarget,
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ScrollbackEditorTarget {
+    /// Takes the place of the current split until the editor exits.
+    #[default]
+    Overlay,
+    Split,
+    Tab,
+}
+
+impl ScrollbackEditor {
+    /// Command that opens `path`, falling back to `editor`.
+    pub fn shell(&self, editor: &Shell, path: &str) -> Shell {
+        let mut shell = self.command.clone().unwrap_or_else(|| editor.clone());
+        shell.args.push(path.to_string());
+        shell
+    }
+}
@@ -2,6 +2,7 @@ use crate::config::colors::Colors;
 use serde::{Deserialize, Serialize};
 
 #[derive(Default, Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AdaptiveColors {
     #[serde(default = "Option::default", skip_serializing)]
     pub dark: Option<Colors>,
@@ -10,12 +11,14 @@ pub struct AdaptiveColors {
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AdaptiveTheme {
     pub dark: String,
     pub light: String,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Theme {
     #[serde(default = "Colors::default")]
     pub colors: Colors,
@@ -0,0 +1,782 @@
+// Color schemes of other terminals, converted to Rio colors when a theme
+// is loaded: iTerm2 `.itermcolors`, Alacritty TOML and YAML, kitty `.conf`,
+// Windows Terminal JSON and base16 YAML.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// Extensions of the theme files, in the order they are looked up.
+pub const EXTENSIONS: &[&str] = &["toml", "itermcolors", "yml", "yaml", "conf", "json"];
+
+/// Colors of the 16 ANSI indexes.
+const ANSI: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "light-black",
+    "light-red",
+    "light-green",
+    "light-yellow",
+    "light-blue",
+    "light-magenta",
+    "light-cyan",
+    "light-white",
+];
+
+/// Dim variants of the first 8 ANSI colors.
+const DIM: [&str; 8] = [
+    "dim-black",
+    "dim-red",
+    "dim-green",
+    "dim-yellow",
+    "dim-blue",
+    "dim-magenta",
+    "dim-cyan",
+    "dim-white",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeFormat {
+    Rio,
+    ITerm2,
+    Alacritty,
+    Kitty,
+    WindowsTerminal,
+    Base16,
+}
+
+impl fmt::Display for ThemeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ThemeFormat::Rio => "Rio",
+            ThemeFormat::ITerm2 => "iTerm2",
+            ThemeFormat::Alacritty => "Alacritty",
+            ThemeFormat::Kitty => "kitty",
+            ThemeFormat::WindowsTerminal => "Windows Terminal",
+            ThemeFormat::Base16 => "base16",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Path of the theme `name` in `directory`, with the first extension
+/// that exists (`.toml` when none does). `name` may also be the path of
+/// a theme file.
+pub fn find(directory: &Path, name: &str) -> PathBuf {
+    let path = directory.join(name);
+    if path.is_file() {
+        return path;
+    }
+
+    EXTENSIONS
+        .iter()
+        .map(|extension| directory.join(name).with_extension(extension))
+        .find(|path| path.exists())
+        .unwrap_or_else(|| directory.join(name).with_extension("toml"))
+}
+
+/// Guesses the format of a theme from its extension, or from its content
+/// when the extension is ambiguous. `None` when the content doesn't look
+/// like any of the supported formats.
+pub fn detect(path: &Path, content: &str) -> Option<ThemeFormat> {
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+    let format = match extension.as_deref() {
+        Some("itermcolors") => ThemeFormat::ITerm2,
+        Some("conf") => ThemeFormat::Kitty,
+        Some("json") => ThemeFormat::WindowsTerminal,
+        Some("yml" | "yaml") => {
+            let is_base16 = content.lines().any(|line| {
+                line.trim_start().starts_with("base00") && line.contains(':')
+            });
+            if is_base16 {
+                ThemeFormat::Base16
+            } else {
+                ThemeFormat::Alacritty
+            }
+        }
+        Some("toml") => match content.parse::<Table>() {
+            Ok(table) if is_alacritty(&table) => ThemeFormat::Alacritty,
+            _ => ThemeFormat::Rio,
+        },
+        _ => {
+            let content = content.trim_start();
+            if content.starts_with("<?xml") || content.starts_with("<plist") {
+                ThemeFormat::ITerm2
+            } else if content.starts_with('{') || content.starts_with('[') {
+                ThemeFormat::WindowsTerminal
+            } else {
+                match content.parse::<Table>() {
+                    Ok(table) if is_alacritty(&table) => ThemeFormat::Alacritty,
+                    Ok(_) => ThemeFormat::Rio,
+                    Err(_) if is_kitty(content) => ThemeFormat::Kitty,
+                    Err(_) => return None,
+                }
+            }
+        }
+    };
+    Some(format)
+}
+
+/// Reads the theme at `path` as a Rio theme table, converting it when it
+/// comes from another terminal.
+pub fn load(path: &Path) -> Result<Table, String> {
+    let content = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    match detect(path, &content).ok_or_else(|| unknown_format(path))? {
+        ThemeFormat::Rio => content
+            .parse::<Table>()
+            .map_err(|err| format!("error parsing: {}", err.message())),
+        format => {
+            let colors = convert(format, &content)?;
+            let mut theme = Table::new();
+            theme.insert(String::from("colors"), Value::Table(colors));
+            Ok(theme)
+        }
+    }
+}
+
+/// Converts a color scheme in `format` to the `[colors]` of a Rio theme.
+pub fn convert(format: ThemeFormat, content: &str) -> Result<Table, String> {
+    let colors = match format {
+        ThemeFormat::Rio => {
+            let table = content
+                .parse::<Table>()
+                .map_err(|err| format!("error parsing: {}", err.message()))?;
+            return match table.get("colors") {
+                Some(Value::Table(colors)) => Ok(colors.clone()),
+                _ => Ok(Table::new()),
+            };
+        }
+        ThemeFormat::ITerm2 => from_iterm2(content)?,
+        ThemeFormat::Alacritty => {
+            let table = match content.parse::<Table>() {
+                Ok(table) => table,
+                Err(_) => parse_yaml(content)?,
+            };
+            from_alacritty(&table)
+        }
+        ThemeFormat::Kitty => from_kitty(content),
+        ThemeFormat::WindowsTerminal => from_windows_terminal(content)?,
+        ThemeFormat::Base16 => from_base16(&parse_yaml(content)?),
+    };
+
+    if colors.is_empty() {
+        return Err(format!("no colors found in the {format} theme"));
+    }
+
+    let mut table = Table::new();
+    for (key, color) in colors {
+        table.insert(key.to_string(), Value::String(color));
+    }
+    Ok(table)
+}
+
+/// Writes a color scheme of another terminal as a Rio theme.
+pub fn to_rio_theme(path: &Path) -> Result<String, String> {
+    let content = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    let format = detect(path, &content).ok_or_else(|| unknown_format(path))?;
+    if format == ThemeFormat::Rio {
+        return Err(format!("{} is already a Rio theme", path.display()));
+    }
+
+    let mut theme = Table::new();
+    theme.insert(
+        String::from("colors"),
+        Value::Table(convert(format, &content)?),
+    );
+    let theme = toml::to_string(&theme).map_err(|err| err.to_string())?;
+    Ok(format!(
+        "# Converted from {} ({format})\n\n{theme}",
+        path.display()
+    ))
+}
+
+fn unknown_format(path: &Path) -> String {
+    format!(
+        "{} is not a theme of Rio, iTerm2, Alacritty, kitty, Windows Terminal or base16",
+        path.display()
+    )
+}
+
+/// kitty themes set colors with `name value` lines, like `color1 #cc0000`.
+fn is_kitty(content: &str) -> bool {
+    kitty_entries(content).any(|(key, _)| kitty_color(key).is_some())
+}
+
+fn is_alacritty(table: &Table) -> bool {
+    table
+        .get("colors")
+        .and_then(Value::as_table)
+        .is_some_and(|colors| {
+            colors.contains_key("primary") || colors.contains_key("normal")
+        })
+}
+
+/// Normalizes `#rgb`, `#rrggbb`, `0xrrggbb` and `rrggbb` to `#rrggbb`.
+fn hex(color: &str) -> Option<String> {
+    let color = color.trim();
+    let digits = color
+        .strip_prefix('#')
+        .or_else(|| color.strip_prefix("0x"))
+        .or_else(|| color.strip_prefix("0X"))
+        .unwrap_or(color);
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    match digits.len() {
+        3 => Some(format!(
+            "#{}",
+            digits
+                .chars()
+                .flat_map(|c| [c, c])
+                .collect::<String>()
+                .to_lowercase()
+        )),
+        6 => Some(format!("#{}", digits.to_lowercase())),
+        _ => None,
+    }
+}
+
+fn push(
+    colors: &mut Vec<(&'static str, String)>,
+    key: &'static str,
+    color: Option<&str>,
+) {
+    if let Some(color) = color.and_then(hex) {
+        colors.retain(|(existing, _)| *existing != key);
+        colors.push((key, color));
+    }
+}
+
+fn from_iterm2(content: &str) -> Result<Vec<(&'static str, String)>, String> {
+    // Property lists come with the DOCTYPE of Apple
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..roxmltree::ParsingOptions::default()
+    };
+    let document = roxmltree::Document::parse_with_options(content, options)
+        .map_err(|err| format!("error parsing: {err}"))?;
+    let dict = document
+        .descendants()
+        .find(|node| node.has_tag_name("dict"))
+        .ok_or_else(|| String::from("error parsing: missing plist dict"))?;
+
+    let mut colors = Vec::new();
+    let mut key = None;
+    for node in dict.children().filter(|node| node.is_element()) {
+        if node.has_tag_name("key") {
+            key = node.text();
+            continue;
+        }
+
+        let (Some(name), true) = (key.take(), node.has_tag_name("dict")) else {
+            continue;
+        };
+        let target = match name {
+            "Background Color" => "background",
+            "Foreground Color" => "foreground",
+            "Cursor Color" => "cursor",
+            "Selection Color" => "selection-background",
+            "Selected Text Color" => "selection-foreground",
+            name => match name
+                .strip_prefix("Ansi ")
+                .and_then(|name| name.strip_suffix(" Color"))
+                .and_then(|index| index.parse::<usize>().ok())
+                .and_then(|index| ANSI.get(index))
+            {
+                Some(target) => target,
+                None => continue,
+            },
+        };
+
+        let mut rgb = [0u8; 3];
+        let mut component = None;
+        for child in node.children().filter(|node| node.is_element()) {
+            if child.has_tag_name("key") {
+                component = match child.text() {
+                    Some("Red Component") => Some(0),
+                    Some("Green Component") => Some(1),
+                    Some("Blue Component") => Some(2),
+                    _ => None,
+                };
+            } else if let Some(index) = component.take() {
+                let value = child
+                    .text()
+                    .and_then(|text| text.trim().parse::<f64>().ok());
+                if let Some(value) = value {
+                    rgb[index] = (value.clamp(0., 1.) * 255.).round() as u8;
+                }
+            }
+        }
+        let color = format!("{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2]);
+        push(&mut colors, target, Some(&color));
+    }
+
+    Ok(colors)
+}
+
+fn from_alacritty(table: &Table) -> Vec<(&'static str, String)> {
+    let mut colors = Vec::new();
+    let Some(scheme) = table.get("colors").and_then(Value::as_table) else {
+        return colors;
+    };
+    let color = |section: &str, key: &str| -> Option<String> {
+        scheme.get(section)?.get(key)?.as_str().map(str::to_string)
+    };
+
+    push(
+        &mut colors,
+        "background",
+        color("primary", "background").as_deref(),
+    );
+    push(
+        &mut colors,
+        "foreground",
+        color("primary", "foreground").as_deref(),
+    );
+    push(
+        &mut colors,
+        "dim-foreground",
+        color("primary", "dim_foreground").as_deref(),
+    );
+    push(
+        &mut colors,
+        "light-foreground",
+        color("primary", "bright_foreground").as_deref(),
+    );
+    push(&mut colors, "cursor", color("cursor", "cursor").as_deref());
+    push(
+        &mut colors,
+        "vi-cursor",
+        color("vi_mode_cursor", "cursor").as_deref(),
+    );
+    push(
+        &mut colors,
+        "selection-background",
+        color("selection", "background").as_deref(),
+    );
+    push(
+        &mut colors,
+        "selection-foreground",
+        color("selection", "text").as_deref(),
+    );
+
+    for (index, name) in ANSI[..8].iter().enumerate() {
+        push(&mut colors, name, color("normal", name).as_deref());
+        push(
+            &mut colors,
+            ANSI[index + 8],
+            color("bright", name).as_deref(),
+        );
+        push(&mut colors, DIM[index], color("dim", name).as_deref());
+    }
+
+    colors
+}
+
+fn from_kitty(content: &str) -> Vec<(&'static str, String)> {
+    let mut colors = Vec::new();
+    for (key, value) in kitty_entries(content) {
+        if let Some(target) = kitty_color(key) {
+            push(&mut colors, target, Some(value));
+        }
+    }
+    colors
+}
+
+/// `name value` lines of a kitty config, comments are skipped.
+fn kitty_entries(content: &str) -> impl Iterator<Item = (&str, &str)> {
+    content.lines().filter_map(|line| {
+        let line = line.trim();
+        if line.starts_with('#') {
+            return None;
+        }
+        line.split_once(char::is_whitespace)
+    })
+}
+
+/// Rio color of a kitty color option.
+fn kitty_color(key: &str) -> Option<&'static str> {
+    let target = match key {
+        "background" => "background",
+        "foreground" => "foreground",
+        "cursor" => "cursor",
+        "selection_background" => "selection-background",
+        "selection_foreground" => "selection-foreground",
+        "active_tab_background" => "tabs-active",
+        "active_tab_foreground" => "tabs-active-foreground",
+        "inactive_tab_background" => "tabs",
+        "inactive_tab_foreground" => "tabs-foreground",
+        "tab_bar_background" => "bar",
+        key => key
+            .strip_prefix("color")
+            .and_then(|index| index.parse::<usize>().ok())
+            .and_then(|index| ANSI.get(index))?,
+    };
+    Some(target)
+}
+
+fn from_windows_terminal(content: &str) -> Result<Vec<(&'static str, String)>, String> {
+    let json: serde_json::Value =
+        serde_json::from_str(content).map_err(|err| format!("error parsing: {err}"))?;
+    // Either a scheme, a list of schemes or the `settings.json` with them
+    let scheme = match &json {
+        serde_json::Value::Array(schemes) => schemes.first(),
+        json => match json.get("schemes") {
+            Some(serde_json::Value::Array(schemes)) => schemes.first(),
+            _ => Some(json),
+        },
+    };
+    let Some(scheme) = scheme else {
+        return Ok(Vec::new());
+    };
+
+    let mut colors = Vec::new();
+    for (key, target) in [
+        ("background", "background"),
+        ("foreground", "foreground"),
+        ("cursorColor", "cursor"),
+        ("selectionBackground", "selection-background"),
+        ("black", "black"),
+        ("red", "red"),
+        ("green", "green"),
+        ("yellow", "yellow"),
+        ("blue", "blue"),
+        ("purple", "magenta"),
+        ("cyan", "cyan"),
+        ("white", "white"),
+        ("brightBlack", "light-black"),
+        ("brightRed", "light-red"),
+        ("brightGreen", "light-green"),
+        ("brightYellow", "light-yellow"),
+        ("brightBlue", "light-blue"),
+        ("brightPurple", "light-magenta"),
+        ("brightCyan", "light-cyan"),
+        ("brightWhite", "light-white"),
+    ] {
+        push(
+            &mut colors,
+            target,
+            scheme.get(key).and_then(|v| v.as_str()),
+        );
+    }
+    Ok(colors)
+}
+
+fn from_base16(table: &Table) -> Vec<(&'static str, String)> {
+    // Newer schemes keep the colors under `palette`
+    let palette = match table.get("palette") {
+        Some(Value::Table(palette)) => palette,
+        _ => table,
+    };
+    let base = |index: &str| -> Option<&str> {
+        palette
+            .get(&format!("base{index}"))
+            .or_else(|| palette.get(&format!("base{}", index.to_lowercase())))
+            .and_then(Value::as_str)
+    };
+
+    // Same mapping of the base16 shell and terminal templates
+    let mut colors = Vec::new();
+    for (target, index) in [
+        ("background", "00"),
+        ("foreground", "05"),
+        ("cursor", "05"),
+        ("selection-background", "02"),
+        ("selection-foreground", "05"),
+        ("tabs", "01"),
+        ("bar", "01"),
+        ("tabs-active", "02"),
+        ("black", "00"),
+        ("red", "08"),
+        ("green", "0B"),
+        ("yellow", "0A"),
+        ("blue", "0D"),
+        ("magenta", "0E"),
+        ("cyan", "0C"),
+        ("white", "05"),
+        ("light-black", "03"),
+        ("light-red", "08"),
+        ("light-green", "0B"),
+        ("light-yellow", "0A"),
+        ("light-blue", "0D"),
+        ("light-magenta", "0E"),
+        ("light-cyan", "0C"),
+        ("light-white", "07"),
+    ] {
+        push(&mut colors, target, base(index));
+    }
+    colors
+}
+
+/// Reads a YAML color scheme as a table, null values are left out since
+/// TOML has no equivalent.
+fn parse_yaml(content: &str) -> Result<Table, String> {
+    let yaml: serde_yaml_ng::Value = serde_yaml_ng::from_str(content)
+        .map_err(|err| format!("error parsing: {err}"))?;
+    match from_yaml(yaml) {
+        Some(Value::Table(table)) => Ok(table),
+        _ => Err(String::from("error parsing: expected a mapping of colors")),
+    }
+}
+
+fn from_yaml(yaml: serde_yaml_ng::Value) -> Option<Value> {
+    use serde_yaml_ng::Value as Yaml;
+
+    let value = match yaml {
+        Yaml::Null => return None,
+        Yaml::Bool(value) => Value::Boolean(value),
+        Yaml::Number(number) => match number.as_i64() {
+            Some(number) => Value::Integer(number),
+            None => Value::Float(number.as_f64()?),
+        },
+        Yaml::String(value) => Value::String(value),
+        Yaml::Sequence(values) => {
+            Value::Array(values.into_iter().filter_map(from_yaml).collect())
+        }
+        Yaml::Mapping(mapping) => Value::Table(
+            mapping
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    let key = match from_yaml(key)? {
+                        Value::String(key) => key,
+                        key => key.to_string(),
+                    };
+                    Some((key, from_yaml(value)?))
+                })
+                .collect(),
+        ),
+        Yaml::Tagged(tagged) => return from_yaml(tagged.value),
+    };
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colors(format: ThemeFormat, content: &str) -> Table {
+        convert(format, content).unwrap()
+    }
+
+    #[test]
+    fn test_detect_theme_format() {
+        assert_eq!(
+            detect(Path::new("a.toml"), "[colors]\nbackground = '#000000'"),
+            Some(ThemeFormat::Rio)
+        );
+        assert_eq!(
+            detect(
+                Path::new("a.toml"),
+                "[colors.primary]\nbackground = '#000000'"
+            ),
+            Some(ThemeFormat::Alacritty)
+        );
+        assert_eq!(
+            detect(Path::new("a.yml"), "colors:\n  primary:\n"),
+            Some(ThemeFormat::Alacritty)
+        );
+        assert_eq!(
+            detect(Path::new("a.yaml"), "scheme: x\nbase00: \"000000\""),
+            Some(ThemeFormat::Base16)
+        );
+        assert_eq!(
+            detect(Path::new("a.itermcolors"), ""),
+            Some(ThemeFormat::ITerm2)
+        );
+        assert_eq!(detect(Path::new("a.conf"), ""), Some(ThemeFormat::Kitty));
+        assert_eq!(
+            detect(Path::new("a.json"), "{}"),
+            Some(ThemeFormat::WindowsTerminal)
+        );
+        assert_eq!(
+            detect(Path::new("theme"), "<?xml version=\"1.0\"?>"),
+            Some(ThemeFormat::ITerm2)
+        );
+        assert_eq!(
+            detect(Path::new("theme"), "foreground #ffffff"),
+            Some(ThemeFormat::Kitty)
+        );
+        assert_eq!(detect(Path::new("theme"), "not a theme"), None);
+    }
+
+    #[test]
+    fn test_iterm2_theme() {
+        let colors = colors(
+            ThemeFormat::ITerm2,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+            <plist version="1.0">
+            <dict>
+                <key>Ansi 1 Color</key>
+                <dict>
+                    <key>Blue Component</key>
+                    <real>0.0</real>
+                    <key>Green Component</key>
+                    <real>0.0</real>
+                    <key>Red Component</key>
+                    <real>1</real>
+                </dict>
+                <key>Background Color</key>
+                <dict>
+                    <key>Alpha Component</key>
+                    <real>1</real>
+                    <key>Blue Component</key>
+                    <real>0.2</real>
+                    <key>Green Component</key>
+                    <real>0.2</real>
+                    <key>Red Component</key>
+                    <real>0.2</real>
+                </dict>
+                <key>Ansi 12 Color</key>
+                <dict>
+                    <key>Blue Component</key>
+                    <real>1</real>
+                    <key>Green Component</key>
+                    <real>0.5</real>
+                    <key>Red Component</key>
+                    <real>0</real>
+                </dict>
+            </dict>
+            </plist>"#,
+        );
+        assert_eq!(colors["red"].as_str(), Some("#ff0000"));
+        assert_eq!(colors["background"].as_str(), Some("#333333"));
+        assert_eq!(colors["light-blue"].as_str(), Some("#0080ff"));
+    }
+
+    #[test]
+    fn test_alacritty_theme() {
+        let toml = colors(
+            ThemeFormat::Alacritty,
+            r#"
+            [colors.primary]
+            background = '#1d1f21'
+            foreground = '0xc5c8c6'
+            [colors.cursor]
+            text = 'CellBackground'
+            cursor = 'CellForeground'
+            [colors.normal]
+            red = '#cc6666'
+            [colors.bright]
+            red = '#d54e53'
+            [colors.dim]
+            red = '#864343'
+            "#,
+        );
+        assert_eq!(toml["background"].as_str(), Some("#1d1f21"));
+        assert_eq!(toml["foreground"].as_str(), Some("#c5c8c6"));
+        assert!(toml.get("cursor").is_none());
+        assert_eq!(toml["red"].as_str(), Some("#cc6666"));
+        assert_eq!(toml["light-red"].as_str(), Some("#d54e53"));
+        assert_eq!(toml["dim-red"].as_str(), Some("#864343"));
+
+        let yaml = colors(
+            ThemeFormat::Alacritty,
+            r#"
+# Colors (Tomorrow Night)
+dim_red: &dim_red '#864343'
+colors:
+  primary:
+    background: '#1d1f21'
+    foreground: "0xc5c8c6" # comment
+  normal:
+    red:   '#cc6666'
+  bright:
+    red: >-
+      #d54e53
+  dim: { red: *dim_red }
+  indexed_colors:
+    - { index: 16, color: '#ff0000' }
+"#,
+        );
+        assert_eq!(yaml, toml);
+    }
+
+    #[test]
+    fn test_kitty_theme() {
+        let colors = colors(
+            ThemeFormat::Kitty,
+            "# vim:ft=kitty\n\
+             foreground   #dddddd\n\
+             background #000\n\
+             color9 #ff5555\n\
+             active_tab_background #44475a\n\
+             url_color #8be9fd\n\
+             selection_foreground none\n",
+        );
+        assert_eq!(colors["foreground"].as_str(), Some("#dddddd"));
+        assert_eq!(colors["background"].as_str(), Some("#000000"));
+        assert_eq!(colors["light-red"].as_str(), Some("#ff5555"));
+        assert_eq!(colors["tabs-active"].as_str(), Some("#44475a"));
+        assert_eq!(colors.len(), 4);
+    }
+
+    #[test]
+    fn test_windows_terminal_theme() {
+        let scheme = r##"{
+            "name": "Campbell",
+            "background": "#0C0C0C",
+            "foreground": "#CCCCCC",
+            "purple": "#881798",
+            "brightPurple": "#B4009E",
+            "cursorColor": "#FFFFFF"
+        }"##;
+        let colors = colors(ThemeFormat::WindowsTerminal, scheme);
+        assert_eq!(colors["background"].as_str(), Some("#0c0c0c"));
+        assert_eq!(colors["magenta"].as_str(), Some("#881798"));
+        assert_eq!(colors["light-magenta"].as_str(), Some("#b4009e"));
+        assert_eq!(colors["cursor"].as_str(), Some("#ffffff"));
+
+        let settings = format!(r#"{{ "profiles": {{}}, "schemes": [{scheme}] }}"#);
+        assert_eq!(
+            convert(ThemeFormat::WindowsTerminal, &settings).unwrap(),
+            colors
+        );
+    }
+
+    #[test]
+    fn test_base16_theme() {
+        let content = r#"
+scheme: "Default Dark"
+author: "Chris Kempson (http://chriskempson.com)"
+base00: "181818"
+base02: "383838"
+base05: "d8d8d8"
+base08: "ab4642"
+base0B: "a1b56c"
+"#;
+        let colors = colors(ThemeFormat::Base16, content);
+        assert_eq!(colors["background"].as_str(), Some("#181818"));
+        assert_eq!(colors["foreground"].as_str(), Some("#d8d8d8"));
+        assert_eq!(colors["selection-background"].as_str(), Some("#383838"));
+        assert_eq!(colors["red"].as_str(), Some("#ab4642"));
+        assert_eq!(colors["light-green"].as_str(), Some("#a1b56c"));
+
+        let palette = r##"
+system: "base16"
+name: "Default Dark"
+palette:
+  base00: "#181818"
+  base08: "#ab4642"
+"##;
+        let colors = convert(ThemeFormat::Base16, palette).unwrap();
+        assert_eq!(colors["red"].as_str(), Some("#ab4642"));
+    }
+
+    #[test]
+    fn test_convert_errors() {
+        assert!(convert(ThemeFormat::Kitty, "font_size 12").is_err());
+        assert!(convert(ThemeFormat::ITerm2, "<plist><dict>").is_err());
+        assert!(convert(ThemeFormat::WindowsTerminal, "{").is_err());
+        assert!(convert(ThemeFormat::Base16, "base00: [").is_err());
+    }
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// Time each row was first written, shown by `ToggleTimestamps`.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Timestamps {
+    /// Prefix the copied lines with their time.
+    #[serde(default)]
+    pub copy: bool,
+    /// Prefix the exported lines with their time.
+    #[serde(default)]
+    pub export: bool,
+}
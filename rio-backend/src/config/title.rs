@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use super::defaults::{default_title_content, default_title_placeholder};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Title {
     #[serde(default = "default_title_placeholder")]
     pub placeholder: Option<String>,
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+/// Action executed whenever `regex` matches a line written by the program.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Trigger {
+    pub regex: String,
+    #[serde(flatten)]
+    pub action: TriggerAction,
+}
+
+/// Text fields of the actions can reference the capture groups of the
+/// match, `$0` is the whole match, `$1` the first group and `${name}`
+/// a named group.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum TriggerAction {
+    /// Paints the match with the given colors (hex).
+    Highlight {
+        #[serde(default)]
+        foreground: Option<String>,
+        #[serde(default)]
+        background: Option<String>,
+    },
+    /// Desktop notification, the message defaults to the matched text.
+    Notify {
+        #[serde(default)]
+        title: Option<String>,
+        #[serde(default)]
+        message: Option<String>,
+    },
+    Bell,
+    /// Writes text to the program, as if it was typed.
+    SendText {
+        text: String,
+    },
+    /// Runs a program in the background, only `args` can reference the
+    /// capture groups.
+    Run {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Root {
+        triggers: Vec<Trigger>,
+    }
+
+    #[test]
+    fn test_deserialize_triggers() {
+        let content = r##"
+            [[triggers]]
+            regex = "error: (.*)"
+            action = "highlight"
+            foreground = "#ff0000"
+
+            [[triggers]]
+            regex = "Password:"
+            action = "send-text"
+            text = "hunter2"
+
+            [[triggers]]
+            regex = "build (\\w+) finished"
+            action = "run"
+            program = "say"
+            args = ["$1"]
+
+            [[triggers]]
+            regex = "done"
+            action = "bell"
+        "##;
+
+        let root: Root = toml::from_str(content).unwrap();
+        assert_eq!(
+            root.triggers,
+            vec![
+                Trigger {
+                    regex: String::from("error: (.*)"),
+                    action: TriggerAction::Highlight {
+                        foreground: Some(String::from("#ff0000")),
+                        background: None,
+                    },
+                },
+                Trigger {
+                    regex: String::from("Password:"),
+                    action: TriggerAction::SendText {
+                        text: String::from("hunter2"),
+                    },
+                },
+                Trigger {
+                    regex: String::from("build (\\w+) finished"),
+                    action: TriggerAction::Run {
+                        program: String::from("say"),
+                        args: vec![String::from("$1")],
+                    },
+                },
+                Trigger {
+                    regex: String::from("done"),
+                    action: TriggerAction::Bell,
+                },
+            ]
+        );
+    }
+}
@@ -1,8 +1,10 @@
 use crate::config::defaults::*;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "ui")]
 use sugarloaf::ImageProperties;
 
 #[derive(Default, Clone, Serialize, Deserialize, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum WindowMode {
     #[serde(alias = "maximized")]
     Maximized,
@@ -15,6 +17,7 @@ pub enum WindowMode {
 }
 
 #[derive(Clone, Serialize, Deserialize, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Decorations {
     #[serde(alias = "enabled")]
     Enabled,
@@ -40,6 +43,7 @@ impl Default for Decorations {
 }
 
 #[derive(PartialEq, Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum WindowsCornerPreference {
     #[serde(alias = "default")]
     Default = 0,
@@ -52,6 +56,7 @@ pub enum WindowsCornerPreference {
 }
 
 #[derive(PartialEq, Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Window {
     #[serde(default = "default_window_width")]
     pub width: i32,
@@ -63,6 +68,7 @@ pub struct Window {
     pub opacity: f32,
     #[serde(default = "bool::default")]
     pub blur: bool,
+    #[cfg(feature = "ui")]
     #[serde(rename = "background-image", skip_serializing)]
     pub background_image: Option<ImageProperties>,
     #[serde(default = "Decorations::default")]
@@ -74,13 +80,16 @@ pub struct Window {
     #[serde(rename = "initial-title", skip_serializing)]
     pub initial_title: Option<String>,
     #[serde(rename = "windows-use-undecorated-shadow", default = "Option::default")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub windows_use_undecorated_shadow: Option<bool>,
     #[serde(
         rename = "windows-use-no-redirection-bitmap",
         default = "Option::default"
     )]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub windows_use_no_redirection_bitmap: Option<bool>,
     #[serde(rename = "windows-corner-preference", default = "Option::default")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub windows_corner_preference: Option<WindowsCornerPreference>,
 }
 
@@ -91,6 +100,7 @@ impl Default for Window {
             height: default_window_height(),
             mode: WindowMode::default(),
             opacity: default_opacity(),
+            #[cfg(feature = "ui")]
             background_image: None,
             decorations: Decorations::default(),
             blur: false,
@@ -0,0 +1,744 @@
+// Serialises the grid content to plain text, ANSI escaped text, standalone
+// HTML or an SVG screenshot.
+
+use crate::config::colors::{AnsiColor, ColorArray, ColorRgb, Colors, NamedColor};
+use crate::config::Config;
+use crate::crosswords::grid::Dimensions;
+use crate::crosswords::pos::{Column, Line};
+use crate::crosswords::square::{Flags, Square};
+use crate::crosswords::timestamp::{self, GUTTER_COLUMNS};
+use crate::crosswords::Crosswords;
+use crate::event::EventListener;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+// Flags that change how a square looks.
+const STYLE_FLAGS: Flags = Flags::BOLD
+    .union(Flags::DIM)
+    .union(Flags::ITALIC)
+    .union(Flags::ALL_UNDERLINES)
+    .union(Flags::INVERSE)
+    .union(Flags::HIDDEN)
+    .union(Flags::STRIKEOUT);
+
+// Advance and height of a cell relative to the font size, used by SVG.
+const CELL_WIDTH_RATIO: f32 = 0.6;
+const CELL_HEIGHT_RATIO: f32 = 1.2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Text,
+    Ansi,
+    Html,
+    Svg,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Text => "txt",
+            ExportFormat::Ansi => "ans",
+            ExportFormat::Html => "html",
+            ExportFormat::Svg => "svg",
+        }
+    }
+
+    /// Format matching the file extension of `path`.
+    pub fn from_path(path: &Path) -> Option<ExportFormat> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<ExportFormat, String> {
+        match format.to_lowercase().as_str() {
+            "text" | "txt" => Ok(ExportFormat::Text),
+            "ansi" | "ans" => Ok(ExportFormat::Ansi),
+            "html" | "htm" => Ok(ExportFormat::Html),
+            "svg" => Ok(ExportFormat::Svg),
+            _ => Err(format!("unknown export format: {format}")),
+        }
+    }
+}
+
+/// Font and palette used by HTML and SVG.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportStyle {
+    pub font_family: String,
+    pub font_size: f32,
+    pub line_height: f32,
+    pub colors: Colors,
+    /// Prefix each line with the time it was first written.
+    pub timestamps: bool,
+}
+
+impl Default for ExportStyle {
+    fn default() -> ExportStyle {
+        ExportStyle::from(&Config::default())
+    }
+}
+
+impl From<&Config> for ExportStyle {
+    fn from(config: &Config) -> ExportStyle {
+        #[cfg(feature = "ui")]
+        let (font_family, font_size) = (
+            config
+                .fonts
+                .family
+                .clone()
+                .unwrap_or_else(|| config.fonts.regular.family.clone()),
+            config.fonts.size,
+        );
+        // Fonts are only configurable with the window system, these are the
+        // defaults of sugarloaf.
+        #[cfg(not(feature = "ui"))]
+        let (font_family, font_size) = (String::from("cascadiacode"), 14.);
+
+        ExportStyle {
+            font_family,
+            font_size,
+            line_height: config.line_height,
+            colors: config.colors,
+            timestamps: config.timestamps.export,
+        }
+    }
+}
+
+// Consecutive squares sharing the same style.
+struct Run {
+    text: String,
+    columns: usize,
+    fg: AnsiColor,
+    bg: AnsiColor,
+    flags: Flags,
+}
+
+struct ExportLine {
+    runs: Vec<Run>,
+    wrapped: bool,
+}
+
+impl<U: EventListener> Crosswords<U> {
+    /// Content of the viewport or, with `scrollback`, the whole history.
+    pub fn export(
+        &self,
+        format: ExportFormat,
+        scrollback: bool,
+        style: &ExportStyle,
+    ) -> String {
+        let (start, end) = if scrollback {
+            (self.grid.topmost_line(), self.grid.bottommost_line())
+        } else {
+            let top = Line(-(self.grid.display_offset() as i32));
+            (top, top + self.grid.screen_lines() - 1)
+        };
+
+        // A screenshot keeps the whole screen, otherwise empty lines at
+        // the end are dropped.
+        let trim = format != ExportFormat::Svg;
+        let mut lines: Vec<ExportLine> = Vec::new();
+        let mut line = start;
+        while line <= end {
+            lines.push(self.export_line(line, trim));
+            line += 1;
+        }
+
+        if trim {
+            while lines.last().is_some_and(|line| line.runs.is_empty()) {
+                lines.pop();
+            }
+        }
+
+        if style.timestamps {
+            let mut continued = false;
+            for (offset, export_line) in lines.iter_mut().enumerate() {
+                // Wrapped rows only get a blank gutter, to stay aligned in SVG.
+                if !continued || format == ExportFormat::Svg {
+                    let time = if continued {
+                        None
+                    } else {
+                        self.grid[start + offset].timestamp()
+                    };
+                    export_line.runs.insert(
+                        0,
+                        Run {
+                            text: timestamp::gutter(time),
+                            columns: GUTTER_COLUMNS,
+                            fg: AnsiColor::Named(NamedColor::Foreground),
+                            bg: AnsiColor::Named(NamedColor::Background),
+                            flags: Flags::DIM,
+                        },
+                    );
+                }
+                continued = export_line.wrapped;
+            }
+        }
+
+        match format {
+            ExportFormat::Text => export_text(&lines),
+            ExportFormat::Ansi => export_ansi(&lines),
+            ExportFormat::Html => self.export_html(&lines, style),
+            ExportFormat::Svg => self.export_svg(&lines, style),
+        }
+    }
+
+    fn export_line(&self, line: Line, trim: bool) -> ExportLine {
+        let row = &self.grid[line];
+        let columns = self.grid.columns();
+        let wrapped = row[Column(columns - 1)].flags.contains(Flags::WRAPLINE);
+
+        let mut length = columns;
+        if trim && !wrapped {
+            while length > 0 && is_blank(&row[Column(length - 1)]) {
+                length -= 1;
+            }
+        }
+
+        let mut runs: Vec<Run> = Vec::new();
+        for column in 0..length {
+            let square = &row[Column(column)];
+            if square
+                .flags
+                .intersects(Flags::WIDE_CHAR_SPACER | Flags::LEADING_WIDE_CHAR_SPACER)
+            {
+                if let Some(run) = runs.last_mut() {
+                    run.columns += 1;
+                }
+                continue;
+            }
+
+            let flags = square.flags & STYLE_FLAGS;
+            let run = match runs.last_mut() {
+                Some(run)
+                    if run.fg == square.fg
+                        && run.bg == square.bg
+                        && run.flags == flags =>
+                {
+                    run
+                }
+                _ => {
+                    runs.push(Run {
+                        text: String::new(),
+                        columns: 0,
+                        fg: square.fg,
+                        bg: square.bg,
+                        flags,
+                    });
+                    runs.last_mut().unwrap()
+                }
+            };
+
+            run.columns += 1;
+            run.text.push(if square.c == '\t' { ' ' } else { square.c });
+            if let Some(zerowidth) = square.zerowidth() {
+                run.text.extend(zerowidth);
+            }
+        }
+
+        ExportLine { runs, wrapped }
+    }
+
+    fn export_html(&self, lines: &[ExportLine], style: &ExportStyle) -> String {
+        let colors = &style.colors;
+        let background = hex(colors.background.0);
+        let title = if self.title.is_empty() {
+            "Rio"
+        } else {
+            &self.title
+        };
+
+        let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n");
+        html.push_str("<meta charset=\"utf-8\">\n");
+        let _ = writeln!(html, "<title>{}</title>", escape_xml(title));
+        let _ = writeln!(
+            html,
+            "<style>\nbody {{ margin: 0; background-color: {background}; }}\npre {{ margin: 0; padding: 8px; color: {}; font-family: '{}', monospace; font-size: {}px; line-height: {}; }}\n</style>",
+            hex(colors.foreground),
+            escape_xml(&style.font_family),
+            style.font_size,
+            CELL_HEIGHT_RATIO * style.line_height
+        );
+        html.push_str("</head>\n<body>\n<pre>");
+
+        for (index, line) in lines.iter().enumerate() {
+            for run in &line.runs {
+                let (fg, bg) = self.run_colors(run, colors);
+                let mut css = String::new();
+                if fg != colors.foreground {
+                    let _ = write!(css, "color: {};", hex(fg));
+                }
+                if bg != colors.background.0 {
+                    let _ = write!(css, "background-color: {};", hex(bg));
+                }
+                if run.flags.contains(Flags::BOLD) {
+                    css.push_str("font-weight: bold;");
+                }
+                if run.flags.contains(Flags::ITALIC) {
+                    css.push_str("font-style: italic;");
+                }
+                if let Some(decoration) = text_decoration(run.flags) {
+                    let _ = write!(css, "text-decoration: {decoration};");
+                }
+
+                if css.is_empty() {
+                    html.push_str(&escape_xml(&run.text));
+                } else {
+                    let _ = write!(
+                        html,
+                        "<span style=\"{css}\">{}</span>",
+                        escape_xml(&run.text)
+                    );
+                }
+            }
+
+            if !line.wrapped && index + 1 < lines.len() {
+                html.push('\n');
+            }
+        }
+
+        html.push_str("</pre>\n</body>\n</html>\n");
+        html
+    }
+
+    fn export_svg(&self, lines: &[ExportLine], style: &ExportStyle) -> String {
+        let colors = &style.colors;
+        let cell_width = style.font_size * CELL_WIDTH_RATIO;
+        let cell_height = style.font_size * CELL_HEIGHT_RATIO * style.line_height;
+        let mut columns = self.grid.columns();
+        if style.timestamps {
+            columns += GUTTER_COLUMNS;
+        }
+        let width = cell_width * columns as f32;
+        let height = cell_height * lines.len() as f32;
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" font-family=\"'{}', monospace\" font-size=\"{}\">",
+            escape_xml(&style.font_family),
+            style.font_size
+        );
+        let _ = writeln!(
+            svg,
+            "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+            hex(colors.background.0)
+        );
+
+        for (row, line) in lines.iter().enumerate() {
+            let y = row as f32 * cell_height;
+            let mut column = 0;
+            for run in &line.runs {
+                let x = column as f32 * cell_width;
+                let run_width = run.columns as f32 * cell_width;
+                column += run.columns;
+
+                let (fg, bg) = self.run_colors(run, colors);
+                if bg != colors.background.0 {
+                    let _ = writeln!(
+                        svg,
+                        "<rect x=\"{x}\" y=\"{y}\" width=\"{run_width}\" height=\"{cell_height}\" fill=\"{}\"/>",
+                        hex(bg)
+                    );
+                }
+
+                let decoration = text_decoration(run.flags);
+                if run.text.trim().is_empty() && decoration.is_none() {
+                    continue;
+                }
+
+                let mut attributes = format!("fill=\"{}\"", hex(fg));
+                if run.flags.contains(Flags::BOLD) {
+                    attributes.push_str(" font-weight=\"bold\"");
+                }
+                if run.flags.contains(Flags::ITALIC) {
+                    attributes.push_str(" font-style=\"italic\"");
+                }
+                if let Some(decoration) = decoration {
+                    let _ = write!(attributes, " text-decoration=\"{decoration}\"");
+                }
+
+                // Glyphs are stretched to the grid, so columns stay aligned
+                // regardless of the font used to display it.
+                let _ = writeln!(
+                    svg,
+                    "<text x=\"{x}\" y=\"{}\" textLength=\"{run_width}\" lengthAdjust=\"spacingAndGlyphs\" xml:space=\"preserve\" {attributes}>{}</text>",
+                    y + cell_height * 0.8,
+                    escape_xml(&run.text)
+                );
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn run_colors(&self, run: &Run, colors: &Colors) -> (ColorArray, ColorArray) {
+        let mut fg = self.resolve_color(&run.fg, run.flags, colors);
+        let mut bg = self.resolve_color(&run.bg, Flags::empty(), colors);
+        if run.flags.contains(Flags::INVERSE) {
+            std::mem::swap(&mut fg, &mut bg);
+        }
+        if run.flags.contains(Flags::HIDDEN) {
+            fg = bg;
+        }
+
+        (fg, bg)
+    }
+
+    fn resolve_color(
+        &self,
+        color: &AnsiColor,
+        flags: Flags,
+        colors: &Colors,
+    ) -> ColorArray {
+        match color {
+            AnsiColor::Named(name) => {
+                let name = if flags.contains(Flags::DIM) {
+                    name.to_dim()
+                } else if flags.contains(Flags::BOLD) {
+                    name.to_light()
+                } else {
+                    *name
+                };
+                named_color(name, colors)
+            }
+            AnsiColor::Spec(rgb) => {
+                if flags.contains(Flags::DIM) {
+                    rgb.to_arr_with_dim()
+                } else {
+                    rgb.to_arr()
+                }
+            }
+            AnsiColor::Indexed(index) => {
+                let index = match (flags & Flags::DIM_BOLD, index) {
+                    (Flags::DIM, 8..=15) => *index as usize - 8,
+                    (Flags::DIM, 0..=7) => {
+                        NamedColor::DimBlack as usize + *index as usize
+                    }
+                    _ => *index as usize,
+                };
+                match index {
+                    16..=255 => self.colors[index],
+                    _ => named_color(ansi_named_color(index), colors),
+                }
+            }
+        }
+    }
+}
+
+fn export_text(lines: &[ExportLine]) -> String {
+    let mut text = String::new();
+    for line in lines {
+        for run in &line.runs {
+            text.push_str(&run.text);
+        }
+        if !line.wrapped {
+            text.push('\n');
+        }
+    }
+
+    text
+}
+
+fn export_ansi(lines: &[ExportLine]) -> String {
+    let mut text = String::new();
+    for line in lines {
+        let mut styled = false;
+        for run in &line.runs {
+            let sgr = sgr(run);
+            if !sgr.is_empty() {
+                let _ = write!(text, "\x1b[0;{sgr}m");
+                styled = true;
+            } else if styled {
+                text.push_str("\x1b[0m");
+                styled = false;
+            }
+            text.push_str(&run.text);
+        }
+
+        if styled {
+            text.push_str("\x1b[0m");
+        }
+        if !line.wrapped {
+            text.push('\n');
+        }
+    }
+
+    text
+}
+
+fn sgr(run: &Run) -> String {
+    let mut params: Vec<String> = Vec::new();
+    let flags = run.flags;
+    if flags.contains(Flags::BOLD) {
+        params.push(String::from("1"));
+    }
+    if flags.contains(Flags::DIM) {
+        params.push(String::from("2"));
+    }
+    if flags.contains(Flags::ITALIC) {
+        params.push(String::from("3"));
+    }
+    if flags.contains(Flags::UNDERLINE) {
+        params.push(String::from("4"));
+    } else if flags.contains(Flags::DOUBLE_UNDERLINE) {
+        params.push(String::from("4:2"));
+    } else if flags.contains(Flags::UNDERCURL) {
+        params.push(String::from("4:3"));
+    } else if flags.contains(Flags::DOTTED_UNDERLINE) {
+        params.push(String::from("4:4"));
+    } else if flags.contains(Flags::DASHED_UNDERLINE) {
+        params.push(String::from("4:5"));
+    }
+    if flags.contains(Flags::INVERSE) {
+        params.push(String::from("7"));
+    }
+    if flags.contains(Flags::HIDDEN) {
+        params.push(String::from("8"));
+    }
+    if flags.contains(Flags::STRIKEOUT) {
+        params.push(String::from("9"));
+    }
+    if let Some(color) = sgr_color(&run.fg, 30, 90, 38) {
+        params.push(color);
+    }
+    if let Some(color) = sgr_color(&run.bg, 40, 100, 48) {
+        params.push(color);
+    }
+
+    params.join(";")
+}
+
+fn sgr_color(color: &AnsiColor, normal: u8, light: u8, extended: u8) -> Option<String> {
+    match color {
+        AnsiColor::Named(name) => {
+            let index = *name as usize;
+            match index {
+                0..=7 => Some((normal + index as u8).to_string()),
+                8..=15 => Some((light + index as u8 - 8).to_string()),
+                _ => {
+                    let dim = NamedColor::DimBlack as usize;
+                    if (dim..dim + 8).contains(&index) {
+                        Some((normal + (index - dim) as u8).to_string())
+                    } else {
+                        // Default foreground, background and cursor colors
+                        None
+                    }
+                }
+            }
+        }
+        AnsiColor::Indexed(index) => Some(format!("{extended};5;{index}")),
+        AnsiColor::Spec(rgb) => {
+            Some(format!("{extended};2;{};{};{}", rgb.r, rgb.g, rgb.b))
+        }
+    }
+}
+
+fn is_blank(square: &Square) -> bool {
+    square.c == ' '
+        && square.bg == AnsiColor::Named(NamedColor::Background)
+        && !square
+            .flags
+            .intersects(Flags::INVERSE | Flags::ALL_UNDERLINES | Flags::STRIKEOUT)
+}
+
+fn text_decoration(flags: Flags) -> Option<&'static str> {
+    match (
+        flags.intersects(Flags::ALL_UNDERLINES),
+        flags.contains(Flags::STRIKEOUT),
+    ) {
+        (true, true) => Some("underline line-through"),
+        (true, false) => Some("underline"),
+        (false, true) => Some("line-through"),
+        (false, false) => None,
+    }
+}
+
+fn ansi_named_color(index: usize) -> NamedColor {
+    match index {
+        0 => NamedColor::Black,
+        1 => NamedColor::Red,
+        2 => NamedColor::Green,
+        3 => NamedColor::Yellow,
+        4 => NamedColor::Blue,
+        5 => NamedColor::Magenta,
+        6 => NamedColor::Cyan,
+        7 => NamedColor::White,
+        8 => NamedColor::LightBlack,
+        9 => NamedColor::LightRed,
+        10 => NamedColor::LightGreen,
+        11 => NamedColor::LightYellow,
+        12 => NamedColor::LightBlue,
+        13 => NamedColor::LightMagenta,
+        14 => NamedColor::LightCyan,
+        15 => NamedColor::LightWhite,
+        257 => NamedColor::Background,
+        258 => NamedColor::Cursor,
+        259 => NamedColor::DimBlack,
+        260 => NamedColor::DimRed,
+        261 => NamedColor::DimGreen,
+        262 => NamedColor::DimYellow,
+        263 => NamedColor::DimBlue,
+        264 => NamedColor::DimMagenta,
+        265 => NamedColor::DimCyan,
+        266 => NamedColor::DimWhite,
+        267 => NamedColor::LightForeground,
+        268 => NamedColor::DimForeground,
+        _ => NamedColor::Foreground,
+    }
+}
+
+fn named_color(name: NamedColor, colors: &Colors) -> ColorArray {
+    match name {
+        NamedColor::Black => colors.black,
+        NamedColor::Red => colors.red,
+        NamedColor::Green => colors.green,
+        NamedColor::Yellow => colors.yellow,
+        NamedColor::Blue => colors.blue,
+        NamedColor::Magenta => colors.magenta,
+        NamedColor::Cyan => colors.cyan,
+        NamedColor::White => colors.white,
+        NamedColor::LightBlack => colors.light_black,
+        NamedColor::LightRed => colors.light_red,
+        NamedColor::LightGreen => colors.light_green,
+        NamedColor::LightYellow => colors.light_yellow,
+        NamedColor::LightBlue => colors.light_blue,
+        NamedColor::LightMagenta => colors.light_magenta,
+        NamedColor::LightCyan => colors.light_cyan,
+        NamedColor::LightWhite => colors.light_white,
+        NamedColor::Foreground => colors.foreground,
+        NamedColor::Background => colors.background.0,
+        NamedColor::Cursor => colors.cursor,
+        NamedColor::DimBlack => colors.dim_black,
+        NamedColor::DimRed => colors.dim_red,
+        NamedColor::DimGreen => colors.dim_green,
+        NamedColor::DimYellow => colors.dim_yellow,
+        NamedColor::DimBlue => colors.dim_blue,
+        NamedColor::DimMagenta => colors.dim_magenta,
+        NamedColor::DimCyan => colors.dim_cyan,
+        NamedColor::DimWhite => colors.dim_white,
+        NamedColor::LightForeground => colors.light_foreground,
+        NamedColor::DimForeground => colors.dim_foreground,
+    }
+}
+
+fn hex(color: ColorArray) -> String {
+    let rgb = ColorRgb::from_color_arr(color);
+    format!("#{:02x}{:02x}{:02x}", rgb.r, rgb.g, rgb.b)
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c if c.is_control() => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crosswords::CrosswordsSize;
+    use crate::event::{VoidListener, WindowId};
+    use crate::performer::handler::ParserProcessor;
+
+    fn terminal(content: &[u8]) -> Crosswords<VoidListener> {
+        let size = CrosswordsSize::new(10, 4);
+        let window_id = WindowId::from(0);
+        let mut terminal = Crosswords::new(
+            size,
+            crate::ansi::CursorShape::Block,
+            VoidListener {},
+            window_id,
+            0,
+        );
+        let mut parser = ParserProcessor::new();
+        for byte in content {
+            parser.advance(&mut terminal, *byte);
+        }
+        terminal
+    }
+
+    #[test]
+    fn test_export_format() {
+        assert_eq!("HTML".parse(), Ok(ExportFormat::Html));
+        assert_eq!(
+            ExportFormat::from_path(Path::new("/tmp/screen.ans")),
+            Some(ExportFormat::Ansi)
+        );
+        assert_eq!(ExportFormat::from_path(Path::new("/tmp/screen")), None);
+    }
+
+    #[test]
+    fn test_export_text_and_ansi() {
+        let terminal = terminal(b"ab\x1b[1;31mcd\x1b[0m\r\n0123456789xy");
+        let style = ExportStyle::default();
+
+        assert_eq!(
+            terminal.export(ExportFormat::Text, false, &style),
+            "abcd\n0123456789xy\n"
+        );
+        assert_eq!(
+            terminal.export(ExportFormat::Ansi, false, &style),
+            "ab\x1b[0;1;31mcd\x1b[0m\n0123456789xy\n"
+        );
+    }
+
+    #[test]
+    fn test_export_html_and_svg() {
+        let terminal = terminal(b"<a>\x1b[4;38;2;255;0;0mb\x1b[0m");
+        let style = ExportStyle::default();
+
+        let html = terminal.export(ExportFormat::Html, false, &style);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains(
+            "<pre>&lt;a&gt;<span style=\"color: #ff0000;text-decoration: underline;\">b</span></pre>"
+        ));
+
+        let svg = terminal.export(ExportFormat::Svg, false, &style);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains(&format!(
+            "font-family=\"'{}', monospace\"",
+            style.font_family
+        )));
+        assert!(svg.contains(">&lt;a&gt;</text>"));
+        assert!(svg.contains("fill=\"#ff0000\" text-decoration=\"underline\">b</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_export_timestamps() {
+        let mut terminal = terminal(b"first\r\n0123456789xy");
+        terminal.grid[Line(0)].timestamp = std::num::NonZeroU32::new(1_700_000_000);
+        terminal.grid[Line(1)].timestamp = std::num::NonZeroU32::new(1_700_000_060);
+        let style = ExportStyle {
+            timestamps: true,
+            ..ExportStyle::default()
+        };
+
+        // The wrapped row continues the line without its own time.
+        assert_eq!(
+            terminal.export(ExportFormat::Text, false, &style),
+            format!(
+                "{}first\n{}0123456789xy\n",
+                timestamp::gutter(Some(1_700_000_000)),
+                timestamp::gutter(Some(1_700_000_060))
+            )
+        );
+    }
+}
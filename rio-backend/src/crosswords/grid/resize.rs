@@ -171,6 +171,7 @@ impl<T: GridSquare + Default + PartialEq + Clone> Grid<T> {
 
             // Add removed cells to previous row and reflow content.
             last_row.append(&mut cells);
+            last_row.merge_timestamp(row.timestamp);
 
             let cursor_buffer_line = self.lines - self.cursor.pos.row.0 as usize - 1;
 
@@ -261,6 +262,7 @@ impl<T: GridSquare + Default + PartialEq + Clone> Grid<T> {
 
         let mut new_raw = Vec::with_capacity(self.raw.len());
         let mut buffered: Option<Vec<T>> = None;
+        let mut buffered_timestamp = None;
 
         let mut rows = self.raw.take_all();
         for (i, mut row) in rows.drain(..).enumerate().rev() {
@@ -274,8 +276,12 @@ impl<T: GridSquare + Default + PartialEq + Clone> Grid<T> {
                 }
 
                 row.append_front(buffered);
+                row.merge_timestamp(buffered_timestamp.take());
             }
 
+            // Rows created by wrapping keep the timestamp of their origin.
+            let timestamp = row.timestamp;
+
             loop {
                 // Remove all cells which require reflowing.
                 let mut wrapped = match row.shrink(columns) {
@@ -347,6 +353,7 @@ impl<T: GridSquare + Default + PartialEq + Clone> Grid<T> {
 
                     // Add removed cells to start of next row.
                     buffered = Some(wrapped);
+                    buffered_timestamp = timestamp;
                     break;
                 } else {
                     // Reflow cursor if a line below it is deleted.
@@ -371,6 +378,7 @@ impl<T: GridSquare + Default + PartialEq + Clone> Grid<T> {
                         wrapped.resize_with(columns, T::default);
                     }
                     row = Row::from_vec(wrapped, occ);
+                    row.timestamp = timestamp;
 
                     if i < self.display_offset {
                         // Since we added a new line, rotate up the viewport.
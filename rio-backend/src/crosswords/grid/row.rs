@@ -5,9 +5,11 @@
 use crate::crosswords::grid::GridSquare;
 use crate::crosswords::square::Flags;
 use crate::crosswords::square::ResetDiscriminant;
+use crate::crosswords::timestamp;
 use crate::crosswords::Column;
 use core::cmp::min;
 use std::cmp::max;
+use std::num::NonZeroU32;
 use std::ops::{Index, IndexMut, Range, RangeFrom, RangeFull, RangeTo, RangeToInclusive};
 use std::{ptr, slice};
 
@@ -20,7 +22,12 @@ pub struct Row<T> {
     ///
     /// This is the upper bound on the number of elements in the row, which have been modified
     /// since the last reset. All cells after this point are guaranteed to be equal.
-    pub(crate) occ: usize,
+    ///
+    /// Stored as `u32` so the timestamp fits in the same word.
+    pub(crate) occ: u32,
+
+    /// Seconds since the Unix epoch when the row was first written.
+    pub(crate) timestamp: Option<NonZeroU32>,
 }
 
 impl<T: PartialEq> PartialEq for Row<T> {
@@ -51,7 +58,11 @@ impl<T: Clone + Default> Row<T> {
             inner.set_len(columns);
         }
 
-        Row { inner, occ: 0 }
+        Row {
+            inner,
+            occ: 0,
+            timestamp: None,
+        }
     }
 
     /// Increase the number of columns in the row.
@@ -80,7 +91,7 @@ impl<T: Clone + Default> Row<T> {
             .map_or(0, |i| i + 1);
         new_row.truncate(index);
 
-        self.occ = min(self.occ, columns);
+        self.occ = min(self.occ, columns as u32);
 
         if new_row.is_empty()
             || new_row
@@ -105,15 +116,16 @@ impl<T: Clone + Default> Row<T> {
         // Mark all cells as dirty if template cell changed.
         let len = self.inner.len();
         if self.inner[len - 1].discriminant() != template.discriminant() {
-            self.occ = len;
+            self.occ = len as u32;
         }
 
         // Reset every dirty cell in the row.
-        for item in &mut self.inner[0..self.occ] {
+        for item in &mut self.inner[0..self.occ as usize] {
             item.reset(template);
         }
 
         self.occ = 0;
+        self.timestamp = None;
     }
 }
 
@@ -121,7 +133,35 @@ impl<T: Clone + Default> Row<T> {
 impl<T> Row<T> {
     #[inline]
     pub fn from_vec(vec: Vec<T>, occ: usize) -> Row<T> {
-        Row { inner: vec, occ }
+        Row {
+            inner: vec,
+            occ: occ as u32,
+            timestamp: None,
+        }
+    }
+
+    /// Seconds since the Unix epoch when the row was first written.
+    #[inline]
+    pub fn timestamp(&self) -> Option<u32> {
+        self.timestamp.map(NonZeroU32::get)
+    }
+
+    /// Records the current time unless the row was already written.
+    #[inline]
+    pub fn stamp(&mut self) {
+        if self.timestamp.is_none() {
+            self.timestamp = timestamp::now();
+        }
+    }
+
+    /// Keeps the earliest timestamp when content of `other` is merged
+    /// into this row.
+    #[inline]
+    pub fn merge_timestamp(&mut self, other: Option<NonZeroU32>) {
+        self.timestamp = match (self.timestamp, other) {
+            (Some(current), Some(other)) => Some(current.min(other)),
+            (current, other) => current.or(other),
+        };
     }
 
     #[inline]
@@ -136,7 +176,7 @@ impl<T> Row<T> {
 
     #[inline]
     pub fn last_mut(&mut self) -> Option<&mut T> {
-        self.occ = self.inner.len();
+        self.occ = self.inner.len() as u32;
         self.inner.last_mut()
     }
 
@@ -145,13 +185,13 @@ impl<T> Row<T> {
     where
         T: GridSquare,
     {
-        self.occ += vec.len();
+        self.occ += vec.len() as u32;
         self.inner.append(vec);
     }
 
     #[inline]
     pub fn append_front(&mut self, mut vec: Vec<T>) {
-        self.occ += vec.len();
+        self.occ += vec.len() as u32;
 
         vec.append(&mut self.inner);
         self.inner = vec;
@@ -159,7 +199,7 @@ impl<T> Row<T> {
 
     #[inline]
     pub fn front_split_off(&mut self, at: usize) -> Vec<T> {
-        self.occ = self.occ.saturating_sub(at);
+        self.occ = self.occ.saturating_sub(at as u32);
 
         let mut split = self.inner.split_off(at);
         std::mem::swap(&mut split, &mut self.inner);
@@ -191,7 +231,7 @@ impl<'a, T> IntoIterator for &'a mut Row<T> {
 
     #[inline]
     fn into_iter(self) -> slice::IterMut<'a, T> {
-        self.occ = self.len();
+        self.occ = self.len() as u32;
         self.inner.iter_mut()
     }
 }
@@ -208,7 +248,7 @@ impl<T> Index<Column> for Row<T> {
 impl<T> IndexMut<Column> for Row<T> {
     #[inline]
     fn index_mut(&mut self, index: Column) -> &mut T {
-        self.occ = max(self.occ, *index + 1);
+        self.occ = max(self.occ, *index as u32 + 1);
         &mut self.inner[index.0]
     }
 }
@@ -225,7 +265,7 @@ impl<T> Index<Range<Column>> for Row<T> {
 impl<T> IndexMut<Range<Column>> for Row<T> {
     #[inline]
     fn index_mut(&mut self, index: Range<Column>) -> &mut [T] {
-        self.occ = max(self.occ, *index.end);
+        self.occ = max(self.occ, *index.end as u32);
         &mut self.inner[(index.start.0)..(index.end.0)]
     }
 }
@@ -242,7 +282,7 @@ impl<T> Index<RangeTo<Column>> for Row<T> {
 impl<T> IndexMut<RangeTo<Column>> for Row<T> {
     #[inline]
     fn index_mut(&mut self, index: RangeTo<Column>) -> &mut [T] {
-        self.occ = max(self.occ, *index.end);
+        self.occ = max(self.occ, *index.end as u32);
         &mut self.inner[..(index.end.0)]
     }
 }
@@ -259,7 +299,7 @@ impl<T> Index<RangeFrom<Column>> for Row<T> {
 impl<T> IndexMut<RangeFrom<Column>> for Row<T> {
     #[inline]
     fn index_mut(&mut self, index: RangeFrom<Column>) -> &mut [T] {
-        self.occ = self.len();
+        self.occ = self.len() as u32;
         &mut self.inner[(index.start.0)..]
     }
 }
@@ -276,7 +316,7 @@ impl<T> Index<RangeFull> for Row<T> {
 impl<T> IndexMut<RangeFull> for Row<T> {
     #[inline]
     fn index_mut(&mut self, _: RangeFull) -> &mut [T] {
-        self.occ = self.len();
+        self.occ = self.len() as u32;
         &mut self.inner[..]
     }
 }
@@ -293,7 +333,7 @@ impl<T> Index<RangeToInclusive<Column>> for Row<T> {
 impl<T> IndexMut<RangeToInclusive<Column>> for Row<T> {
     #[inline]
     fn index_mut(&mut self, index: RangeToInclusive<Column>) -> &mut [T] {
-        self.occ = max(self.occ, *index.end);
+        self.occ = max(self.occ, *index.end as u32);
         &mut self.inner[..=(index.end.0)]
     }
 }
@@ -4,7 +4,6 @@
 
 use std::cmp::{max, PartialEq};
 use std::mem;
-use std::mem::MaybeUninit;
 use std::ops::{Index, IndexMut};
 
 use super::Row;
@@ -152,37 +151,15 @@ impl<T> Storage<T> {
         self.len == 0
     }
 
-    /// Swap implementation for Row<T>.
+    /// Swap two rows.
     ///
-    /// Exploits the known size of Row<T> to produce a slightly more efficient
-    /// swap than going through slice::swap.
-    ///
-    /// The default implementation from swap generates 8 movups and 4 movaps
-    /// instructions. This implementation achieves the swap in only 8 movups
-    /// instructions.
+    /// Goes through `slice::swap` so it doesn't depend on the size of
+    /// `Row<T>`, which differs between targets.
+    #[inline]
     pub fn swap(&mut self, a: Line, b: Line) {
-        debug_assert_eq!(mem::size_of::<Row<T>>(), mem::size_of::<usize>() * 4);
-
         let a = self.compute_index(a);
         let b = self.compute_index(b);
-
-        unsafe {
-            // Cast to a qword array to opt out of copy restrictions and avoid
-            // drop hazards. Byte array is no good here since for whatever
-            // reason LLVM won't optimized it.
-            let a_ptr = self.inner.as_mut_ptr().add(a) as *mut MaybeUninit<usize>;
-            let b_ptr = self.inner.as_mut_ptr().add(b) as *mut MaybeUninit<usize>;
-
-            // Copy 1 qword at a time.
-            //
-            // The optimizer unrolls this loop and vectorizes it.
-            let mut tmp: MaybeUninit<usize>;
-            for i in 0..4 {
-                tmp = *a_ptr.offset(i);
-                *a_ptr.offset(i) = *b_ptr.offset(i);
-                *b_ptr.offset(i) = tmp;
-            }
-        }
+        self.inner.swap(a, b);
     }
 
     /// Rotate the grid, moving all lines up/down in history.
@@ -308,6 +285,23 @@ mod tests {
         assert_eq!(storage[Line(2)], filled_row('1'));
     }
 
+    #[test]
+    fn swap_keeps_row_state() {
+        let mut storage = Storage::<char>::with_capacity(3, 1);
+        storage[Line(0)] = filled_row('0');
+        storage[Line(2)] = filled_row('2');
+        storage[Line(2)].occ = 1;
+        storage[Line(2)].timestamp = std::num::NonZeroU32::new(42);
+
+        storage.swap(Line(0), Line(2));
+
+        assert_eq!(storage[Line(0)], filled_row('2'));
+        assert_eq!(storage[Line(0)].occ, 1);
+        assert_eq!(storage[Line(0)].timestamp(), Some(42));
+        assert_eq!(storage[Line(2)], filled_row('0'));
+        assert_eq!(storage[Line(2)].timestamp(), None);
+    }
+
     #[test]
     #[should_panic]
     #[cfg(debug_assertions)]
@@ -192,6 +192,25 @@ fn shrink_reflow() {
     assert_eq!(grid[Line(0)][Column(1)], Square::default());
 }
 
+#[test]
+fn reflow_keeps_timestamps() {
+    let mut grid = Grid::<Square>::new(1, 5, 2);
+    for (column, c) in "12345".chars().enumerate() {
+        grid[Line(0)][Column(column)] = cell(c);
+    }
+    grid[Line(0)].timestamp = std::num::NonZeroU32::new(42);
+
+    grid.resize(true, 1, 2);
+
+    for line in -2..=0 {
+        assert_eq!(grid[Line(line)].timestamp(), Some(42));
+    }
+
+    grid.resize(true, 1, 5);
+
+    assert_eq!(grid[Line(0)].timestamp(), Some(42));
+}
+
 #[test]
 fn shrink_reflow_twice() {
     let mut grid = Grid::<Square>::new(1, 5, 2);
@@ -15,19 +15,21 @@
 */
 
 pub mod attr;
+pub mod export;
 pub mod grid;
 pub mod pos;
 pub mod search;
 pub mod square;
+pub mod timestamp;
+pub mod triggers;
 pub mod vi_mode;
 
-use crate::ansi::graphics::GraphicCell;
-use crate::ansi::graphics::Graphics;
-use crate::ansi::graphics::TextureRef;
-use crate::ansi::graphics::UpdateQueues;
+#[cfg(feature = "ui")]
+use crate::ansi::graphics::{GraphicCell, Graphics, TextureRef, UpdateQueues};
 use crate::ansi::mode::NamedMode;
 use crate::ansi::mode::NamedPrivateMode;
 use crate::ansi::mode::PrivateMode;
+#[cfg(feature = "ui")]
 use crate::ansi::sixel;
 use crate::ansi::{
     mode::Mode as AnsiMode, ClearMode, CursorShape, KeyboardModes,
@@ -47,20 +49,24 @@ use crate::selection::{Selection, SelectionRange, SelectionType};
 use attr::*;
 use base64::{engine::general_purpose, Engine as _};
 use bitflags::bitflags;
+#[cfg(feature = "ui")]
 use copa::Params;
 use grid::row::Row;
 use pos::{
     Boundary, CharsetIndex, Column, Cursor, CursorState, Direction, Line, Pos, Side,
 };
 use square::{Hyperlink, LineLength, Square};
+#[cfg(feature = "ui")]
 use std::collections::HashSet;
 use std::mem;
 use std::ops::{Index, IndexMut, Range};
 use std::option::Option;
 use std::ptr;
 use std::sync::Arc;
+#[cfg(feature = "ui")]
 use sugarloaf::{GraphicData, MAX_GRAPHIC_DIMENSIONS};
 use tracing::{debug, info, trace, warn};
+use triggers::Triggers;
 use unicode_width::UnicodeWidthChar;
 use vi_mode::{ViModeCursor, ViMotion};
 
@@ -70,6 +76,7 @@ pub const MIN_COLUMNS: usize = 2;
 pub const MIN_LINES: usize = 1;
 
 /// Max. number of graphics stored in a single cell.
+#[cfg(feature = "ui")]
 const MAX_GRAPHICS_PER_CELL: usize = 20;
 
 bitflags! {
@@ -399,6 +406,11 @@ where
     colors: List,
     pub title: String,
     damage: TermDamageState,
+    /// Cell width in pixels.
+    square_width: f32,
+    /// Cell height in pixels.
+    square_height: f32,
+    #[cfg(feature = "ui")]
     pub graphics: Graphics,
     pub cursor_shape: CursorShape,
     pub default_cursor_shape: CursorShape,
@@ -408,6 +420,7 @@ where
     title_stack: Vec<String>,
     current_directory: Option<std::path::PathBuf>,
     hyperlink_re: regex::Regex,
+    triggers: Triggers,
 
     // The stack for the keyboard modes.
     keyboard_mode_stack: Vec<KeyboardModes>,
@@ -455,7 +468,10 @@ impl<U: EventListener> Crosswords<U> {
                 | Mode::ALTERNATE_SCROLL
                 | Mode::URGENCY_HINTS,
             damage: TermDamageState::new(cols, rows),
-            graphics: Graphics::new(&dimensions),
+            square_width: dimensions.square_width(),
+            square_height: dimensions.square_height(),
+            #[cfg(feature = "ui")]
+            graphics: Graphics::default(),
             default_cursor_shape: cursor_shape,
             cursor_shape,
             blinking_cursor: false,
@@ -463,6 +479,7 @@ impl<U: EventListener> Crosswords<U> {
             route_id,
             title_stack: Default::default(),
             current_directory: None,
+            triggers: Triggers::default(),
             keyboard_mode_stack: Default::default(),
             inactive_keyboard_mode_stack: Default::default(),
         }
@@ -523,6 +540,7 @@ impl<U: EventListener> Crosswords<U> {
 
     /// Get queues to update graphic data. If both queues are empty, it returns
     /// `None`.
+    #[cfg(feature = "ui")]
     #[inline]
     pub fn graphics_take_queues(&mut self) -> Option<UpdateQueues> {
         self.graphics.take_queues()
@@ -587,8 +605,9 @@ impl<U: EventListener> Crosswords<U> {
         // Resize damage information.
         self.damage.resize(num_cols, num_lines);
 
-        // Update size information for graphics.
-        self.graphics.resize(&size);
+        // Update the cell dimensions.
+        self.square_width = size.square_width();
+        self.square_height = size.square_height();
     }
 
     /// Toggle the vi mode.
@@ -972,6 +991,9 @@ impl<U: EventListener> Crosswords<U> {
         cursor_square.bg = bg;
         cursor_square.flags = flags;
         cursor_square.extra = extra;
+
+        let line = self.grid.cursor.pos.row;
+        self.grid[line].stamp();
     }
 
     #[inline]
@@ -1085,6 +1107,16 @@ impl<U: EventListener> Crosswords<U> {
     }
 
     pub fn selection_to_string(&self) -> Option<String> {
+        self.selection_text(false)
+    }
+
+    /// Same as `selection_to_string` with the time each line was written
+    /// in front of it.
+    pub fn selection_to_string_with_timestamps(&self) -> Option<String> {
+        self.selection_text(true)
+    }
+
+    fn selection_text(&self, timestamps: bool) -> Option<String> {
         let selection_range = self.selection.as_ref().and_then(|s| s.to_range(self))?;
         let SelectionRange { start, end, .. } = selection_range;
 
@@ -1096,12 +1128,18 @@ impl<U: EventListener> Crosswords<U> {
                 ..
             }) => {
                 for line in (start.row.0..end.row.0).map(Line::from) {
+                    if timestamps {
+                        res += &self.line_timestamp(line);
+                    }
                     res += self
                         .line_to_string(line, start.col..end.col, start.col.0 != 0)
                         .trim_end();
                     res += "\n";
                 }
 
+                if timestamps {
+                    res += &self.line_timestamp(end.row);
+                }
                 res += self
                     .line_to_string(end.row, start.col..end.col, true)
                     .trim_end();
@@ -1110,10 +1148,10 @@ impl<U: EventListener> Crosswords<U> {
                 ty: SelectionType::Lines,
                 ..
             }) => {
-                res = self.bounds_to_string(start, end) + "\n";
+                res = self.bounds_text(start, end, timestamps) + "\n";
             }
             _ => {
-                res = self.bounds_to_string(start, end);
+                res = self.bounds_text(start, end, timestamps);
             }
         }
 
@@ -1121,9 +1159,23 @@ impl<U: EventListener> Crosswords<U> {
     }
 
     pub fn bounds_to_string(&self, start: Pos, end: Pos) -> String {
+        self.bounds_text(start, end, false)
+    }
+
+    fn bounds_text(&self, start: Pos, end: Pos, timestamps: bool) -> String {
         let mut res = String::new();
 
         for line in (start.row.0..=end.row.0).map(Line::from) {
+            // Wrapped rows continue the text of the previous one.
+            if timestamps
+                && (line == start.row
+                    || !self.grid[line - 1i32][self.grid.last_column()]
+                        .flags
+                        .contains(square::Flags::WRAPLINE))
+            {
+                res += &self.line_timestamp(line);
+            }
+
             let start_col = if line == start.row {
                 start.col
             } else {
@@ -1141,6 +1193,12 @@ impl<U: EventListener> Crosswords<U> {
         res.strip_suffix('\n').map(str::to_owned).unwrap_or(res)
     }
 
+    /// Gutter text with the time `line` was first written.
+    #[inline]
+    fn line_timestamp(&self, line: Line) -> String {
+        timestamp::gutter(self.grid[line].timestamp())
+    }
+
     /// Convert a single line in the grid to a String.
     fn line_to_string(
         &self,
@@ -1411,7 +1469,10 @@ impl<U: EventListener> Handler for Crosswords<U> {
 
             // SixelPrivateColorRegisters
             PrivateMode::Unknown(1070) => {
-                self.graphics.sixel_shared_palette = None;
+                #[cfg(feature = "ui")]
+                {
+                    self.graphics.sixel_shared_palette = None;
+                }
                 self.mode.remove(Mode::SIXEL_PRIV_PALETTE);
                 return;
             }
@@ -2275,6 +2336,8 @@ impl<U: EventListener> Handler for Crosswords<U> {
 
     #[inline]
     fn linefeed(&mut self) {
+        self.triggers_on_linefeed();
+
         let next = self.grid.cursor.pos.row + 1;
         if next == self.scroll_region.end {
             self.scroll_up_relative(self.scroll_region.start, 1);
@@ -2318,7 +2381,8 @@ impl<U: EventListener> Handler for Crosswords<U> {
 
     #[inline]
     fn bell(&mut self) {
-        warn!("[unimplemented] Bell");
+        self.event_proxy
+            .send_event(RioEvent::BellOnRoute(self.route_id), self.window_id);
     }
 
     #[inline]
@@ -2497,10 +2561,7 @@ impl<U: EventListener> Handler for Crosswords<U> {
     #[inline]
     fn cells_size_pixels(&mut self) {
         // https://terminalguide.namepad.de/seq/csi_st-16/
-        let text = format!(
-            "\x1b[6;{};{}t",
-            self.graphics.cell_height, self.graphics.cell_width
-        );
+        let text = format!("\x1b[6;{};{}t", self.square_height, self.square_width);
         debug!("cells_size_pixels {:?}", text);
         self.event_proxy
             .send_event(RioEvent::PtyWrite(text), self.window_id);
@@ -2518,6 +2579,7 @@ impl<U: EventListener> Handler for Crosswords<U> {
             .send_event(RioEvent::PtyWrite(text), self.window_id);
     }
 
+    #[cfg(feature = "ui")]
     #[inline]
     fn graphics_attribute(&mut self, pi: u16, pa: u16) {
         // From Xterm documentation:
@@ -2618,17 +2680,20 @@ impl<U: EventListener> Handler for Crosswords<U> {
         );
     }
 
+    #[cfg(feature = "ui")]
     #[inline]
     fn sixel_graphic_start(&mut self, params: &Params) {
         let palette = self.graphics.sixel_shared_palette.take();
         self.graphics.sixel_parser = Some(Box::new(sixel::Parser::new(params, palette)));
     }
 
+    #[cfg(feature = "ui")]
     #[inline]
     fn is_sixel_graphic_active(&self) -> bool {
         self.graphics.sixel_parser.is_some()
     }
 
+    #[cfg(feature = "ui")]
     #[inline]
     fn sixel_graphic_put(&mut self, byte: u8) -> Result<(), sixel::Error> {
         if let Some(parser) = &mut self.graphics.sixel_parser {
@@ -2639,11 +2704,13 @@ impl<U: EventListener> Handler for Crosswords<U> {
         }
     }
 
+    #[cfg(feature = "ui")]
     #[inline]
     fn sixel_graphic_reset(&mut self) {
         self.graphics.sixel_parser = None;
     }
 
+    #[cfg(feature = "ui")]
     #[inline]
     fn sixel_graphic_finish(&mut self) {
         let parser = self.graphics.sixel_parser.take();
@@ -2657,10 +2724,11 @@ impl<U: EventListener> Handler for Crosswords<U> {
         }
     }
 
+    #[cfg(feature = "ui")]
     #[inline]
     fn insert_graphic(&mut self, graphic: GraphicData, palette: Option<Vec<ColorRgb>>) {
-        let cell_width = self.graphics.cell_width as usize;
-        let cell_height = self.graphics.cell_height as usize;
+        let cell_width = self.square_width as usize;
+        let cell_height = self.square_height as usize;
 
         // Store last palette if we receive a new one, and it is shared.
         if let Some(palette) = palette {
@@ -2930,11 +2998,11 @@ impl<T: EventListener> Dimensions for Crosswords<T> {
     }
 
     fn square_width(&self) -> f32 {
-        self.graphics.cell_width
+        self.square_width
     }
 
     fn square_height(&self) -> f32 {
-        self.graphics.cell_height
+        self.square_height
     }
 }
 
@@ -3143,6 +3211,36 @@ mod tests {
         assert_eq!(term.selection_to_string(), Some(String::from("\"aa\"a\n")));
     }
 
+    #[test]
+    fn rows_are_stamped_when_written() {
+        let size = CrosswordsSize::new(5, 3);
+        let window_id = crate::event::WindowId::from(0);
+        let mut term =
+            Crosswords::new(size, CursorShape::Block, VoidListener {}, window_id, 0);
+
+        term.input('a');
+        assert!(term.grid[Line(0)].timestamp().is_some());
+        assert_eq!(term.grid[Line(1)].timestamp(), None);
+
+        term.grid[Line(0)].timestamp = std::num::NonZeroU32::new(1_700_000_000);
+        term.input('b');
+        assert_eq!(term.grid[Line(0)].timestamp(), Some(1_700_000_000));
+
+        term.selection = Some(Selection::new(
+            SelectionType::Lines,
+            Pos {
+                row: Line(0),
+                col: Column(0),
+            },
+            Side::Left,
+        ));
+        assert_eq!(term.selection_to_string(), Some(String::from("ab\n")));
+        assert_eq!(
+            term.selection_to_string_with_timestamps(),
+            Some(format!("{}ab\n", timestamp::gutter(Some(1_700_000_000))))
+        );
+    }
+
     #[test]
     fn block_selection_works() {
         let size = CrosswordsSize::new(5, 5);
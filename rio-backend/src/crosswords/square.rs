@@ -1,6 +1,7 @@
 // square.rs was originally taken from Alacritty as cell.rs https://github.com/alacritty/alacritty/blob/e35e5ad14fce8456afdd89f2b392b9924bb27471/alacritty_terminal/src/term/cell.rs
 // which is licensed under Apache 2.0 license.
 
+#[cfg(feature = "ui")]
 use crate::ansi::graphics::GraphicsCell;
 use crate::config::colors::{AnsiColor, NamedColor};
 use crate::crosswords::grid::GridSquare;
@@ -101,6 +102,7 @@ pub struct CellExtra {
 
     hyperlink: Option<Hyperlink>,
 
+    #[cfg(feature = "ui")]
     graphics: Option<GraphicsCell>,
 }
 
@@ -141,6 +143,7 @@ impl Square {
     }
 
     /// Graphic present in the cell.
+    #[cfg(feature = "ui")]
     #[inline]
     pub fn graphics(&self) -> Option<&GraphicsCell> {
         self.extra
@@ -149,6 +152,7 @@ impl Square {
     }
 
     /// Extract the graphics value from the cell.
+    #[cfg(feature = "ui")]
     #[inline]
     pub fn take_graphics(&mut self) -> Option<GraphicsCell> {
         if let Some(extra) = &mut self.extra {
@@ -161,6 +165,7 @@ impl Square {
     }
 
     /// Write the graphic data in the cell.
+    #[cfg(feature = "ui")]
     #[inline]
     pub fn set_graphics(&mut self, graphics_cell: GraphicsCell) {
         let extra = self.extra.get_or_insert_with(Default::default);
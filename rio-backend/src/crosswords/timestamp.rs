@@ -0,0 +1,63 @@
+// Wall-clock time recorded on each row of the grid when it is first
+// written, shown by the timestamps gutter and optionally included when
+// copying or exporting.
+
+use std::num::NonZeroU32;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Columns taken by the gutter, `HH:MM:SS` followed by a space.
+pub const GUTTER_COLUMNS: usize = 9;
+
+/// Current time as stored by `Row`.
+#[inline]
+pub fn now() -> Option<NonZeroU32> {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    NonZeroU32::new(u32::try_from(seconds).ok()?)
+}
+
+/// Formats `timestamp` as `HH:MM:SS` in the local time zone.
+pub fn format(timestamp: u32) -> String {
+    let seconds = (timestamp as i64 + utc_offset(timestamp)).rem_euclid(86_400);
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds / 60) % 60,
+        seconds % 60
+    )
+}
+
+/// Text of the gutter for a row, blank when the row was never written.
+pub fn gutter(timestamp: Option<u32>) -> String {
+    match timestamp {
+        Some(timestamp) => format!("{} ", format(timestamp)),
+        None => " ".repeat(GUTTER_COLUMNS),
+    }
+}
+
+#[cfg(unix)]
+fn utc_offset(timestamp: u32) -> i64 {
+    let time = timestamp as libc::time_t;
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&time, &mut tm) }.is_null() {
+        return 0;
+    }
+
+    tm.tm_gmtoff as i64
+}
+
+#[cfg(not(unix))]
+fn utc_offset(_timestamp: u32) -> i64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gutter_width() {
+        assert_eq!(gutter(None).len(), GUTTER_COLUMNS);
+        assert_eq!(gutter(Some(1_700_000_000)).len(), GUTTER_COLUMNS);
+        assert!(now().is_some());
+    }
+}
@@ -0,0 +1,403 @@
+// Regex triggers on the output of the program.
+//
+// Lines are matched once they are complete (on line feed), and the line
+// holding the cursor is matched again after every PTY read so prompts
+// that don't end with a new line are caught as well. Nothing runs on
+// render. Actions of matches already fired on the cursor line are not
+// fired again when the line is matched later.
+
+use crate::config::colors::{AnsiColor, ColorBuilder, ColorRgb, Format};
+use crate::config::triggers::{Trigger, TriggerAction};
+use crate::crosswords::grid::Dimensions;
+use crate::crosswords::pos::{Column, Line, Pos};
+use crate::crosswords::square::Flags;
+use crate::crosswords::{Crosswords, Mode};
+use crate::event::{EventListener, RioEvent};
+use regex::{Captures, Regex};
+use tracing::warn;
+
+enum Rule {
+    Highlight(Option<AnsiColor>, Option<AnsiColor>),
+    Action(TriggerAction),
+}
+
+#[derive(Default)]
+pub struct Triggers {
+    rules: Vec<(Regex, Rule)>,
+    // Matches of the cursor line already fired, as (rule, start offset).
+    fired: Vec<(usize, usize)>,
+    fired_line: Line,
+}
+
+impl std::fmt::Debug for Triggers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Triggers({})", self.rules.len())
+    }
+}
+
+impl Triggers {
+    pub fn new(triggers: &[Trigger]) -> Triggers {
+        let mut rules = Vec::with_capacity(triggers.len());
+        for trigger in triggers {
+            let regex = match Regex::new(&trigger.regex) {
+                Ok(regex) => regex,
+                Err(err) => {
+                    warn!("invalid trigger regex {:?}: {err}", trigger.regex);
+                    continue;
+                }
+            };
+
+            let rule = match &trigger.action {
+                TriggerAction::Highlight {
+                    foreground,
+                    background,
+                } => Rule::Highlight(
+                    foreground.as_deref().and_then(parse_color),
+                    background.as_deref().and_then(parse_color),
+                ),
+                action => Rule::Action(action.clone()),
+            };
+
+            rules.push((regex, rule));
+        }
+
+        Triggers {
+            rules,
+            ..Triggers::default()
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+fn parse_color(hex: &str) -> Option<AnsiColor> {
+    match ColorBuilder::from_hex(hex.to_string(), Format::SRGB0_1) {
+        Ok(color) => Some(AnsiColor::Spec(ColorRgb::from_color_arr(color.to_arr()))),
+        Err(err) => {
+            warn!("invalid trigger color {hex:?}: {err}");
+            None
+        }
+    }
+}
+
+fn expand(captures: &Captures, template: &str) -> String {
+    let mut expanded = String::new();
+    captures.expand(template, &mut expanded);
+    expanded
+}
+
+// Copy of the action with the capture groups replaced. The program of
+// `Run` is kept as written, so the output can't pick what gets executed.
+fn expand_action(action: &TriggerAction, captures: &Captures) -> TriggerAction {
+    match action {
+        TriggerAction::Notify { title, message } => TriggerAction::Notify {
+            title: title.as_deref().map(|title| expand(captures, title)),
+            message: Some(match message {
+                Some(message) => expand(captures, message),
+                None => captures[0].to_string(),
+            }),
+        },
+        TriggerAction::SendText { text } => TriggerAction::SendText {
+            text: expand(captures, text),
+        },
+        TriggerAction::Run { program, args } => TriggerAction::Run {
+            program: program.clone(),
+            args: args.iter().map(|arg| expand(captures, arg)).collect(),
+        },
+        action => action.clone(),
+    }
+}
+
+impl<U: EventListener> Crosswords<U> {
+    pub fn set_triggers(&mut self, triggers: &[Trigger]) {
+        self.triggers = Triggers::new(triggers);
+    }
+
+    /// Matches the line left by a line feed, unless it continues on the
+    /// next row.
+    pub(crate) fn triggers_on_linefeed(&mut self) {
+        if self.triggers.is_empty() || self.mode.contains(Mode::ALT_SCREEN) {
+            return;
+        }
+
+        let row = self.grid.cursor.pos.row;
+        let last_column = self.grid.last_column();
+        if self.grid[row][last_column].flags.contains(Flags::WRAPLINE) {
+            return;
+        }
+
+        self.run_triggers(row);
+        self.triggers.fired.clear();
+    }
+
+    /// Matches the line holding the cursor, called after the program output
+    /// is processed.
+    pub fn triggers_on_cursor_line(&mut self) {
+        if self.triggers.is_empty() || self.mode.contains(Mode::ALT_SCREEN) {
+            return;
+        }
+
+        self.run_triggers(self.grid.cursor.pos.row);
+    }
+
+    // Runs the triggers over the logical line ending at `end`.
+    fn run_triggers(&mut self, end: Line) {
+        let last_column = self.grid.last_column();
+        let topmost_line = self.grid.topmost_line();
+        let mut start = end;
+        while start > topmost_line
+            && self.grid[Line(start.0 - 1)][last_column]
+                .flags
+                .contains(Flags::WRAPLINE)
+        {
+            start = Line(start.0 - 1);
+        }
+
+        // Cursor moved to another line since the last check
+        if self.triggers.fired_line < start || self.triggers.fired_line > end {
+            self.triggers.fired.clear();
+        }
+        self.triggers.fired_line = end;
+
+        // Text of the line and the position of the square of each char
+        let mut text = String::new();
+        let mut offsets: Vec<(usize, Pos)> = Vec::new();
+        for line in start.0..=end.0 {
+            let row = &self.grid[Line(line)];
+            for column in 0..self.grid.columns() {
+                let square = &row[Column(column)];
+                if square
+                    .flags
+                    .intersects(Flags::WIDE_CHAR_SPACER | Flags::LEADING_WIDE_CHAR_SPACER)
+                {
+                    continue;
+                }
+
+                offsets.push((text.len(), Pos::new(Line(line), Column(column))));
+                text.push(square.c);
+                if let Some(zerowidth) = square.zerowidth() {
+                    text.extend(zerowidth);
+                }
+            }
+        }
+        text.truncate(text.trim_end_matches(' ').len());
+        if text.is_empty() {
+            return;
+        }
+
+        let position = |offset: usize| {
+            let index = offsets.partition_point(|(start, _)| *start <= offset);
+            offsets[index.saturating_sub(1)].1
+        };
+
+        let mut highlights = Vec::new();
+        let mut events = Vec::new();
+        for (index, (regex, rule)) in self.triggers.rules.iter().enumerate() {
+            for captures in regex.captures_iter(&text) {
+                let range = captures.get(0).map_or(0..0, |m| m.range());
+                if range.is_empty() {
+                    continue;
+                }
+
+                match rule {
+                    Rule::Highlight(foreground, background) => {
+                        highlights.push((
+                            position(range.start),
+                            position(range.end - 1),
+                            *foreground,
+                            *background,
+                        ));
+                    }
+                    Rule::Action(action) => {
+                        if self.triggers.fired.contains(&(index, range.start)) {
+                            continue;
+                        }
+
+                        self.triggers.fired.push((index, range.start));
+                        events.push(expand_action(action, &captures));
+                    }
+                }
+            }
+        }
+
+        for (start, end, foreground, background) in highlights {
+            let mut pos = start;
+            while pos <= end {
+                let square = &mut self.grid[pos.row][pos.col];
+                if let Some(foreground) = foreground {
+                    square.fg = foreground;
+                }
+                if let Some(background) = background {
+                    square.bg = background;
+                }
+
+                if pos.col >= last_column {
+                    if pos.row.0 >= 0 {
+                        self.mark_line_damaged(pos.row);
+                    }
+                    pos = Pos::new(Line(pos.row.0 + 1), Column(0));
+                } else {
+                    pos.col += 1;
+                }
+            }
+
+            if end.row.0 >= 0 {
+                self.mark_line_damaged(end.row);
+            }
+        }
+
+        for action in events {
+            let event = match action {
+                TriggerAction::Bell => RioEvent::BellOnRoute(self.route_id),
+                action => RioEvent::Trigger(self.route_id, action),
+            };
+            self.event_proxy.send_event(event, self.window_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ansi::CursorShape;
+    use crate::crosswords::CrosswordsSize;
+    use crate::event::WindowId;
+    use crate::performer::handler::ParserProcessor;
+    use std::sync::mpsc;
+
+    #[derive(Clone)]
+    struct Listener(mpsc::Sender<RioEvent>);
+
+    impl EventListener for Listener {
+        fn event(&self) -> (Option<RioEvent>, bool) {
+            (None, false)
+        }
+
+        fn send_event(&self, event: RioEvent, _id: WindowId) {
+            let _ = self.0.send(event);
+        }
+    }
+
+    fn terminal(
+        triggers: &[Trigger],
+    ) -> (Crosswords<Listener>, mpsc::Receiver<RioEvent>) {
+        let (sender, receiver) = mpsc::channel();
+        let mut terminal = Crosswords::new(
+            CrosswordsSize::new(10, 4),
+            CursorShape::Block,
+            Listener(sender),
+            WindowId::from(0),
+            7,
+        );
+        terminal.set_triggers(triggers);
+        (terminal, receiver)
+    }
+
+    fn write(terminal: &mut Crosswords<Listener>, text: &str) {
+        let mut parser = ParserProcessor::new();
+        for byte in text.as_bytes() {
+            parser.advance(terminal, *byte);
+        }
+        terminal.triggers_on_cursor_line();
+    }
+
+    fn fired(receiver: &mpsc::Receiver<RioEvent>) -> Vec<TriggerAction> {
+        receiver
+            .try_iter()
+            .filter_map(|event| match event {
+                RioEvent::Trigger(7, action) => Some(action),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_trigger_action_on_wrapped_line() {
+        let (mut terminal, receiver) = terminal(&[Trigger {
+            regex: String::from(r"build (\w+) ok"),
+            action: TriggerAction::Run {
+                program: String::from("notify"),
+                args: vec![String::from("$1"), String::from("$0")],
+            },
+        }]);
+
+        // Wraps into the second row before the line feed
+        write(&mut terminal, "$ build rio ok\r\n");
+        assert_eq!(
+            fired(&receiver),
+            vec![TriggerAction::Run {
+                program: String::from("notify"),
+                args: vec![String::from("rio"), String::from("build rio ok")],
+            }]
+        );
+
+        // Another line with the same content fires again
+        write(&mut terminal, "$ build rio ok\r\n");
+        assert_eq!(fired(&receiver).len(), 1);
+    }
+
+    #[test]
+    fn test_trigger_run_keeps_program() {
+        let (mut terminal, receiver) = terminal(&[Trigger {
+            regex: String::from(r"run (\S+)"),
+            action: TriggerAction::Run {
+                program: String::from("$1"),
+                args: vec![String::from("$1")],
+            },
+        }]);
+
+        write(&mut terminal, "run rm\r\n");
+        assert_eq!(
+            fired(&receiver),
+            vec![TriggerAction::Run {
+                program: String::from("$1"),
+                args: vec![String::from("rm")],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_trigger_on_cursor_line_fires_once() {
+        let (mut terminal, receiver) = terminal(&[Trigger {
+            regex: String::from("Password:"),
+            action: TriggerAction::SendText {
+                text: String::from("secret\r"),
+            },
+        }]);
+
+        write(&mut terminal, "Password:");
+        assert_eq!(
+            fired(&receiver),
+            vec![TriggerAction::SendText {
+                text: String::from("secret\r"),
+            }]
+        );
+
+        // Same prompt matched again after more output and the line feed
+        write(&mut terminal, " ");
+        write(&mut terminal, "\r\n");
+        assert!(fired(&receiver).is_empty());
+    }
+
+    #[test]
+    fn test_trigger_highlight() {
+        let (mut terminal, receiver) = terminal(&[Trigger {
+            regex: String::from("err"),
+            action: TriggerAction::Highlight {
+                foreground: Some(String::from("#ff0000")),
+                background: None,
+            },
+        }]);
+
+        write(&mut terminal, "an error\r\n");
+        let red = AnsiColor::Spec(ColorRgb { r: 255, g: 0, b: 0 });
+        assert_ne!(terminal.grid[Line(0)][Column(2)].fg, red);
+        assert_eq!(terminal.grid[Line(0)][Column(3)].fg, red);
+        assert_eq!(terminal.grid[Line(0)][Column(5)].fg, red);
+        assert_ne!(terminal.grid[Line(0)][Column(6)].fg, red);
+        assert!(fired(&receiver).is_empty());
+    }
+}
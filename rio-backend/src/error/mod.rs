@@ -1,4 +1,6 @@
+use crate::config::diagnostics::Diagnostic;
 use crate::config::ConfigError;
+#[cfg(feature = "ui")]
 use crate::sugarloaf::font::SugarloafFont;
 
 #[derive(Clone, Copy, PartialEq)]
@@ -20,13 +22,50 @@ impl RioError {
             report: RioErrorType::ConfigurationNotFound,
         }
     }
+
+    /// Warnings of a config that loaded and is in use.
+    pub fn configuration_warnings(diagnostics: Vec<Diagnostic>) -> Self {
+        RioError {
+            level: RioErrorLevel::Warning,
+            report: RioErrorType::ConfigurationDiagnostics(diagnostics, None),
+        }
+    }
 }
 
-impl From<ConfigError> for RioError {
-    fn from(error: ConfigError) -> Self {
+/// Config Rio proceeds with when the config file has errors.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConfigFallback {
+    /// On startup, when there isn't a previous config.
+    Default,
+    /// On reload.
+    LastValid,
+}
+
+impl ConfigFallback {
+    pub fn message(&self) -> &'static str {
+        match self {
+            ConfigFallback::Default => "Rio will proceed with the default configuration",
+            ConfigFallback::LastValid => {
+                "Rio will proceed with the last valid configuration"
+            }
+        }
+    }
+}
+
+impl RioError {
+    /// Error of a config that didn't load, `fallback` is the config used
+    /// instead.
+    pub fn configuration_error(error: ConfigError, fallback: ConfigFallback) -> Self {
         match error {
             ConfigError::ErrLoadingConfig(message) => RioError {
-                report: RioErrorType::InvalidConfigurationFormat(message),
+                report: RioErrorType::InvalidConfigurationFormat(message, fallback),
+                level: RioErrorLevel::Warning,
+            },
+            ConfigError::Invalid(diagnostics, _) => RioError {
+                report: RioErrorType::ConfigurationDiagnostics(
+                    diagnostics,
+                    Some(fallback),
+                ),
                 level: RioErrorLevel::Warning,
             },
             ConfigError::ErrLoadingTheme(message) => RioError {
@@ -41,9 +80,18 @@ impl From<ConfigError> for RioError {
     }
 }
 
+/// Errors of the config loaded on startup, which proceeds with the default
+/// config.
+impl From<ConfigError> for RioError {
+    fn from(error: ConfigError) -> Self {
+        RioError::configuration_error(error, ConfigFallback::Default)
+    }
+}
+
 #[derive(Clone, PartialEq)]
 pub enum RioErrorType {
     // font was not found
+    #[cfg(feature = "ui")]
     FontsNotFound(Vec<SugarloafFont>),
 
     // navigation configuration has changed
@@ -53,9 +101,12 @@ pub enum RioErrorType {
     // configurlation file was not found
     ConfigurationNotFound,
     // configuration file have an invalid format
-    InvalidConfigurationFormat(String),
+    InvalidConfigurationFormat(String, ConfigFallback),
     // configuration invalid theme
     InvalidConfigurationTheme(String),
+    // errors and warnings of the configuration file, with the config used
+    // instead when it didn't load
+    ConfigurationDiagnostics(Vec<Diagnostic>, Option<ConfigFallback>),
 
     // reports that are ignored by RioErrorType
     IgnoredReport,
@@ -64,6 +115,7 @@ pub enum RioErrorType {
 impl std::fmt::Display for RioErrorType {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
+            #[cfg(feature = "ui")]
             RioErrorType::FontsNotFound(fonts) => {
                 let mut font_str = String::from("");
                 for font in fonts.iter() {
@@ -92,12 +144,22 @@ impl std::fmt::Display for RioErrorType {
                 write!(f, "Error initializing Rio terminal:\n{message}")
             }
             RioErrorType::IgnoredReport => write!(f, ""),
-            RioErrorType::InvalidConfigurationFormat(message) => {
-                write!(f, "Found an issue loading the configuration file:\n\n{message}\n\nRio will proceed with the default configuration\nhttps://raphamorim.io/rio/docs/config")
+            RioErrorType::InvalidConfigurationFormat(message, fallback) => {
+                write!(f, "Found an issue loading the configuration file:\n\n{message}\n\n{}\nhttps://raphamorim.io/rio/docs/config", fallback.message())
             }
             RioErrorType::InvalidConfigurationTheme(message) => {
                 write!(f, "Found an issue in the configured theme:\n\n{message}")
             }
+            RioErrorType::ConfigurationDiagnostics(diagnostics, fallback) => {
+                writeln!(f, "Found issues in the configuration file:")?;
+                for diagnostic in diagnostics {
+                    write!(f, "\n• {diagnostic}")?;
+                }
+                match fallback {
+                    Some(fallback) => write!(f, "\n\n{}", fallback.message()),
+                    None => write!(f, "\n\nRio will proceed with the configuration file"),
+                }
+            }
         }
     }
 }
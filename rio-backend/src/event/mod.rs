@@ -2,22 +2,49 @@ pub mod sync;
 
 use crate::clipboard::ClipboardType;
 use crate::config::colors::ColorRgb;
+use crate::config::overrides::ConfigOverrides;
+use crate::config::triggers::TriggerAction;
+use crate::crosswords::export::ExportFormat;
 use crate::crosswords::grid::Scroll;
 use crate::crosswords::pos::{Direction, Pos};
 use crate::crosswords::search::{Match, RegexSearch};
 use crate::error::RioError;
+#[cfg(feature = "ui")]
 use rio_window::event::Event as RioWindowEvent;
 use std::borrow::Cow;
 use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::path::PathBuf;
 use std::sync::Arc;
 use teletypewriter::WinsizeBuilder;
 
+#[cfg(feature = "ui")]
 use rio_window::event_loop::EventLoopProxy;
 
+#[cfg(feature = "ui")]
 pub type WindowId = rio_window::window::WindowId;
 
+/// Identifier of a window, used when rio-backend is built without a window
+/// system, like in headless mode.
+#[cfg(not(feature = "ui"))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WindowId(u64);
+
+#[cfg(not(feature = "ui"))]
+impl From<WindowId> for u64 {
+    fn from(window_id: WindowId) -> Self {
+        window_id.0
+    }
+}
+
+#[cfg(not(feature = "ui"))]
+impl From<u64> for WindowId {
+    fn from(raw_id: u64) -> Self {
+        Self(raw_id)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum RioEventType {
     Rio(RioEvent),
@@ -34,6 +61,11 @@ pub enum Msg {
     Shutdown,
 
     Resize(WinsizeBuilder),
+
+    /// Start tee'ing the PTY output (and resizes) into a session recording.
+    StartRecording(crate::performer::recorder::Recorder),
+
+    StopRecording,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -53,6 +85,11 @@ pub enum RioEvent {
     Render,
     /// New terminal content available per route.
     RenderRoute(usize),
+    /// Route read new output from its PTY, only sent by the performers
+    /// created with `Machine::with_output_events`.
+    Output(usize),
+    /// Route didn't write any output for the silence monitor interval.
+    MonitorSilence(usize),
     Paste,
     Copy(String),
     UpdateFontSize(u8),
@@ -63,6 +100,12 @@ pub enum RioEvent {
     HideOtherApplications,
     UpdateConfig,
     CreateWindow,
+
+    /// Open a window with its own config overrides (from IPC).
+    CreateWindowWithOverrides(ConfigOverrides),
+
+    /// Open the command palette.
+    CommandPalette,
     CloseWindow,
     CreateNativeTab(Option<String>),
     CreateConfigEditor,
@@ -73,6 +116,14 @@ pub enum RioEvent {
 
     ReportToAssistant(RioError),
 
+    /// Save the content of the focused split to a file, with the format
+    /// and whether the scrollback is included.
+    SaveScreen(PathBuf, Option<ExportFormat>, bool),
+
+    /// Trigger matched the output of a route, the action has the capture
+    /// groups already expanded.
+    Trigger(usize, TriggerAction),
+
     /// Grid has changed possibly requiring a mouse cursor shape change.
     MouseCursorDirty,
 
@@ -120,6 +171,9 @@ pub enum RioEvent {
     /// Terminal bell ring.
     Bell,
 
+    /// Bell rang by the program running on the route.
+    BellOnRoute(usize),
+
     /// Shutdown request.
     Exit,
 
@@ -167,12 +221,19 @@ impl Debug for RioEvent {
             }
             RioEvent::Render => write!(f, "Render"),
             RioEvent::RenderRoute(route) => write!(f, "Render route {route}"),
+            RioEvent::Output(route) => write!(f, "Output {route}"),
+            RioEvent::MonitorSilence(route) => write!(f, "MonitorSilence {route}"),
             RioEvent::Scroll(scroll) => write!(f, "Scroll {scroll:?}"),
             RioEvent::Bell => write!(f, "Bell"),
+            RioEvent::BellOnRoute(route) => write!(f, "BellOnRoute {route}"),
             RioEvent::Exit => write!(f, "Exit"),
             RioEvent::Quit => write!(f, "Quit"),
             RioEvent::CloseTerminal(route) => write!(f, "CloseTerminal {route}"),
             RioEvent::CreateWindow => write!(f, "CreateWindow"),
+            RioEvent::CreateWindowWithOverrides(overrides) => {
+                write!(f, "CreateWindowWithOverrides({overrides:?})")
+            }
+            RioEvent::CommandPalette => write!(f, "CommandPalette"),
             RioEvent::CloseWindow => write!(f, "CloseWindow"),
             RioEvent::CreateNativeTab(_) => write!(f, "CreateNativeTab"),
             RioEvent::SelectNativeTabByIndex(tab_index) => {
@@ -186,6 +247,12 @@ impl Debug for RioEvent {
             RioEvent::ReportToAssistant(error_report) => {
                 write!(f, "ReportToAssistant({})", error_report.report)
             }
+            RioEvent::SaveScreen(path, format, scrollback) => {
+                write!(f, "SaveScreen({path:?}, {format:?}, {scrollback})")
+            }
+            RioEvent::Trigger(route_id, action) => {
+                write!(f, "Trigger({route_id}, {action:?})")
+            }
             RioEvent::ToggleFullScreen => write!(f, "FullScreen"),
             RioEvent::BlinkCursor(timeout, route_id) => {
                 write!(f, "BlinkCursor {timeout} {route_id}")
@@ -212,6 +279,7 @@ impl EventPayload {
     }
 }
 
+#[cfg(feature = "ui")]
 impl From<EventPayload> for RioWindowEvent<EventPayload> {
     fn from(event: EventPayload) -> Self {
         RioWindowEvent::UserEvent(event)
@@ -250,11 +318,13 @@ impl EventListener for VoidListener {
     }
 }
 
+#[cfg(feature = "ui")]
 #[derive(Debug, Clone)]
 pub struct EventProxy {
     proxy: EventLoopProxy<EventPayload>,
 }
 
+#[cfg(feature = "ui")]
 impl EventProxy {
     pub fn new(proxy: EventLoopProxy<EventPayload>) -> Self {
         Self { proxy }
@@ -265,6 +335,7 @@ impl EventProxy {
     }
 }
 
+#[cfg(feature = "ui")]
 impl EventListener for EventProxy {
     fn event(&self) -> (std::option::Option<RioEvent>, bool) {
         (None, false)
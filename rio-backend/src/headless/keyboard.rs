@@ -0,0 +1,200 @@
+// Keys are encoded by the same code as the frontend: the default key
+// sequences, the kitty keyboard protocol when the application enables it and
+// plain text otherwise. Only key presses are encoded.
+
+use crate::crosswords::Mode;
+use crate::keyboard::{
+    default_sequence, Key as KeyboardKey, KeyInput, KeyLocation, KeyState, NamedKey,
+};
+
+pub use crate::keyboard::KeyModifiers;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Tab,
+    Backspace,
+    Escape,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
+    /// Function keys from F1 to F20.
+    F(u8),
+}
+
+/// Bytes written to the PTY when `key` is pressed with `mods` in `mode`.
+pub fn encode_key(key: Key, mods: KeyModifiers, mode: Mode) -> Vec<u8> {
+    let (logical_key, key_without_modifiers) = keyboard_keys(key, mods);
+    if let Some(sequence) = default_sequence(&logical_key, mods, mode) {
+        return sequence.as_bytes().to_vec();
+    }
+
+    let text = key_text(key, mods);
+    let input = KeyInput {
+        logical_key,
+        key_without_modifiers,
+        location: KeyLocation::Standard,
+        state: KeyState::Pressed,
+        repeat: false,
+        text: Some(&text),
+    };
+
+    crate::keyboard::encode_key(&input, mods, mode)
+}
+
+// Logical key and key without modifiers, like the ones of a key event of
+// the window.
+fn keyboard_keys(key: Key, mods: KeyModifiers) -> (KeyboardKey, KeyboardKey) {
+    let named = match key {
+        Key::Char(' ') => NamedKey::Space,
+        Key::Char(character) => {
+            let character = character.to_lowercase().collect::<String>();
+            let logical_key = if mods.contains(KeyModifiers::SHIFT) {
+                character.to_uppercase()
+            } else {
+                character.clone()
+            };
+
+            return (
+                KeyboardKey::Character(logical_key),
+                KeyboardKey::Character(character),
+            );
+        }
+        Key::Enter => NamedKey::Enter,
+        Key::Tab => NamedKey::Tab,
+        Key::Backspace => NamedKey::Backspace,
+        Key::Escape => NamedKey::Escape,
+        Key::Up => NamedKey::ArrowUp,
+        Key::Down => NamedKey::ArrowDown,
+        Key::Left => NamedKey::ArrowLeft,
+        Key::Right => NamedKey::ArrowRight,
+        Key::Home => NamedKey::Home,
+        Key::End => NamedKey::End,
+        Key::PageUp => NamedKey::PageUp,
+        Key::PageDown => NamedKey::PageDown,
+        Key::Insert => NamedKey::Insert,
+        Key::Delete => NamedKey::Delete,
+        Key::F(number) => match number {
+            1 => NamedKey::F1,
+            2 => NamedKey::F2,
+            3 => NamedKey::F3,
+            4 => NamedKey::F4,
+            5 => NamedKey::F5,
+            6 => NamedKey::F6,
+            7 => NamedKey::F7,
+            8 => NamedKey::F8,
+            9 => NamedKey::F9,
+            10 => NamedKey::F10,
+            11 => NamedKey::F11,
+            12 => NamedKey::F12,
+            13 => NamedKey::F13,
+            14 => NamedKey::F14,
+            15 => NamedKey::F15,
+            16 => NamedKey::F16,
+            17 => NamedKey::F17,
+            18 => NamedKey::F18,
+            19 => NamedKey::F19,
+            20 => NamedKey::F20,
+            _ => return (KeyboardKey::Unidentified, KeyboardKey::Unidentified),
+        },
+    };
+
+    (KeyboardKey::Named(named), KeyboardKey::Named(named))
+}
+
+fn key_text(key: Key, mods: KeyModifiers) -> String {
+    match key {
+        Key::Char(character) if mods.contains(KeyModifiers::CONTROL) => {
+            match control_character(character) {
+                Some(control) => control.to_string(),
+                None => character.to_string(),
+            }
+        }
+        Key::Char(character) if mods.contains(KeyModifiers::SHIFT) => {
+            character.to_uppercase().collect()
+        }
+        Key::Char(character) => character.to_string(),
+        Key::Enter => String::from("\r"),
+        Key::Tab => String::from("\t"),
+        Key::Backspace => String::from("\x7f"),
+        Key::Escape => String::from("\x1b"),
+        _ => String::new(),
+    }
+}
+
+fn control_character(character: char) -> Option<char> {
+    let code = match character.to_ascii_lowercase() {
+        character @ 'a'..='z' => character as u8 - b'a' + 1,
+        '@' | ' ' | '2' => 0,
+        '[' | '3' => 27,
+        '\\' | '4' => 28,
+        ']' | '5' => 29,
+        '^' | '6' => 30,
+        '_' | '7' => 31,
+        '?' | '8' => 127,
+        _ => return None,
+    };
+
+    Some(code as char)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_key() {
+        let none = KeyModifiers::empty();
+        let mode = Mode::empty();
+
+        assert_eq!(encode_key(Key::Char('a'), none, mode), b"a");
+        assert_eq!(encode_key(Key::Char('a'), KeyModifiers::SHIFT, mode), b"A");
+        assert_eq!(
+            encode_key(Key::Char('c'), KeyModifiers::CONTROL, mode),
+            b"\x03"
+        );
+        assert_eq!(
+            encode_key(Key::Char('b'), KeyModifiers::ALT, mode),
+            b"\x1bb"
+        );
+        assert_eq!(encode_key(Key::Enter, none, mode), b"\r");
+        assert_eq!(encode_key(Key::Tab, KeyModifiers::SHIFT, mode), b"\x1b[Z");
+        assert_eq!(encode_key(Key::Up, none, mode), b"\x1b[A");
+        assert_eq!(encode_key(Key::Up, none, Mode::APP_CURSOR), b"\x1bOA");
+        assert_eq!(
+            encode_key(Key::Right, KeyModifiers::CONTROL, Mode::APP_CURSOR),
+            b"\x1b[1;5C"
+        );
+        assert_eq!(encode_key(Key::Delete, none, mode), b"\x1b[3~");
+        assert_eq!(encode_key(Key::F(1), none, mode), b"\x1bOP");
+        assert_eq!(encode_key(Key::F(5), none, mode), b"\x1b[15~");
+        assert_eq!(encode_key(Key::Home, none, mode), b"\x1b[H");
+    }
+
+    #[test]
+    fn test_encode_key_kitty() {
+        let none = KeyModifiers::empty();
+        let mode = Mode::DISAMBIGUATE_ESC_CODES;
+
+        assert_eq!(encode_key(Key::Char('a'), none, mode), b"a");
+        assert_eq!(encode_key(Key::Escape, none, mode), b"\x1b[27u");
+        assert_eq!(
+            encode_key(Key::Char('c'), KeyModifiers::CONTROL, mode),
+            b"\x1b[99;5u"
+        );
+        assert_eq!(encode_key(Key::Enter, none, mode), b"\r");
+        assert_eq!(
+            encode_key(Key::Enter, none, Mode::REPORT_ALL_KEYS_AS_ESC),
+            b"\x1b[13u"
+        );
+        assert_eq!(encode_key(Key::F(3), none, mode), b"\x1b[13~");
+    }
+}
@@ -0,0 +1,347 @@
+// Headless terminal for driving and inspecting TUI applications in tests.
+//
+// The command runs on a real PTY and its output goes through the same
+// performer and Crosswords used by the frontend, but nothing is rendered
+// and no window is created. Events that would reach the window are handled
+// here instead (answers to queries are written back to the PTY).
+
+pub mod keyboard;
+
+use crate::ansi::CursorShape;
+use crate::config::Shell;
+use crate::crosswords::grid::Dimensions;
+use crate::crosswords::pos::{Column, Line, Pos};
+use crate::crosswords::square::{Flags, Square};
+use crate::crosswords::{Crosswords, CrosswordsSize, Mode};
+use crate::event::sync::FairMutex;
+use crate::event::{EventListener, Msg, RioEvent, WindowId};
+use crate::performer::Machine;
+use corcovado::channel;
+use keyboard::{encode_key, Key, KeyModifiers};
+use std::borrow::Cow;
+use std::error::Error;
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+use teletypewriter::WinsizeBuilder;
+
+#[cfg(target_os = "windows")]
+use teletypewriter::create_pty;
+#[cfg(not(target_os = "windows"))]
+use teletypewriter::create_pty_with_spawn;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Clone)]
+pub struct HeadlessListener {
+    sender: mpsc::Sender<RioEvent>,
+}
+
+impl EventListener for HeadlessListener {
+    fn event(&self) -> (Option<RioEvent>, bool) {
+        (None, false)
+    }
+
+    fn send_event(&self, event: RioEvent, _id: WindowId) {
+        let _ = self.sender.send(event);
+    }
+}
+
+pub struct HeadlessTerminal {
+    terminal: Arc<FairMutex<Crosswords<HeadlessListener>>>,
+    channel: channel::Sender<Msg>,
+    events: mpsc::Receiver<RioEvent>,
+    exited: bool,
+    bells: usize,
+}
+
+impl HeadlessTerminal {
+    /// Spawns `shell` on a PTY with `columns` x `lines` cells.
+    pub fn spawn(
+        shell: &Shell,
+        working_dir: Option<String>,
+        columns: usize,
+        lines: usize,
+    ) -> Result<HeadlessTerminal, Box<dyn Error>> {
+        let (sender, events) = mpsc::channel();
+        let listener = HeadlessListener { sender };
+        let window_id = WindowId::from(0);
+
+        let terminal = Crosswords::new(
+            CrosswordsSize::new(columns, lines),
+            CursorShape::Block,
+            listener.clone(),
+            window_id,
+            0,
+        );
+        let terminal = Arc::new(FairMutex::new(terminal));
+
+        #[cfg(not(target_os = "windows"))]
+        let pty = create_pty_with_spawn(
+            &Cow::Borrowed(&shell.program),
+            shell.args.clone(),
+            &working_dir,
+            columns as u16,
+            lines as u16,
+        )?;
+        #[cfg(target_os = "windows")]
+        let pty = create_pty(
+            &Cow::Borrowed(&shell.program),
+            shell.args.clone(),
+            &working_dir,
+            columns as u16,
+            lines as u16,
+        )?;
+
+        let machine = Machine::new(Arc::clone(&terminal), pty, listener, window_id, 0)?;
+        let channel = machine.channel();
+        machine.spawn();
+
+        Ok(HeadlessTerminal {
+            terminal,
+            channel,
+            events,
+            exited: false,
+            bells: 0,
+        })
+    }
+
+    /// Writes raw bytes to the PTY.
+    pub fn write<B: Into<Cow<'static, [u8]>>>(&self, data: B) {
+        let _ = self.channel.send(Msg::Input(data.into()));
+    }
+
+    pub fn send_text(&self, text: &str) {
+        self.write(text.as_bytes().to_vec());
+    }
+
+    /// Sends a key press encoded for the modes currently enabled by the
+    /// application, the same way the frontend does.
+    pub fn send_key(&self, key: Key, mods: KeyModifiers) {
+        let mode = self.terminal.lock().mode();
+        self.write(encode_key(key, mods, mode));
+    }
+
+    pub fn resize(&self, columns: usize, lines: usize) {
+        self.terminal
+            .lock()
+            .resize(CrosswordsSize::new(columns, lines));
+        let _ = self.channel.send(Msg::Resize(WinsizeBuilder {
+            cols: columns as u16,
+            rows: lines as u16,
+            width: 0,
+            height: 0,
+        }));
+    }
+
+    /// Handles pending events from the terminal, answering queries.
+    pub fn process_events(&mut self) {
+        while let Ok(event) = self.events.try_recv() {
+            match event {
+                RioEvent::PtyWrite(text) => self.write(text.into_bytes()),
+                RioEvent::CloseTerminal(_) => self.exited = true,
+                RioEvent::Bell | RioEvent::BellOnRoute(_) => self.bells += 1,
+                _ => {}
+            }
+        }
+    }
+
+    /// Waits until `condition` holds or `timeout` elapses, returns
+    /// whether the condition was met.
+    pub fn wait_for<F>(&mut self, timeout: Duration, mut condition: F) -> bool
+    where
+        F: FnMut(&Crosswords<HeadlessListener>) -> bool,
+    {
+        let deadline = Instant::now() + timeout;
+        loop {
+            self.process_events();
+            if condition(&self.terminal.lock()) {
+                return true;
+            }
+
+            if Instant::now() >= deadline {
+                return false;
+            }
+
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Waits until `text` is visible on the screen.
+    pub fn wait_for_text(&mut self, text: &str, timeout: Duration) -> bool {
+        self.wait_for(timeout, |terminal| screen_text(terminal).contains(text))
+    }
+
+    /// Waits until the command exits.
+    pub fn wait_for_exit(&mut self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        loop {
+            self.process_events();
+            if self.exited {
+                return true;
+            }
+
+            if Instant::now() >= deadline {
+                return false;
+            }
+
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Visible lines with trailing spaces removed, separated by newlines.
+    pub fn screen_text(&self) -> String {
+        screen_text(&self.terminal.lock())
+    }
+
+    pub fn line_text(&self, line: usize) -> String {
+        line_text(&self.terminal.lock(), line)
+    }
+
+    /// Snapshot of the visible cells with their attributes.
+    pub fn cells(&self) -> Vec<Vec<Square>> {
+        let terminal = self.terminal.lock();
+        (0..terminal.grid.screen_lines())
+            .map(|line| {
+                (0..terminal.grid.columns())
+                    .map(|column| {
+                        terminal.grid[Line(line as i32)][Column(column)].clone()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn cursor(&self) -> Pos {
+        self.terminal.lock().grid.cursor.pos
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.terminal.lock().mode()
+    }
+
+    pub fn title(&self) -> String {
+        self.terminal.lock().title.clone()
+    }
+
+    /// Number of bells received so far.
+    pub fn bells(&self) -> usize {
+        self.bells
+    }
+
+    pub fn has_exited(&self) -> bool {
+        self.exited
+    }
+
+    /// Shared terminal state, for inspections not covered above.
+    pub fn terminal(&self) -> &Arc<FairMutex<Crosswords<HeadlessListener>>> {
+        &self.terminal
+    }
+}
+
+impl Drop for HeadlessTerminal {
+    fn drop(&mut self) {
+        // Dropping the PTY will also hang up the child process
+        let _ = self.channel.send(Msg::Shutdown);
+    }
+}
+
+fn line_text(terminal: &Crosswords<HeadlessListener>, line: usize) -> String {
+    let mut text = String::new();
+    if line >= terminal.grid.screen_lines() {
+        return text;
+    }
+
+    let row = &terminal.grid[Line(line as i32)];
+    for column in 0..terminal.grid.columns() {
+        let square = &row[Column(column)];
+        if square
+            .flags
+            .intersects(Flags::WIDE_CHAR_SPACER | Flags::LEADING_WIDE_CHAR_SPACER)
+        {
+            continue;
+        }
+
+        text.push(square.c);
+        if let Some(zerowidth) = square.zerowidth() {
+            text.extend(zerowidth);
+        }
+    }
+
+    text.truncate(text.trim_end_matches(' ').len());
+    text
+}
+
+fn screen_text(terminal: &Crosswords<HeadlessListener>) -> String {
+    (0..terminal.grid.screen_lines())
+        .map(|line| line_text(terminal, line))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(all(test, not(target_os = "windows")))]
+mod tests {
+    use super::*;
+    use crate::performer::recorder::Recorder;
+
+    #[test]
+    fn test_headless_terminal() {
+        let shell = Shell {
+            program: String::from("sh"),
+            args: vec![
+                String::from("-c"),
+                String::from("printf '\\033]2;headless\\007hello\\n\\007'; cat"),
+            ],
+        };
+
+        let mut terminal = HeadlessTerminal::spawn(&shell, None, 40, 10).unwrap();
+        assert!(terminal.wait_for_text("hello", Duration::from_secs(5)));
+        assert_eq!(terminal.line_text(0), "hello");
+        assert_eq!(terminal.title(), "headless");
+        assert_eq!(terminal.cursor().row, Line(1));
+
+        terminal.send_text("rio");
+        terminal.send_key(Key::Enter, KeyModifiers::empty());
+        // Typed line echoed by the PTY and then written back by cat
+        assert!(terminal.wait_for(Duration::from_secs(5), |terminal| {
+            line_text(terminal, 2) == "rio"
+        }));
+        assert_eq!(terminal.line_text(1), "rio");
+        assert_eq!(terminal.bells(), 1);
+
+        let cells = terminal.cells();
+        assert_eq!(cells.len(), 10);
+        assert_eq!(cells[0].len(), 40);
+        assert_eq!(cells[1][0].c, 'r');
+
+        terminal.send_key(Key::Char('d'), KeyModifiers::CONTROL);
+        assert!(terminal.wait_for_exit(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_recording_is_readable_while_running() {
+        let shell = Shell {
+            program: String::from("cat"),
+            args: vec![],
+        };
+        let path = std::env::temp_dir().join("rio-headless-recording.cast");
+        let recorder = Recorder::create(&path, 40, 10, false).unwrap();
+
+        let mut terminal = HeadlessTerminal::spawn(&shell, None, 40, 10).unwrap();
+        let _ = terminal.channel.send(Msg::StartRecording(recorder));
+        terminal.send_text("recorded\r");
+        assert!(terminal.wait_for_text("recorded", Duration::from_secs(5)));
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut content = String::new();
+        while !content.contains("recorded") && Instant::now() < deadline {
+            std::thread::sleep(POLL_INTERVAL);
+            content = std::fs::read_to_string(&path).unwrap();
+        }
+        assert!(!terminal.has_exited());
+        assert!(content.starts_with("{\"version\":2,\"width\":40,\"height\":10,"));
+        assert!(content.contains(",\"o\",\"recorded"));
+
+        terminal.send_key(Key::Char('d'), KeyModifiers::CONTROL);
+        assert!(terminal.wait_for_exit(Duration::from_secs(5)));
+    }
+}
@@ -1,17 +1,311 @@
+// Encoding of key presses into the bytes written to the PTY, shared by the
+// frontend and the headless terminal. The keys are described with the types
+// below, so it doesn't depend on a window system: the frontend converts its
+// key events into them.
+//
 // build_key_sequence was originally taken from alacritty
 // which is licensed under Apache 2.0 license.
 
-use rio_backend::crosswords::Mode;
-use rio_window::event::{ElementState, KeyEvent};
-use rio_window::keyboard::Key;
-use rio_window::keyboard::KeyLocation;
-use rio_window::keyboard::ModifiersState;
-use rio_window::keyboard::NamedKey;
-use rio_window::platform::modifier_supplement::KeyEventExtModifierSupplement;
+use crate::crosswords::Mode;
 use std::borrow::Cow;
 
+bitflags::bitflags! {
+    /// Modifiers held while a key is pressed.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct KeyModifiers: u8 {
+        const SHIFT   = 0b0001;
+        const CONTROL = 0b0010;
+        const ALT     = 0b0100;
+        const SUPER   = 0b1000;
+    }
+}
+
+/// Key as the keyboard layout reports it, without its location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Key<Str = String> {
+    Named(NamedKey),
+    /// Text of the key, like `a` or `!`.
+    Character(Str),
+    /// Key the terminal doesn't encode, only its text (if any) is sent.
+    Unidentified,
+}
+
+impl Key {
+    /// Key borrowing its text, to match it against string literals.
+    pub fn as_ref(&self) -> Key<&str> {
+        match self {
+            Key::Named(named) => Key::Named(*named),
+            Key::Character(character) => Key::Character(character.as_str()),
+            Key::Unidentified => Key::Unidentified,
+        }
+    }
+}
+
+/// Keys without text the terminal encodes, named like the ones of the
+/// window system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NamedKey {
+    Enter,
+    Tab,
+    Space,
+    Backspace,
+    Escape,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    End,
+    Home,
+    PageDown,
+    PageUp,
+    Insert,
+    Delete,
+    Alt,
+    CapsLock,
+    Control,
+    Hyper,
+    Meta,
+    NumLock,
+    ScrollLock,
+    Shift,
+    Super,
+    ContextMenu,
+    Pause,
+    PrintScreen,
+    MediaFastForward,
+    MediaPause,
+    MediaPlay,
+    MediaPlayPause,
+    MediaRecord,
+    MediaRewind,
+    MediaStop,
+    MediaTrackNext,
+    MediaTrackPrevious,
+    AudioVolumeDown,
+    AudioVolumeUp,
+    AudioVolumeMute,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+    F25,
+    F26,
+    F27,
+    F28,
+    F29,
+    F30,
+    F31,
+    F32,
+    F33,
+    F34,
+    F35,
+}
+
+impl NamedKey {
+    /// Text of the keys that have one.
+    pub fn to_text(self) -> Option<&'static str> {
+        match self {
+            NamedKey::Enter => Some("\r"),
+            NamedKey::Backspace => Some("\x08"),
+            NamedKey::Tab => Some("\t"),
+            NamedKey::Space => Some(" "),
+            NamedKey::Escape => Some("\x1b"),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyLocation {
+    Standard,
+    Left,
+    Right,
+    Numpad,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyState {
+    Pressed,
+    Released,
+}
+
+impl KeyState {
+    pub fn is_pressed(self) -> bool {
+        self == KeyState::Pressed
+    }
+}
+
+/// Parts of a key event used to encode it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyInput<'a> {
+    pub logical_key: Key,
+    /// Key ignoring the modifiers, like `1` for `!`.
+    pub key_without_modifiers: Key,
+    pub location: KeyLocation,
+    pub state: KeyState,
+    pub repeat: bool,
+    /// Text produced by the key with all the modifiers applied.
+    pub text: Option<&'a str>,
+}
+
+/// Sequence sent for `key` pressed with exactly `mods` while the terminal
+/// has every mode of `mode` and none of `notmode` (nor vi mode).
+#[derive(Debug, Clone, Copy)]
+pub struct DefaultSequence {
+    pub key: NamedKey,
+    pub mods: KeyModifiers,
+    pub mode: Mode,
+    pub notmode: Mode,
+    pub sequence: &'static str,
+}
+
+const fn sequence(
+    key: NamedKey,
+    mods: KeyModifiers,
+    mode: Mode,
+    notmode: Mode,
+    sequence: &'static str,
+) -> DefaultSequence {
+    DefaultSequence {
+        key,
+        mods,
+        mode,
+        notmode,
+        sequence,
+    }
+}
+
+const NO_MODS: KeyModifiers = KeyModifiers::empty();
+const NO_MODE: Mode = Mode::empty();
+const ALL_KEYS_AS_ESC: Mode = Mode::REPORT_ALL_KEYS_AS_ESC;
+const LEGACY_KEYS: Mode =
+    Mode::REPORT_ALL_KEYS_AS_ESC.union(Mode::DISAMBIGUATE_ESC_CODES);
+const CURSOR_KEYS: Mode = Mode::REPORT_ALL_KEYS_AS_ESC.union(Mode::APP_CURSOR);
+
+/// Sequences of keys that aren't encoded by `encode_key`, the frontend
+/// uses them as default key bindings so they can be overridden.
+#[rustfmt::skip]
+pub const DEFAULT_SEQUENCES: &[DefaultSequence] = &[
+    sequence(NamedKey::Home, NO_MODS, Mode::APP_CURSOR, NO_MODE, "\x1bOH"),
+    sequence(NamedKey::End, NO_MODS, Mode::APP_CURSOR, NO_MODE, "\x1bOF"),
+    sequence(NamedKey::ArrowUp, NO_MODS, Mode::APP_CURSOR, NO_MODE, "\x1bOA"),
+    sequence(NamedKey::ArrowDown, NO_MODS, Mode::APP_CURSOR, NO_MODE, "\x1bOB"),
+    sequence(NamedKey::ArrowRight, NO_MODS, Mode::APP_CURSOR, NO_MODE, "\x1bOC"),
+    sequence(NamedKey::ArrowLeft, NO_MODS, Mode::APP_CURSOR, NO_MODE, "\x1bOD"),
+    sequence(NamedKey::ArrowUp, NO_MODS, NO_MODE, CURSOR_KEYS, "\x1b[A"),
+    sequence(NamedKey::ArrowDown, NO_MODS, NO_MODE, CURSOR_KEYS, "\x1b[B"),
+    sequence(NamedKey::ArrowRight, NO_MODS, NO_MODE, CURSOR_KEYS, "\x1b[C"),
+    sequence(NamedKey::ArrowLeft, NO_MODS, NO_MODE, CURSOR_KEYS, "\x1b[D"),
+    sequence(NamedKey::Insert, NO_MODS, NO_MODE, LEGACY_KEYS, "\x1b[2~"),
+    sequence(NamedKey::Delete, NO_MODS, NO_MODE, LEGACY_KEYS, "\x1b[3~"),
+    sequence(NamedKey::PageUp, NO_MODS, NO_MODE, LEGACY_KEYS, "\x1b[5~"),
+    sequence(NamedKey::PageDown, NO_MODS, NO_MODE, LEGACY_KEYS, "\x1b[6~"),
+    sequence(NamedKey::Backspace, NO_MODS, NO_MODE, ALL_KEYS_AS_ESC, "\x7f"),
+    sequence(NamedKey::Backspace, KeyModifiers::ALT, NO_MODE, ALL_KEYS_AS_ESC, "\x1b\x7f"),
+    sequence(NamedKey::Backspace, KeyModifiers::SHIFT, NO_MODE, ALL_KEYS_AS_ESC, "\x7f"),
+    sequence(NamedKey::F1, NO_MODS, NO_MODE, LEGACY_KEYS, "\x1bOP"),
+    sequence(NamedKey::F2, NO_MODS, NO_MODE, LEGACY_KEYS, "\x1bOQ"),
+    sequence(NamedKey::F3, NO_MODS, NO_MODE, LEGACY_KEYS, "\x1bOR"),
+    sequence(NamedKey::F4, NO_MODS, NO_MODE, LEGACY_KEYS, "\x1bOS"),
+    sequence(NamedKey::Tab, KeyModifiers::SHIFT, NO_MODE, ALL_KEYS_AS_ESC, "\x1b[Z"),
+    sequence(
+        NamedKey::Tab,
+        KeyModifiers::SHIFT.union(KeyModifiers::ALT),
+        NO_MODE,
+        ALL_KEYS_AS_ESC,
+        "\x1b\x1b[Z",
+    ),
+];
+
+/// Sequence of `DEFAULT_SEQUENCES` for the key, if any.
+pub fn default_sequence(
+    key: &Key,
+    mods: KeyModifiers,
+    mode: Mode,
+) -> Option<&'static str> {
+    let named = match key {
+        Key::Named(named) => *named,
+        _ => return None,
+    };
+
+    if mode.contains(Mode::VI) {
+        return None;
+    }
+
+    DEFAULT_SEQUENCES
+        .iter()
+        .find(|default| {
+            default.key == named
+                && default.mods == mods
+                && mode.contains(default.mode)
+                && !mode.intersects(default.notmode)
+        })
+        .map(|default| default.sequence)
+}
+
+/// Bytes written for a key press, either the text of the key or an
+/// escape sequence if the key has no text or the mode requires it.
+pub fn encode_key(key: &KeyInput, mods: KeyModifiers, mode: Mode) -> Vec<u8> {
+    let text = key.text.unwrap_or_default();
+    if should_build_sequence(key, text, mode, mods) {
+        return build_key_sequence(key, mods, mode);
+    }
+
+    let mut bytes = Vec::with_capacity(text.len() + 1);
+    if mods.contains(KeyModifiers::ALT) {
+        bytes.push(b'\x1b');
+    }
+
+    bytes.extend_from_slice(text.as_bytes());
+    bytes
+}
+
+/// Check whether we should try to build escape sequence for the key.
+fn should_build_sequence(
+    key: &KeyInput,
+    text: &str,
+    mode: Mode,
+    mods: KeyModifiers,
+) -> bool {
+    if mode.contains(Mode::REPORT_ALL_KEYS_AS_ESC) {
+        return true;
+    }
+
+    let disambiguate = mode.contains(Mode::DISAMBIGUATE_ESC_CODES)
+        && (key.logical_key == Key::Named(NamedKey::Escape)
+            || (!mods.is_empty() && mods != KeyModifiers::SHIFT)
+            || key.location == KeyLocation::Numpad);
+
+    match key.logical_key {
+        _ if disambiguate => true,
+        // Exclude all the named keys unless they have textual representation.
+        Key::Named(named) => named.to_text().is_none(),
+        _ => text.is_empty(),
+    }
+}
+
 #[inline(never)]
-pub fn build_key_sequence(key: &KeyEvent, mods: ModifiersState, mode: Mode) -> Vec<u8> {
+pub fn build_key_sequence(key: &KeyInput, mods: KeyModifiers, mode: Mode) -> Vec<u8> {
     let mut modifiers = mods.into();
 
     let kitty_seq = mode.intersects(
@@ -23,7 +317,7 @@ pub fn build_key_sequence(key: &KeyEvent, mods: ModifiersState, mode: Mode) -> V
     let kitty_encode_all = mode.contains(Mode::REPORT_ALL_KEYS_AS_ESC);
     // The default parameter is 1, so we can omit it.
     let kitty_event_type = mode.contains(Mode::REPORT_EVENT_TYPES)
-        && (key.repeat || key.state == ElementState::Released);
+        && (key.repeat || key.state == KeyState::Released);
 
     let context = SequenceBuilder {
         mode,
@@ -40,15 +334,15 @@ pub fn build_key_sequence(key: &KeyEvent, mods: ModifiersState, mode: Mode) -> V
     let text = if key.logical_key == Key::Named(NamedKey::Delete) {
         None
     } else {
-        key.text_with_all_modifiers()
+        key.text
     };
 
     #[cfg(not(target_os = "macos"))]
-    let text = key.text_with_all_modifiers();
+    let text = key.text;
 
     let associated_text = text.filter(|text| {
         mode.contains(Mode::REPORT_ASSOCIATED_TEXT)
-            && key.state != ElementState::Released
+            && key.state != KeyState::Released
             && !text.is_empty()
             && !is_control_character(text)
     });
@@ -80,8 +374,8 @@ pub fn build_key_sequence(key: &KeyEvent, mods: ModifiersState, mode: Mode) -> V
         payload.push(':');
         let event_type = match key.state {
             _ if key.repeat => '2',
-            ElementState::Pressed => '1',
-            ElementState::Released => '3',
+            KeyState::Pressed => '1',
+            KeyState::Released => '3',
         };
         payload.push(event_type);
     }
@@ -101,7 +395,7 @@ pub fn build_key_sequence(key: &KeyEvent, mods: ModifiersState, mode: Mode) -> V
     payload.into_bytes()
 }
 
-/// Helper to build escape sequence payloads from [`KeyEvent`].
+/// Helper to build escape sequence payloads from [`KeyInput`].
 pub struct SequenceBuilder {
     mode: Mode,
     /// The emitted sequence should follow the kitty keyboard protocol.
@@ -117,7 +411,7 @@ impl SequenceBuilder {
     /// Try building sequence from the event's emitting text.
     fn try_build_textual(
         &self,
-        key: &KeyEvent,
+        key: &KeyInput,
         associated_text: Option<&str>,
     ) -> Option<SequenceBase> {
         let character = match key.logical_key.as_ref() {
@@ -133,7 +427,7 @@ impl SequenceBuilder {
             let mut unicode_key_code = u32::from(base_character);
 
             // Try to get the base for keys which change based on modifier, like `1` for `!`.
-            match key.key_without_modifiers().as_ref() {
+            match key.key_without_modifiers.as_ref() {
                 Key::Character(unmodded) if alternate_key_code == unicode_key_code => {
                     unicode_key_code =
                         u32::from(unmodded.chars().next().unwrap_or(base_character));
@@ -164,7 +458,7 @@ impl SequenceBuilder {
     /// Try building from numpad key.
     ///
     /// `None` is returned when the key is neither known nor numpad.
-    fn try_build_numpad(&self, key: &KeyEvent) -> Option<SequenceBase> {
+    fn try_build_numpad(&self, key: &KeyInput) -> Option<SequenceBase> {
         if !self.kitty_seq || key.location != KeyLocation::Numpad {
             return None;
         }
@@ -208,7 +502,7 @@ impl SequenceBuilder {
 
     /// Try building from [`NamedKey`] using the kitty keyboard protocol encoding
     /// for functional keys.
-    fn try_build_named_kitty(&self, key: &KeyEvent) -> Option<SequenceBase> {
+    fn try_build_named_kitty(&self, key: &KeyInput) -> Option<SequenceBase> {
         let named = match key.logical_key {
             Key::Named(named) if self.kitty_seq => named,
             _ => return None,
@@ -265,7 +559,7 @@ impl SequenceBuilder {
     /// Try building from [`NamedKey`].
     fn try_build_named_normal(
         &self,
-        key: &KeyEvent,
+        key: &KeyInput,
         has_associated_text: bool,
     ) -> Option<SequenceBase> {
         let named = match key.logical_key {
@@ -322,7 +616,7 @@ impl SequenceBuilder {
     /// Try building escape from control characters (e.g. Enter) and modifiers.
     fn try_build_control_char_or_mod(
         &self,
-        key: &KeyEvent,
+        key: &KeyInput,
         mods: &mut SequenceModifiers,
     ) -> Option<SequenceBase> {
         if !self.kitty_encode_all && !self.kitty_seq {
@@ -441,13 +735,13 @@ impl SequenceModifiers {
     }
 }
 
-impl From<ModifiersState> for SequenceModifiers {
-    fn from(mods: ModifiersState) -> Self {
+impl From<KeyModifiers> for SequenceModifiers {
+    fn from(mods: KeyModifiers) -> Self {
         let mut modifiers = Self::empty();
-        modifiers.set(Self::SHIFT, mods.shift_key());
-        modifiers.set(Self::ALT, mods.alt_key());
-        modifiers.set(Self::CONTROL, mods.control_key());
-        modifiers.set(Self::SUPER, mods.super_key());
+        modifiers.set(Self::SHIFT, mods.contains(KeyModifiers::SHIFT));
+        modifiers.set(Self::ALT, mods.contains(KeyModifiers::ALT));
+        modifiers.set(Self::CONTROL, mods.contains(KeyModifiers::CONTROL));
+        modifiers.set(Self::SUPER, mods.contains(KeyModifiers::SUPER));
         modifiers
     }
 }
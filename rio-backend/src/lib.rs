@@ -4,7 +4,11 @@ pub mod config;
 pub mod crosswords;
 pub mod error;
 pub mod event;
+#[cfg(feature = "headless")]
+pub mod headless;
+pub mod keyboard;
 pub mod performer;
 pub mod selection;
 
+#[cfg(feature = "ui")]
 pub use sugarloaf;
@@ -1,6 +1,7 @@
-use crate::ansi::iterm2_image_protocol;
 use crate::ansi::CursorShape;
-use crate::ansi::{sixel, KeyboardModes, KeyboardModesApplyBehavior};
+#[cfg(feature = "ui")]
+use crate::ansi::{iterm2_image_protocol, sixel};
+use crate::ansi::{KeyboardModes, KeyboardModesApplyBehavior};
 use crate::config::colors::{AnsiColor, ColorRgb, NamedColor};
 use crate::crosswords::pos::{CharsetIndex, Column, Line, StandardCharset};
 use crate::crosswords::square::Hyperlink;
@@ -8,6 +9,7 @@ use cursor_icon::CursorIcon;
 use std::str::FromStr;
 use std::time::Duration;
 use std::time::Instant;
+#[cfg(feature = "ui")]
 use sugarloaf::GraphicData;
 use tracing::{debug, warn};
 
@@ -345,17 +347,23 @@ pub trait Handler {
     fn graphics_attribute(&mut self, _: u16, _: u16) {}
 
     /// Create a parser for Sixel data.
+    #[cfg(feature = "ui")]
     fn sixel_graphic_start(&mut self, _params: &Params) {}
+    #[cfg(feature = "ui")]
     fn is_sixel_graphic_active(&self) -> bool {
         false
     }
+    #[cfg(feature = "ui")]
     fn sixel_graphic_put(&mut self, _byte: u8) -> Result<(), sixel::Error> {
         Ok(())
     }
+    #[cfg(feature = "ui")]
     fn sixel_graphic_reset(&mut self) {}
+    #[cfg(feature = "ui")]
     fn sixel_graphic_finish(&mut self) {}
 
     /// Insert a new graphic item.
+    #[cfg(feature = "ui")]
     fn insert_graphic(&mut self, _data: GraphicData, _palette: Option<Vec<ColorRgb>>) {}
 
     /// Set hyperlink.
@@ -552,6 +560,7 @@ impl<U: Handler> copa::Perform for Performer<'_, U> {
         action: char,
     ) {
         match (action, intermediates) {
+            #[cfg(feature = "ui")]
             ('q', []) => {
                 self.handler.sixel_graphic_start(params);
             }
@@ -563,23 +572,27 @@ impl<U: Handler> copa::Perform for Performer<'_, U> {
     }
 
     fn put(&mut self, byte: u8) {
+        #[cfg(feature = "ui")]
         if self.handler.is_sixel_graphic_active() {
             if let Err(err) = self.handler.sixel_graphic_put(byte) {
                 tracing::warn!("Failed to parse Sixel data: {}", err);
                 self.handler.sixel_graphic_reset();
             }
-        } else {
-            debug!("[unhandled put] byte={:?}", byte);
+            return;
         }
+
+        debug!("[unhandled put] byte={:?}", byte);
     }
 
     #[inline]
     fn unhook(&mut self) {
+        #[cfg(feature = "ui")]
         if self.handler.is_sixel_graphic_active() {
             self.handler.sixel_graphic_finish();
-        } else {
-            debug!("[unhandled dcs_unhook]");
+            return;
         }
+
+        debug!("[unhandled dcs_unhook]");
     }
 
     fn osc_dispatch(&mut self, params: &[&[u8]], bell_terminated: bool) {
@@ -792,6 +805,7 @@ impl<U: Handler> copa::Perform for Performer<'_, U> {
 
             // OSC 1337 is not necessarily only used by iTerm2 protocol
             // OSC 1337 is equal to xterm OSC 50
+            #[cfg(feature = "ui")]
             b"1337" => {
                 if let Some(graphic) = iterm2_image_protocol::parse(params) {
                     self.handler.insert_graphic(graphic, None);
@@ -1,4 +1,6 @@
 pub mod handler;
+pub mod player;
+pub mod recorder;
 
 use crate::crosswords::Crosswords;
 use crate::event::sync::FairMutex;
@@ -8,9 +10,11 @@ use corcovado::channel;
 #[cfg(unix)]
 use corcovado::unix::UnixReady;
 use corcovado::{self, Events, PollOpt, Ready};
+use recorder::Recorder;
 use std::borrow::Cow;
 use std::collections::VecDeque;
 use std::io::{self, ErrorKind, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread::{Builder, JoinHandle};
 use std::time::Instant;
@@ -42,6 +46,7 @@ pub struct Machine<T: teletypewriter::EventedPty, U: EventListener> {
     event_proxy: U,
     window_id: WindowId,
     route_id: usize,
+    output_events: Arc<AtomicBool>,
 }
 
 #[derive(Default)]
@@ -49,9 +54,30 @@ pub struct State {
     write_list: VecDeque<Cow<'static, [u8]>>,
     writing: Option<Writing>,
     parser: handler::ParserProcessor,
+    recorder: Option<Recorder>,
 }
 
 impl State {
+    /// Writes to the session recording, it stops recording on failure.
+    #[inline]
+    fn record<F: FnOnce(&mut Recorder) -> io::Result<()>>(&mut self, f: F) {
+        if let Some(recorder) = &mut self.recorder {
+            if let Err(err) = f(recorder) {
+                error!("Error writing session recording: {}", err);
+                self.recorder = None;
+            }
+        }
+    }
+
+    #[inline]
+    fn stop_recording(&mut self) {
+        if let Some(mut recorder) = self.recorder.take() {
+            if let Err(err) = recorder.flush() {
+                error!("Error writing session recording: {}", err);
+            }
+        }
+    }
+
     #[inline]
     fn ensure_next(&mut self) {
         if self.writing.is_none() {
@@ -134,9 +160,17 @@ where
             event_proxy,
             window_id,
             route_id,
+            output_events: Arc::default(),
         })
     }
 
+    /// Sends `RioEvent::Output` for the PTY reads while `enabled` is set,
+    /// like when the output of the route is monitored.
+    pub fn with_output_events(mut self, enabled: Arc<AtomicBool>) -> Self {
+        self.output_events = enabled;
+        self
+    }
+
     #[inline]
     fn pty_read(&mut self, state: &mut State, buf: &mut [u8]) -> io::Result<()> {
         let mut unprocessed = 0;
@@ -151,7 +185,12 @@ where
             match self.pty.reader().read(&mut buf[unprocessed..]) {
                 // This is received on Windows/macOS when no more data is readable from the PTY.
                 Ok(0) if unprocessed == 0 => break,
-                Ok(got) => unprocessed += got,
+                Ok(got) => {
+                    state.record(|recorder| {
+                        recorder.output(&buf[unprocessed..unprocessed + got])
+                    });
+                    unprocessed += got;
+                }
                 Err(err) => match err.kind() {
                     ErrorKind::Interrupted | ErrorKind::WouldBlock => {
                         // Go back to mio if we're caught up on parsing and the PTY would block.
@@ -190,6 +229,16 @@ where
             }
         }
 
+        // Partial lines, like prompts, are only matched once per read.
+        if let Some(terminal) = terminal.as_mut() {
+            terminal.triggers_on_cursor_line();
+        }
+
+        if processed > 0 && self.output_events.load(Ordering::Relaxed) {
+            self.event_proxy
+                .send_event(RioEvent::Output(self.route_id), self.window_id);
+        }
+
         // Queue terminal redraw unless all processed bytes were synchronized.
         if state.parser.sync_bytes_count() < processed && processed > 0 {
             self.event_proxy
@@ -203,11 +252,20 @@ where
         while let Ok(msg) = self.receiver.try_recv() {
             match msg {
                 Msg::Input(input) => {
+                    state.record(|recorder| recorder.input(&input));
                     state.write_list.push_back(input);
                 }
                 Msg::Resize(window_size) => {
+                    state.record(|recorder| {
+                        recorder.resize(window_size.cols, window_size.rows)
+                    });
                     let _ = self.pty.set_winsize(window_size);
                 }
+                Msg::StartRecording(recorder) => {
+                    state.stop_recording();
+                    state.recorder = Some(recorder);
+                }
+                Msg::StopRecording => state.stop_recording(),
                 Msg::Shutdown => return false,
             }
         }
@@ -382,6 +440,9 @@ where
                     }
                 }
 
+                // Keeps the recording readable while the session runs.
+                state.record(|recorder| recorder.flush());
+
                 // Register write interest if necessary.
                 let mut interest = Ready::readable();
                 if state.needs_write() {
@@ -393,6 +454,8 @@ where
                     .unwrap();
             }
 
+            state.stop_recording();
+
             // The evented instances are not dropped here so deregister them explicitly.
             let _ = self.poll.deregister(&self.receiver);
             let _ = self.pty.deregister(&self.poll);
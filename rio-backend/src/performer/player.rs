@@ -0,0 +1,550 @@
+// Player replays recorded sessions into a terminal, it supports
+// asciicast v2 (https://docs.asciinema.org/manual/asciicast/v2/)
+// and typescripts created by script(1), optionally with a timing file.
+
+use crate::crosswords::{Crosswords, CrosswordsSize};
+use crate::event::sync::FairMutex;
+use crate::event::{EventListener, RioEvent, WindowId};
+use crate::performer::handler::{Handler, ParserProcessor};
+use crate::performer::spawn_named;
+use std::io;
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const MIN_SPEED: f64 = 0.125;
+const MAX_SPEED: f64 = 16.;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    /// Time since the start of the recording.
+    pub time: Duration,
+    pub data: Vec<u8>,
+    /// Columns and lines the terminal is resized to before the data.
+    pub resize: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Recording {
+    /// Terminal size declared by the recording, if any.
+    pub columns: Option<usize>,
+    pub lines: Option<usize>,
+    pub frames: Vec<Frame>,
+}
+
+impl Recording {
+    /// Loads an asciicast file or a typescript (with an optional timing file).
+    pub fn load(path: &Path, timing: Option<&Path>) -> io::Result<Recording> {
+        let data = std::fs::read(path)?;
+        let timing = match timing {
+            Some(timing) => Some(std::fs::read_to_string(timing)?),
+            None => None,
+        };
+
+        let is_asciicast = data
+            .iter()
+            .find(|byte| !byte.is_ascii_whitespace())
+            .is_some_and(|byte| *byte == b'{');
+        if is_asciicast && timing.is_none() {
+            let content = String::from_utf8_lossy(&data);
+            return Recording::from_asciicast(&content)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err));
+        }
+
+        Ok(Recording::from_typescript(&data, timing.as_deref()))
+    }
+
+    pub fn from_asciicast(content: &str) -> Result<Recording, String> {
+        let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+        let header: serde_json::Value = match lines.next() {
+            Some(header) => {
+                serde_json::from_str(header).map_err(|err| err.to_string())?
+            }
+            None => return Err(String::from("empty asciicast file")),
+        };
+
+        if header["version"].as_u64() != Some(2) {
+            return Err(String::from("only asciicast version 2 is supported"));
+        }
+
+        let mut recording = Recording {
+            columns: header["width"].as_u64().map(|width| width as usize),
+            lines: header["height"].as_u64().map(|height| height as usize),
+            frames: Vec::new(),
+        };
+
+        for line in lines {
+            let (time, code, data): (f64, String, String) =
+                serde_json::from_str(line).map_err(|err| err.to_string())?;
+            // Input and marker events don't change the output
+            let time = Duration::from_secs_f64(time.max(0.));
+            match code.as_str() {
+                "o" => recording.frames.push(Frame {
+                    time,
+                    data: data.into_bytes(),
+                    resize: None,
+                }),
+                "r" => {
+                    if let Some(size) = parse_size(&data) {
+                        recording.frames.push(Frame {
+                            time,
+                            data: Vec::new(),
+                            resize: Some(size),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(recording)
+    }
+
+    /// Typescript without timing is replayed at once. Timing files can use
+    /// the classic format (`delay bytes`) or the advanced one (`O delay bytes`).
+    pub fn from_typescript(data: &[u8], timing: Option<&str>) -> Recording {
+        // Skip the "Script started on ..." header
+        let mut offset = 0;
+        if data.starts_with(b"Script started") {
+            offset = data
+                .iter()
+                .position(|byte| *byte == b'\n')
+                .map(|position| position + 1)
+                .unwrap_or(data.len());
+        }
+
+        let timing = match timing {
+            Some(timing) => timing,
+            None => {
+                return Recording {
+                    frames: vec![Frame {
+                        time: Duration::ZERO,
+                        data: data[offset..].to_vec(),
+                        resize: None,
+                    }],
+                    ..Recording::default()
+                }
+            }
+        };
+
+        let mut recording = Recording::default();
+        let mut time = Duration::ZERO;
+        for line in timing.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (is_output, delay, length) = match fields.as_slice() {
+                [delay, length] => (true, *delay, *length),
+                [kind, delay, length, ..] => (*kind == "O", *delay, *length),
+                _ => continue,
+            };
+
+            if let Ok(delay) = delay.parse::<f64>() {
+                time += Duration::from_secs_f64(delay.max(0.));
+            }
+
+            // Input, signals and headers are not part of the output log
+            let length = match length.parse::<usize>() {
+                Ok(length) if is_output => length,
+                _ => continue,
+            };
+
+            let end = (offset + length).min(data.len());
+            recording.frames.push(Frame {
+                time,
+                data: data[offset..end].to_vec(),
+                resize: None,
+            });
+            offset = end;
+        }
+
+        recording
+    }
+
+    /// Terminal size declared by the header, if any.
+    #[inline]
+    pub fn size(&self) -> Option<(usize, usize)> {
+        match (self.columns, self.lines) {
+            (Some(columns), Some(lines)) if columns > 0 && lines > 0 => {
+                Some((columns, lines))
+            }
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        self.frames
+            .last()
+            .map(|frame| frame.time)
+            .unwrap_or_default()
+    }
+}
+
+/// Parses the `COLSxROWS` data of resize events.
+fn parse_size(data: &str) -> Option<(usize, usize)> {
+    let (columns, lines) = data.split_once('x')?;
+    let columns = columns.trim().parse().ok()?;
+    let lines = lines.trim().parse().ok()?;
+    (columns > 0 && lines > 0).then_some((columns, lines))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlayerMsg {
+    TogglePause,
+    /// Moves the playback by the amount of seconds.
+    Seek(f64),
+    /// Pauses and plays the next frame.
+    Step,
+    /// Multiplies the playback speed.
+    Speed(f64),
+    Shutdown,
+}
+
+pub struct Player<U: EventListener> {
+    sender: mpsc::Sender<PlayerMsg>,
+    receiver: mpsc::Receiver<PlayerMsg>,
+    recording: Recording,
+    terminal: Arc<FairMutex<Crosswords<U>>>,
+    parser: ParserProcessor,
+    event_proxy: U,
+    window_id: WindowId,
+    route_id: usize,
+    // Index of the next frame to be played.
+    position: usize,
+    // Recording time played when the playback was (re)started.
+    time: Duration,
+    // None while paused.
+    resumed_at: Option<Instant>,
+    speed: f64,
+}
+
+impl<U> Player<U>
+where
+    U: EventListener + Send + 'static,
+{
+    pub fn new(
+        recording: Recording,
+        terminal: Arc<FairMutex<Crosswords<U>>>,
+        event_proxy: U,
+        window_id: WindowId,
+        route_id: usize,
+    ) -> Player<U> {
+        let (sender, receiver) = mpsc::channel();
+        // Replayed with the size it was recorded with
+        if let Some((columns, lines)) = recording.size() {
+            terminal.lock().resize(CrosswordsSize::new(columns, lines));
+        }
+
+        Player {
+            sender,
+            receiver,
+            recording,
+            terminal,
+            parser: ParserProcessor::new(),
+            event_proxy,
+            window_id,
+            route_id,
+            position: 0,
+            time: Duration::ZERO,
+            resumed_at: Some(Instant::now()),
+            speed: 1.,
+        }
+    }
+
+    pub fn channel(&self) -> mpsc::Sender<PlayerMsg> {
+        self.sender.clone()
+    }
+
+    #[inline]
+    fn current_time(&self) -> Duration {
+        match self.resumed_at {
+            Some(resumed_at) => self.time + resumed_at.elapsed().mul_f64(self.speed),
+            None => self.time,
+        }
+    }
+
+    #[inline]
+    fn is_finished(&self) -> bool {
+        self.position >= self.recording.frames.len()
+    }
+
+    /// Feeds every frame until `time` through the parser.
+    fn play_until(&mut self, time: Duration) {
+        let frames = &self.recording.frames;
+        if self.position >= frames.len() || frames[self.position].time > time {
+            return;
+        }
+
+        let mut terminal = self.terminal.lock();
+        while self.position < frames.len() && frames[self.position].time <= time {
+            if let Some((columns, lines)) = frames[self.position].resize {
+                terminal.resize(CrosswordsSize::new(columns, lines));
+            }
+            for byte in &frames[self.position].data {
+                self.parser.advance(&mut *terminal, *byte);
+            }
+            self.position += 1;
+        }
+        drop(terminal);
+
+        self.event_proxy
+            .send_event(RioEvent::RenderRoute(self.route_id), self.window_id);
+    }
+
+    /// Clears the terminal so the recording can be played again from the start.
+    fn rewind(&mut self) {
+        self.parser = ParserProcessor::new();
+        let mut terminal = self.terminal.lock();
+        terminal.reset_state();
+        if let Some((columns, lines)) = self.recording.size() {
+            terminal.resize(CrosswordsSize::new(columns, lines));
+        }
+        drop(terminal);
+        self.position = 0;
+        self.time = Duration::ZERO;
+    }
+
+    fn seek(&mut self, target: Duration) {
+        if target < self.current_time() {
+            self.rewind();
+        }
+
+        self.play_until(target);
+        self.time = target;
+        if self.resumed_at.is_some() {
+            self.resumed_at = Some(Instant::now());
+        }
+    }
+
+    /// Returns false once the player should stop.
+    fn handle(&mut self, msg: PlayerMsg) -> bool {
+        match msg {
+            PlayerMsg::TogglePause => {
+                if self.resumed_at.is_some() {
+                    self.time = self.current_time();
+                    self.resumed_at = None;
+                } else {
+                    if self.is_finished() {
+                        self.rewind();
+                        self.event_proxy.send_event(
+                            RioEvent::RenderRoute(self.route_id),
+                            self.window_id,
+                        );
+                    }
+                    self.resumed_at = Some(Instant::now());
+                }
+            }
+            PlayerMsg::Seek(seconds) => {
+                let current = self.current_time().as_secs_f64();
+                let target = (current + seconds)
+                    .clamp(0., self.recording.duration().as_secs_f64());
+                self.seek(Duration::from_secs_f64(target));
+            }
+            PlayerMsg::Step => {
+                self.resumed_at = None;
+                if let Some(frame) = self.recording.frames.get(self.position) {
+                    let time = frame.time;
+                    self.play_until(time);
+                    self.time = time;
+                }
+            }
+            PlayerMsg::Speed(factor) => {
+                self.time = self.current_time();
+                self.speed = (self.speed * factor).clamp(MIN_SPEED, MAX_SPEED);
+                if self.resumed_at.is_some() {
+                    self.resumed_at = Some(Instant::now());
+                }
+            }
+            PlayerMsg::Shutdown => return false,
+        }
+
+        true
+    }
+
+    pub fn spawn(mut self) {
+        spawn_named("Player", move || loop {
+            self.play_until(self.current_time());
+
+            if self.resumed_at.is_some() && self.is_finished() {
+                self.time = self.recording.duration();
+                self.resumed_at = None;
+            }
+
+            let msg = match self.resumed_at {
+                Some(_) => {
+                    let next = self.recording.frames[self.position].time;
+                    let wait =
+                        next.saturating_sub(self.current_time()).div_f64(self.speed);
+                    match self.receiver.recv_timeout(wait) {
+                        Ok(msg) => msg,
+                        Err(RecvTimeoutError::Timeout) => continue,
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+                None => match self.receiver.recv() {
+                    Ok(msg) => msg,
+                    Err(_) => break,
+                },
+            };
+
+            if !self.handle(msg) {
+                break;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ansi::CursorShape;
+    use crate::crosswords::grid::Dimensions;
+    use crate::crosswords::pos::{Column, Line};
+    use crate::event::VoidListener;
+
+    #[test]
+    fn test_player_seek_and_step() {
+        let window_id = WindowId::from(0);
+        let terminal = Crosswords::new(
+            CrosswordsSize::new(10, 2),
+            CursorShape::Block,
+            VoidListener {},
+            window_id,
+            0,
+        );
+        let terminal = Arc::new(FairMutex::new(terminal));
+        let frame = |millis: u64, data: &[u8]| Frame {
+            time: Duration::from_millis(millis),
+            data: data.to_vec(),
+            resize: None,
+        };
+        let recording = Recording {
+            frames: vec![frame(0, b"a"), frame(1000, b"b"), frame(2000, b"c")],
+            ..Recording::default()
+        };
+
+        let mut player =
+            Player::new(recording, terminal.clone(), VoidListener {}, window_id, 0);
+        let cell = |column: usize| terminal.lock().grid[Line(0)][Column(column)].c;
+
+        assert!(player.handle(PlayerMsg::TogglePause));
+        assert!(player.resumed_at.is_none());
+
+        assert!(player.handle(PlayerMsg::Step));
+        assert_eq!(player.position, 1);
+        assert_eq!(cell(0), 'a');
+
+        assert!(player.handle(PlayerMsg::Seek(1.5)));
+        assert_eq!(player.position, 2);
+        assert_eq!(cell(1), 'b');
+
+        // Seeking backwards replays the recording from the start
+        assert!(player.handle(PlayerMsg::Seek(-1.5)));
+        assert_eq!(player.position, 1);
+        assert_eq!(cell(0), 'a');
+        assert_eq!(cell(1), ' ');
+
+        // Seeking is limited by the recording duration
+        assert!(player.handle(PlayerMsg::Seek(10.)));
+        assert!(player.is_finished());
+        assert_eq!(player.current_time(), Duration::from_secs(2));
+
+        assert!(player.handle(PlayerMsg::Speed(64.)));
+        assert_eq!(player.speed, MAX_SPEED);
+
+        // Resuming a finished playback starts it again
+        assert!(player.handle(PlayerMsg::TogglePause));
+        assert_eq!(player.position, 0);
+        assert!(player.resumed_at.is_some());
+
+        assert!(!player.handle(PlayerMsg::Shutdown));
+    }
+
+    #[test]
+    fn test_player_resize() {
+        let window_id = WindowId::from(0);
+        let terminal = Crosswords::new(
+            CrosswordsSize::new(10, 2),
+            CursorShape::Block,
+            VoidListener {},
+            window_id,
+            0,
+        );
+        let terminal = Arc::new(FairMutex::new(terminal));
+        let recording = Recording {
+            columns: Some(20),
+            lines: Some(4),
+            frames: vec![Frame {
+                time: Duration::from_secs(1),
+                data: vec![],
+                resize: Some((30, 6)),
+            }],
+        };
+        let size = || {
+            let terminal = terminal.lock();
+            (terminal.grid.columns(), terminal.grid.screen_lines())
+        };
+
+        let mut player =
+            Player::new(recording, terminal.clone(), VoidListener {}, window_id, 0);
+        assert_eq!(size(), (20, 4));
+
+        assert!(player.handle(PlayerMsg::Step));
+        assert_eq!(size(), (30, 6));
+
+        // Rewinding goes back to the size of the header
+        assert!(player.handle(PlayerMsg::Seek(-1.)));
+        assert_eq!(size(), (20, 4));
+    }
+
+    #[test]
+    fn test_recording_from_asciicast() {
+        let recording = Recording::from_asciicast(
+            "{\"version\": 2, \"width\": 80, \"height\": 24, \"timestamp\": 1}\n\
+             [0.5, \"o\", \"ls\\r\\n\"]\n\
+             [0.7, \"i\", \"x\"]\n\
+             [1, \"r\", \"100x30\"]\n\
+             [1.1, \"r\", \"x\"]\n\
+             [1.25, \"o\", \"\\u001b[1mé\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(recording.columns, Some(80));
+        assert_eq!(recording.lines, Some(24));
+        assert_eq!(recording.size(), Some((80, 24)));
+        assert_eq!(recording.frames.len(), 3);
+        assert_eq!(recording.frames[0].time, Duration::from_millis(500));
+        assert_eq!(recording.frames[0].data, b"ls\r\n");
+        assert_eq!(recording.frames[1].resize, Some((100, 30)));
+        assert!(recording.frames[1].data.is_empty());
+        assert_eq!(recording.frames[2].data, "\x1b[1mé".as_bytes());
+        assert_eq!(recording.duration(), Duration::from_millis(1250));
+
+        assert!(Recording::from_asciicast("{\"version\": 1}").is_err());
+        assert!(Recording::from_asciicast("").is_err());
+    }
+
+    #[test]
+    fn test_recording_from_typescript() {
+        let data = b"Script started on 2024-01-01\nhello world";
+
+        let recording = Recording::from_typescript(data, None);
+        assert_eq!(recording.frames.len(), 1);
+        assert_eq!(recording.frames[0].data, b"hello world");
+
+        let recording = Recording::from_typescript(data, Some("0.5 6\n1.0 5\n"));
+        assert_eq!(recording.frames.len(), 2);
+        assert_eq!(recording.frames[0].data, b"hello ");
+        assert_eq!(recording.frames[1].time, Duration::from_millis(1500));
+        assert_eq!(recording.frames[1].data, b"world");
+
+        let recording = Recording::from_typescript(
+            data,
+            Some("H 0 START_TIME 2024\nO 0.25 5\nI 0.1 3\nO 0.25 100\n"),
+        );
+        assert_eq!(recording.frames.len(), 2);
+        assert_eq!(recording.frames[0].data, b"hello");
+        assert_eq!(recording.frames[1].time, Duration::from_millis(600));
+        assert_eq!(recording.frames[1].data, b" world");
+    }
+}
@@ -0,0 +1,153 @@
+// Recorder writes sessions using asciicast v2 format
+// https://docs.asciinema.org/manual/asciicast/v2/
+
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize)]
+struct Header {
+    version: u8,
+    width: usize,
+    height: usize,
+    timestamp: u64,
+}
+
+#[derive(Debug)]
+pub struct Recorder<W: Write = BufWriter<File>> {
+    writer: W,
+    started: Instant,
+    record_input: bool,
+    // Bytes of an incomplete UTF-8 sequence, kept until the next read.
+    pending: Vec<u8>,
+}
+
+impl Recorder {
+    pub fn create(
+        path: &Path,
+        columns: usize,
+        lines: usize,
+        record_input: bool,
+    ) -> io::Result<Recorder> {
+        let file = File::create(path)?;
+        Recorder::new(BufWriter::new(file), columns, lines, record_input)
+    }
+}
+
+impl<W: Write> Recorder<W> {
+    pub fn new(
+        mut writer: W,
+        columns: usize,
+        lines: usize,
+        record_input: bool,
+    ) -> io::Result<Recorder<W>> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        let header = Header {
+            version: 2,
+            width: columns,
+            height: lines,
+            timestamp,
+        };
+        serde_json::to_writer(&mut writer, &header)?;
+        writer.write_all(b"\n")?;
+
+        Ok(Recorder {
+            writer,
+            started: Instant::now(),
+            record_input,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Bytes read from the PTY.
+    pub fn output(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.pending.extend_from_slice(bytes);
+        let valid_up_to = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            // The sequence was split between reads
+            Err(err) if err.error_len().is_none() => err.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+
+        let rest = self.pending.split_off(valid_up_to);
+        let data = String::from_utf8_lossy(&self.pending).into_owned();
+        self.pending = rest;
+
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        self.write_event("o", &data)
+    }
+
+    /// Bytes written to the PTY, ignored unless input recording is enabled.
+    pub fn input(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if !self.record_input || bytes.is_empty() {
+            return Ok(());
+        }
+
+        self.write_event("i", &String::from_utf8_lossy(bytes))
+    }
+
+    pub fn resize(&mut self, columns: u16, lines: u16) -> io::Result<()> {
+        self.write_event("r", &format!("{columns}x{lines}"))
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    fn write_event(&mut self, code: &str, data: &str) -> io::Result<()> {
+        // Microseconds are enough for players and keep the lines short
+        let time = (self.started.elapsed().as_secs_f64() * 1e6).round() / 1e6;
+        serde_json::to_writer(&mut self.writer, &(time, code, data))?;
+        self.writer.write_all(b"\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::performer::player::Recording;
+
+    #[test]
+    fn test_recorder_events() {
+        let mut recorder = Recorder::new(Vec::new(), 80, 24, false).unwrap();
+        recorder.output(b"ls\r\n\x1b[1m\"a\"").unwrap();
+        // Input is only written when enabled
+        recorder.input(b"ls").unwrap();
+        recorder.resize(100, 30).unwrap();
+
+        // "é" split across two reads
+        recorder.output(&[b'x', 0xc3]).unwrap();
+        recorder.output(&[0xa9]).unwrap();
+
+        let content = String::from_utf8(recorder.writer).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with("{\"version\":2,\"width\":80,\"height\":24,"));
+        assert!(lines[1].ends_with(",\"o\",\"ls\\r\\n\\u001b[1m\\\"a\\\"\"]"));
+        assert!(lines[2].ends_with(",\"r\",\"100x30\"]"));
+        assert!(lines[3].ends_with(",\"o\",\"x\"]"));
+        assert!(lines[4].ends_with(",\"o\",\"é\"]"));
+
+        let recording = Recording::from_asciicast(&content).unwrap();
+        assert_eq!((recording.columns, recording.lines), (Some(80), Some(24)));
+        assert_eq!(recording.frames[0].data, b"ls\r\n\x1b[1m\"a\"");
+
+        let mut recorder = Recorder::new(Vec::new(), 80, 24, true).unwrap();
+        recorder.input(b"ls\r").unwrap();
+        let content = String::from_utf8(recorder.writer).unwrap();
+        assert!(content
+            .lines()
+            .nth(1)
+            .unwrap()
+            .ends_with(",\"i\",\"ls\\r\"]"));
+    }
+}
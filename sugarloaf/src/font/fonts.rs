@@ -2,6 +2,7 @@ use crate::font::DEFAULT_FONT_FAMILY;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum SugarloafFontStyle {
     #[default]
     Normal,
@@ -9,6 +10,7 @@ pub enum SugarloafFontStyle {
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum SugarloafFontWidth {
     UltraCondensed,
     ExtraCondensed,
@@ -23,14 +25,17 @@ pub enum SugarloafFontWidth {
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SugarloafFont {
     #[serde(default = "default_font_family")]
     pub family: String,
     #[serde(default = "Option::default")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub weight: Option<u16>,
     #[serde(default = "SugarloafFontStyle::default")]
     pub style: SugarloafFontStyle,
     #[serde(default = "Option::default")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub width: Option<SugarloafFontWidth>,
 }
 
@@ -104,14 +109,17 @@ pub fn default_font_bold_italic() -> SugarloafFont {
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SugarloafFonts {
     #[serde(default = "default_font_size")]
     pub size: f32,
     #[serde(default = "default_font_hinting")]
     pub hinting: bool,
     #[serde(default = "Option::default")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub features: Option<Vec<String>>,
     #[serde(default = "Option::default")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub family: Option<String>,
     #[serde(default = "default_font_regular")]
     pub regular: SugarloafFont,
@@ -122,10 +130,13 @@ pub struct SugarloafFonts {
     #[serde(default = "default_font_italic")]
     pub italic: SugarloafFont,
     #[serde(default = "Option::default")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub ui: Option<SugarloafFont>,
     #[serde(default = "Option::default")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub emoji: Option<SugarloafFont>,
     #[serde(default = "Vec::default")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub extras: Vec<SugarloafFont>,
 }
 
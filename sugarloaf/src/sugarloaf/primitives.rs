@@ -16,12 +16,15 @@ pub enum SugarCursor {
 }
 
 #[derive(Default, Clone, Deserialize, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ImageProperties {
     #[serde(default = "String::default")]
     pub path: String,
     #[serde(default = "Option::default")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub width: Option<f32>,
     #[serde(default = "Option::default")]
+    #[cfg_attr(feature = "schemars", schemars(default))]
     pub height: Option<f32>,
     #[serde(default = "f32::default")]
     pub x: f32,
@@ -400,6 +400,19 @@ pub fn create_pty_with_spawn(
     working_directory: &Option<String>,
     columns: u16,
     rows: u16,
+) -> Result<Pty, Error> {
+    create_pty_with_spawn_and_env(shell, args, working_directory, &[], columns, rows)
+}
+
+/// Same as [`create_pty_with_spawn`], with `env` added to the environment of
+/// the shell.
+pub fn create_pty_with_spawn_and_env(
+    shell: &str,
+    args: Vec<String>,
+    working_directory: &Option<String>,
+    env: &[(String, String)],
+    columns: u16,
+    rows: u16,
 ) -> Result<Pty, Error> {
     #[cfg(not(any(target_os = "macos", target_os = "freebsd")))]
     let mut is_controling_terminal = true;
@@ -475,6 +488,9 @@ pub fn create_pty_with_spawn(
             }
 
             with_args.push("--env=TERM_PROGRAM=rio".to_string());
+            for (key, value) in env {
+                with_args.push(format!("--env={key}={value}"));
+            }
 
             let output = std::process::Command::new("flatpak-spawn")
                 .args(["--host", "sh", "-c", "echo $SHELL"])
@@ -502,6 +518,7 @@ pub fn create_pty_with_spawn(
 
     builder.env("USER", user.user);
     builder.env("HOME", user.home);
+    builder.envs(env.iter().map(|(key, value)| (key, value)));
 
     unsafe {
         builder.pre_exec(move || {
@@ -582,6 +599,17 @@ pub fn create_pty_with_spawn(
 /// It returns two [`Pty`] along with respective process name [`String`] and process id (`libc::pid_`)
 ///
 pub fn create_pty_with_fork(shell: &str, columns: u16, rows: u16) -> Result<Pty, Error> {
+    create_pty_with_fork_and_env(shell, &[], columns, rows)
+}
+
+/// Same as [`create_pty_with_fork`], with `env` added to the environment of
+/// the shell.
+pub fn create_pty_with_fork_and_env(
+    shell: &str,
+    env: &[(String, String)],
+    columns: u16,
+    rows: u16,
+) -> Result<Pty, Error> {
     let mut main = 0;
     let winsize = Winsize {
         ws_row: rows as libc::c_ushort,
@@ -608,6 +636,17 @@ pub fn create_pty_with_fork(shell: &str, columns: u16, rows: u16) -> Result<Pty,
 
     tracing::info!("fork {:?}", shell_program);
 
+    // Built before forking, the child only hands them to setenv.
+    let env: Vec<(CString, CString)> = env
+        .iter()
+        .filter_map(|(key, value)| {
+            Some((
+                CString::new(key.as_str()).ok()?,
+                CString::new(value.as_str()).ok()?,
+            ))
+        })
+        .collect();
+
     match unsafe {
         forkpty(
             &mut main as *mut _,
@@ -617,6 +656,11 @@ pub fn create_pty_with_fork(shell: &str, columns: u16, rows: u16) -> Result<Pty,
         )
     } {
         0 => {
+            for (key, value) in &env {
+                unsafe {
+                    libc::setenv(key.as_ptr(), value.as_ptr(), 1);
+                }
+            }
             default_shell_command(shell_program);
             Err(Error::new(
                 ErrorKind::Other,